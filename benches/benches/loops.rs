@@ -0,0 +1,88 @@
+//! Benchmarks for loops in [quote!], which are commonly used to emit large
+//! numbers of repeated items.
+//!
+//! `previous` and `previous_join` are hand-written copies of what [quote!]
+//! used to expand loops to, before the receiver was borrowed once and room was
+//! reserved for the remaining iterations. They don't go through [quote!], so
+//! they stay fixed baselines for `quote` and `quote_join` respectively, which
+//! produce the same items.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use genco::prelude::*;
+use genco::tokens::ItemStr;
+
+const ITERATIONS: usize = 50_000;
+
+fn loops(c: &mut Criterion) {
+    let names = (0..ITERATIONS)
+        .map(|n| format!("field{}", n))
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("loops");
+    group.throughput(Throughput::Elements(ITERATIONS as u64));
+
+    group.bench_function("previous", |b| {
+        b.iter(|| {
+            let mut tokens = rust::Tokens::new();
+            let t = &mut tokens;
+
+            for name in black_box(&names) {
+                t.push();
+                t.append(ItemStr::Static("pub"));
+                t.space();
+                t.append(name);
+                t.append(ItemStr::Static(":"));
+                t.space();
+                t.append(ItemStr::Static("u32,"));
+            }
+
+            tokens
+        })
+    });
+
+    group.bench_function("quote", |b| {
+        b.iter(|| -> rust::Tokens {
+            quote! {
+                $(for name in black_box(&names) =>
+                    $['\r']pub $name: u32,
+                )
+            }
+        })
+    });
+
+    group.bench_function("previous_join", |b| {
+        b.iter(|| {
+            let mut tokens = rust::Tokens::new();
+            let t = &mut tokens;
+            let mut it = black_box(&names).iter().peekable();
+
+            while let Some(name) = it.next() {
+                t.append(ItemStr::Static("pub"));
+                t.space();
+                t.append(name);
+                t.append(ItemStr::Static(":"));
+                t.space();
+                t.append(ItemStr::Static("u32,"));
+
+                if it.peek().is_some() {
+                    t.push();
+                }
+            }
+
+            tokens
+        })
+    });
+
+    group.bench_function("quote_join", |b| {
+        b.iter(|| -> rust::Tokens {
+            quote! {
+                $(for name in black_box(&names) join ($['\r']) => pub $name: u32,)
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, loops);
+criterion_main!(benches);
@@ -0,0 +1,2 @@
+//! Benchmarks for genco, which are found in the `benches` directory and run
+//! through `cargo bench` from this directory.
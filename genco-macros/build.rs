@@ -4,6 +4,7 @@ use std::str;
 
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(proc_macro_span)");
 
     let version = rustc_version().unwrap_or(RustcVersion {
         minor: u32::MAX,
@@ -84,8 +84,8 @@ impl q::ToTokens for Name {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
             Name::Const(t) => t.to_tokens(tokens),
-            Name::Ident(_, name) => name.to_tokens(tokens),
-            Name::Char(_, c) => c.to_tokens(tokens),
+            Name::Ident(ident, _) => ident.to_tokens(tokens),
+            Name::Char(c, _) => c.to_tokens(tokens),
         }
     }
 }
@@ -94,7 +94,9 @@ impl q::ToTokens for Name {
 pub(crate) enum ControlKind {
     Space,
     Push,
+    SoftPush,
     Line,
+    Flush,
 }
 
 #[derive(Debug)]
@@ -157,6 +159,20 @@ pub(crate) enum Ast {
     Eval {
         expr: syn::Expr,
     },
+    /// An expression evaluating to a string which is emitted verbatim.
+    Raw {
+        expr: syn::Expr,
+    },
+    /// An expression evaluating to a string whose lines are emitted with their
+    /// leading whitespace preserved relative to the current indentation.
+    Verbatim {
+        expr: syn::Expr,
+    },
+    /// An expression which is evaluated like [Ast::Eval], but whose output
+    /// is logged at runtime when debugging is enabled.
+    Debug {
+        expr: syn::Expr,
+    },
     /// A bound scope.
     Scope {
         binding: Option<syn::Ident>,
@@ -174,6 +190,27 @@ pub(crate) enum Ast {
         /// The inner stream processed.
         stream: TokenStream,
     },
+    /// Existing fragments joined by a separator, like `$(spread <expr> with
+    /// <separator>)`.
+    Spread {
+        /// Expression being bound to an iterator.
+        expr: Box<syn::Expr>,
+        /// Expression for the separator, which is only evaluated once.
+        separator: Box<syn::Expr>,
+    },
+    /// A block whose tokens are concatenated without any spacing.
+    Concat {
+        /// The inner stream processed.
+        stream: TokenStream,
+    },
+    /// A block which is only emitted the first time its key is seen while
+    /// formatting.
+    Once {
+        /// Expression evaluating to the key of the block.
+        key: syn::Expr,
+        /// The inner stream processed.
+        stream: TokenStream,
+    },
     Condition {
         /// Expression being use as a condition.
         condition: syn::Expr,
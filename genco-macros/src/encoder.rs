@@ -30,6 +30,8 @@ pub(crate) struct Encoder<'a> {
     last: Option<Cursor>,
     /// Which column the last line start on.
     last_start_column: Option<usize>,
+    /// The last source line which was marked in the output.
+    source_line: Option<usize>,
     /// Indentation columns.
     indents: Vec<(usize, Option<Span>)>,
     /// Indicates if the encoder has encountered a string which requires eval
@@ -51,6 +53,7 @@ impl<'a> Encoder<'a> {
             output: TokenStream::new(),
             last: None,
             last_start_column: None,
+            source_line: None,
             indents: Vec::new(),
             requirements: Requirements::default(),
         }
@@ -88,6 +91,15 @@ impl<'a> Encoder<'a> {
             Ast::Eval { expr, .. } => {
                 self.encode_eval(expr);
             }
+            Ast::Raw { expr } => {
+                self.encode_raw(expr);
+            }
+            Ast::Verbatim { expr } => {
+                self.encode_verbatim(expr);
+            }
+            Ast::Debug { expr } => {
+                self.encode_debug(expr);
+            }
             Ast::Loop {
                 pattern,
                 expr,
@@ -103,6 +115,15 @@ impl<'a> Encoder<'a> {
             Ast::DelimiterClose { delimiter, .. } => {
                 self.encode_close_delimiter(delimiter);
             }
+            Ast::Spread { expr, separator } => {
+                self.encode_spread(*expr, *separator);
+            }
+            Ast::Concat { stream } => {
+                self.encode_concat(stream);
+            }
+            Ast::Once { key, stream } => {
+                self.encode_once(key, stream);
+            }
             Ast::Condition {
                 condition,
                 then_branch,
@@ -133,6 +154,10 @@ impl<'a> Encoder<'a> {
             self.tokenize_whitespace(from, next.start, Some(next.span))?;
         }
 
+        if cfg!(feature = "sourcemap") && self.source_line != Some(next.start.line) {
+            self.encode_source(next);
+        }
+
         // Assign the current cursor to the next item.
         // This will then be used to make future indentation decisions.
         self.last = Some(next);
@@ -189,10 +214,18 @@ impl<'a> Encoder<'a> {
                 self.output
                     .extend(q::quote_spanned!(control.span => #receiver.push();));
             }
+            ControlKind::SoftPush => {
+                self.output
+                    .extend(q::quote_spanned!(control.span => #receiver.soft_push();));
+            }
             ControlKind::Line => {
                 self.output
                     .extend(q::quote_spanned!(control.span => #receiver.line();));
             }
+            ControlKind::Flush => {
+                self.output
+                    .extend(q::quote_spanned!(control.span => #receiver.flush();));
+            }
         }
     }
 
@@ -231,6 +264,60 @@ impl<'a> Encoder<'a> {
         });
     }
 
+    /// Encode an expression which is emitted verbatim.
+    pub(crate) fn encode_raw(&mut self, expr: syn::Expr) {
+        let Ctxt { receiver, module } = self.cx;
+
+        self.item_buffer.flush(&mut self.output);
+        self.output.extend(q::quote! {
+            #receiver.append(#module::tokens::raw(#expr));
+        });
+    }
+
+    /// Encode an expression whose lines keep their relative indentation.
+    pub(crate) fn encode_verbatim(&mut self, expr: syn::Expr) {
+        let Ctxt { receiver, module } = self.cx;
+
+        self.item_buffer.flush(&mut self.output);
+        self.output.extend(q::quote! {
+            #receiver.append(#module::tokens::verbatim(#expr));
+        });
+    }
+
+    /// Mark the items which follow as coming from the source line of `next`,
+    /// which is used to build source maps.
+    fn encode_source(&mut self, next: Cursor) {
+        let Ctxt { receiver, module } = self.cx;
+
+        self.source_line = Some(next.start.line);
+        let line = q::quote_spanned!(next.span => line!());
+
+        self.item_buffer.flush(&mut self.output);
+        self.output.extend(q::quote! {
+            #receiver.append(#module::tokens::__source(file!(), #line));
+        });
+    }
+
+    /// Encode an evaluation whose output is logged when debugging is enabled.
+    pub(crate) fn encode_debug(&mut self, expr: syn::Expr) {
+        let Ctxt { receiver, module } = self.cx;
+
+        let source = q::ToTokens::to_token_stream(&expr).to_string();
+        // NB: spanned so that the location points to the expression rather
+        // than the macro invocation.
+        let line = q::quote_spanned!(syn::spanned::Spanned::span(&expr) => line!());
+
+        self.item_buffer.flush(&mut self.output);
+        self.output.extend(q::quote! {
+            #receiver.append(#module::tokens::__debug(#source, file!(), #line, #expr));
+        });
+    }
+
+    /// Encode a loop.
+    ///
+    /// The receiver is borrowed once outside of the loop, and once the first
+    /// iteration has been appended room is reserved for the remaining ones
+    /// based on the size hint of the iterator.
     pub(crate) fn encode_repeat(
         &mut self,
         pattern: syn::Pat,
@@ -239,30 +326,105 @@ impl<'a> Encoder<'a> {
         stream: TokenStream,
     ) {
         self.item_buffer.flush(&mut self.output);
+        let repeat = self.repeat(pattern, expr, join, stream);
+        self.output.extend(repeat);
+    }
+
+    /// Encode existing fragments joined by a separator, which is evaluated
+    /// once before the loop.
+    pub(crate) fn encode_spread(&mut self, expr: syn::Expr, separator: syn::Expr) {
+        let Ctxt { receiver, module } = self.cx;
 
-        if let Some(join) = join {
-            self.output.extend(q::quote! {
-                {
-                    let mut __it = IntoIterator::into_iter(#expr).peekable();
+        self.item_buffer.flush(&mut self.output);
 
-                    while let Some(#pattern) = __it.next() {
-                        #stream
+        let item = syn::Ident::new("__genco_macros_item", Span::call_site());
 
-                        if __it.peek().is_some() {
-                            #join
-                        }
+        let repeat = self.repeat(
+            syn::parse_quote!(#item),
+            expr,
+            Some(q::quote!(#receiver.append(__genco_macros_separator.clone());)),
+            q::quote!(#receiver.append(#item);),
+        );
+
+        self.output.extend(q::quote! {{
+            let __genco_macros_separator = #module::tokens::ItemStr::from(#separator);
+            #repeat
+        }});
+    }
+
+    /// Build a loop, see [Encoder::encode_repeat].
+    fn repeat(
+        &self,
+        pattern: syn::Pat,
+        expr: syn::Expr,
+        join: Option<TokenStream>,
+        stream: TokenStream,
+    ) -> TokenStream {
+        let Ctxt { receiver, module } = self.cx;
+
+        let (it, join) = match join {
+            Some(join) => (
+                q::quote!(IntoIterator::into_iter(#expr).peekable()),
+                q::quote! {
+                    if __it.peek().is_some() {
+                        #join
                     }
-                }
-            });
-        } else {
-            self.output.extend(q::quote! {
-                for #pattern in #expr {
+                },
+            ),
+            None => (q::quote!(IntoIterator::into_iter(#expr)), q::quote!()),
+        };
+
+        q::quote! {
+            {
+                let #receiver = &mut *#receiver;
+                let mut __it = #it;
+                let mut __start = Some(#module::tokens::__loop_start(#receiver));
+
+                while let Some(__item) = __it.next() {
+                    let #pattern = __item;
                     #stream
+                    #join
+
+                    if let Some(start) = __start.take() {
+                        #module::tokens::__loop_reserve(#receiver, start, __it.size_hint());
+                    }
                 }
-            });
+            }
         }
     }
 
+    /// Encode a concatenation, which is built separately so that whitespace
+    /// can be stripped from it.
+    pub(crate) fn encode_concat(&mut self, stream: TokenStream) {
+        let Ctxt { receiver, module } = self.cx;
+
+        self.item_buffer.flush(&mut self.output);
+
+        self.output.extend(q::quote! {{
+            let mut __genco_macros_concat = #module::tokens::Tokens::new();
+
+            {
+                let #receiver = &mut __genco_macros_concat;
+                #stream
+            }
+
+            #receiver.append(#module::tokens::__concat(__genco_macros_concat));
+        }});
+    }
+
+    /// Encode a block which is only emitted once per key.
+    pub(crate) fn encode_once(&mut self, key: syn::Expr, stream: TokenStream) {
+        let Ctxt { receiver, module } = self.cx;
+
+        self.item_buffer.flush(&mut self.output);
+
+        self.output.extend(q::quote! {
+            #receiver.append(#module::tokens::__open_once(#key));
+            { #stream }
+            #receiver.append(#module::tokens::__close_once());
+        });
+    }
+
     /// Encode an if statement with an inner stream.
     pub(crate) fn encode_condition(
         &mut self,
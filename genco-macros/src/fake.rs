@@ -39,13 +39,27 @@ impl LineColumn {
     }
 
     #[cfg(not(proc_macro_span))]
-    pub(crate) fn start(_: Span) -> Option<Self> {
-        None
+    pub(crate) fn start(span: Span) -> Option<Self> {
+        Self::from_proc_macro2(span.start())
+    }
+
+    #[cfg(not(proc_macro_span))]
+    pub(crate) fn end(span: Span) -> Option<Self> {
+        Self::from_proc_macro2(span.end())
     }
 
+    /// Convert from a proc-macro2 line-column, which reports line `0` if span
+    /// locations are not available.
     #[cfg(not(proc_macro_span))]
-    pub(crate) fn end(_: Span) -> Option<Self> {
-        None
+    fn from_proc_macro2(lc: proc_macro2::LineColumn) -> Option<Self> {
+        if lc.line == 0 {
+            return None;
+        }
+
+        Some(Self {
+            line: lc.line,
+            column: lc.column,
+        })
     }
 }
 
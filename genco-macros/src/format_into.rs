@@ -0,0 +1,155 @@
+use proc_macro2::{Span, TokenStream};
+use syn::spanned::Spanned as _;
+use syn::{Result, Token};
+
+use crate::Ctxt;
+
+/// The separator to insert between fields.
+enum Separator {
+    /// A literal string, like `#[genco(sep = ", ")]`.
+    Literal(syn::LitStr),
+    /// A space, like `#[genco(sep = space)]`.
+    Space,
+    /// A push, like `#[genco(sep = push)]`.
+    Push,
+    /// A line, like `#[genco(sep = line)]`.
+    Line,
+}
+
+impl Separator {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+        if input.peek(syn::LitStr) {
+            return Ok(Self::Literal(input.parse()?));
+        }
+
+        let ident = input.parse::<syn::Ident>()?;
+
+        match ident.to_string().as_str() {
+            "space" => Ok(Self::Space),
+            "push" => Ok(Self::Push),
+            "line" => Ok(Self::Line),
+            other => Err(syn::Error::new(
+                ident.span(),
+                format!("Unsupported separator `{other}`, expected a string literal or one of: space, push, line"),
+            )),
+        }
+    }
+
+    fn encode(&self, receiver: &syn::Ident) -> TokenStream {
+        match self {
+            Self::Literal(lit) => q::quote!(#receiver.append(#lit);),
+            Self::Space => q::quote!(#receiver.space();),
+            Self::Push => q::quote!(#receiver.push();),
+            Self::Line => q::quote!(#receiver.line();),
+        }
+    }
+}
+
+/// Expand the `FormatInto` derive.
+pub(crate) fn expand(input: syn::DeriveInput) -> Result<TokenStream> {
+    let cx = Ctxt::default();
+    let Ctxt { receiver, module } = &cx;
+
+    let mut sep = Separator::Space;
+
+    for attr in &input.attrs {
+        if !attr.path().is_ident("genco") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("sep") {
+                meta.input.parse::<Token![=]>()?;
+                sep = Separator::parse(meta.input)?;
+                return Ok(());
+            }
+
+            Err(meta.error("Unsupported attribute, expected one of: sep"))
+        })?;
+    }
+
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "FormatInto can only be derived for structs",
+            ));
+        }
+    };
+
+    let mut members = Vec::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        let mut skip = false;
+
+        for attr in &field.attrs {
+            if !attr.path().is_ident("genco") {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    return Ok(());
+                }
+
+                Err(meta.error("Unsupported field attribute, expected one of: skip"))
+            })?;
+        }
+
+        if skip {
+            continue;
+        }
+
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(syn::Index {
+                index: index as u32,
+                span: field.span(),
+            }),
+        };
+
+        members.push((member, &field.ty));
+    }
+
+    let lang = syn::Ident::new("__GencoLang", Span::call_site());
+
+    let mut generics = input.generics.clone();
+    generics
+        .params
+        .push(syn::parse_quote!(#lang: #module::lang::Lang));
+
+    let where_clause = generics.make_where_clause();
+
+    for (_, ty) in &members {
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#ty: #module::tokens::FormatInto<#lang>));
+    }
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let ident = &input.ident;
+
+    let sep = sep.encode(receiver);
+    let mut body = TokenStream::new();
+
+    for (n, (member, ty)) in members.iter().enumerate() {
+        if n > 0 {
+            body.extend(sep.clone());
+        }
+
+        body.extend(q::quote_spanned! { ty.span() =>
+            #receiver.append(self.#member);
+        });
+    }
+
+    Ok(q::quote! {
+        impl #impl_generics #module::tokens::FormatInto<#lang> for #ident #ty_generics #where_clause {
+            fn format_into(self, #receiver: &mut #module::tokens::Tokens<#lang>) {
+                #body
+            }
+        }
+    })
+}
@@ -38,6 +38,7 @@ mod ast;
 mod cursor;
 mod encoder;
 mod fake;
+mod format_into;
 mod quote;
 mod quote_fn;
 mod quote_in;
@@ -87,3 +88,13 @@ pub fn quote_fn(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let quote_fn = syn::parse_macro_input!(input as quote_fn::QuoteFn);
     quote_fn.stream.into()
 }
+
+#[proc_macro_derive(FormatInto, attributes(genco))]
+pub fn derive_format_into(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    match format_into::expand(input) {
+        Ok(stream) => stream.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
@@ -1,9 +1,10 @@
+use proc_macro2::extra::DelimSpan;
 use proc_macro2::{Punct, Spacing, Span, TokenStream, TokenTree};
 use syn::parse::{ParseBuffer, ParseStream};
 use syn::spanned::Spanned;
 use syn::{token, Result, Token};
 
-use crate::ast::{Ast, Control, Delimiter, LiteralName, MatchArm, Name};
+use crate::ast::{Ast, Control, ControlKind, Delimiter, LiteralName, MatchArm, Name};
 use crate::encoder::Encoder;
 use crate::fake::Buf;
 use crate::fake::LineColumn;
@@ -11,6 +12,19 @@ use crate::requirements::Requirements;
 use crate::string_parser::StringParser;
 use crate::Ctxt;
 
+mod kw {
+    syn::custom_keyword!(concat);
+    syn::custom_keyword!(debug);
+    syn::custom_keyword!(join);
+    syn::custom_keyword!(once);
+    syn::custom_keyword!(raw);
+    syn::custom_keyword!(repeat);
+    syn::custom_keyword!(sorted);
+    syn::custom_keyword!(spread);
+    syn::custom_keyword!(verbatim);
+    syn::custom_keyword!(with);
+}
+
 pub(crate) struct Quote<'a> {
     /// Context variables.
     cx: &'a Ctxt,
@@ -83,42 +97,55 @@ impl<'a> Quote<'a> {
         encoder.into_output()
     }
 
-    /// Parse `if <condition> { <quoted> } [else { <quoted> }]`.
+    /// Parse `if <condition> { <quoted> } [else { <quoted> }]` or `if
+    /// <condition> => <quoted> [else => <quoted>]`.
     fn parse_condition(&self, input: ParseStream) -> Result<(Requirements, Ast)> {
         input.parse::<Token![if]>()?;
         let condition = syn::Expr::parse_without_eager_brace(input)?;
 
-        if input.peek(Token![=>]) {
-            input.parse::<Token![=>]>()?;
-            let (req, then_branch) = Quote::new(self.cx).parse(input)?;
-
-            return Ok((
-                req,
-                Ast::Condition {
-                    condition,
-                    then_branch,
-                    else_branch: None,
-                },
-            ));
-        }
-
         let mut req = Requirements::default();
 
-        let content;
-        syn::braced!(content in input);
+        let then_branch = if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
 
-        let (r, then_branch) = Quote::new(self.cx).parse(&content)?;
-        req.merge_with(r);
+            // NB: the then branch runs until an `else =>`, so the tokens
+            // up until it are collected and parsed separately.
+            let mut then = TokenStream::new();
 
-        let else_branch = if input.peek(Token![else]) {
-            input.parse::<Token![else]>()?;
+            while !input.is_empty() {
+                if input.peek(Token![else]) && input.peek2(Token![=>]) {
+                    break;
+                }
 
+                then.extend(std::iter::once(input.parse::<TokenTree>()?));
+            }
+
+            let parser = |input: ParseStream| Quote::new(self.cx).parse(input);
+            let (r, then_branch) = syn::parse::Parser::parse2(parser, then)?;
+            req.merge_with(r);
+            then_branch
+        } else {
             let content;
             syn::braced!(content in input);
 
-            let (r, else_branch) = Quote::new(self.cx).parse(&content)?;
+            let (r, then_branch) = Quote::new(self.cx).parse(&content)?;
             req.merge_with(r);
+            then_branch
+        };
 
+        let else_branch = if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+
+            let (r, else_branch) = if input.peek(Token![=>]) {
+                input.parse::<Token![=>]>()?;
+                Quote::new(self.cx).parse(input)?
+            } else {
+                let content;
+                syn::braced!(content in input);
+                Quote::new(self.cx).parse(&content)?
+            };
+
+            req.merge_with(r);
             Some(else_branch)
         } else {
             None
@@ -136,17 +163,112 @@ impl<'a> Quote<'a> {
 
     /// Parse `for <expr> in <iter> [join (<quoted>)] => <quoted>`.
     fn parse_loop(&self, input: ParseStream) -> Result<(Requirements, Ast)> {
-        syn::custom_keyword!(join);
-
-        let mut req = Requirements::default();
-
         input.parse::<Token![for]>()?;
         let pattern = syn::Pat::parse_single(input)?;
         input.parse::<Token![in]>()?;
+        let mut expr = syn::Expr::parse_without_eager_brace(input)?;
+
+        if input.peek(kw::sorted) {
+            input.parse::<kw::sorted>()?;
+            let module = &self.cx.module;
+
+            // NB: a pair pattern like `(k, v)` is sorted by its key, anything
+            // else by the whole item.
+            expr = match &pattern {
+                syn::Pat::Tuple(tuple) if tuple.elems.len() == 2 => {
+                    syn::parse_quote!(#module::tokens::__sorted_by_key(#expr))
+                }
+                _ => syn::parse_quote!(#module::tokens::__sorted(#expr)),
+            };
+        }
+
+        let (req, join, stream) = self.parse_loop_body(input)?;
+
+        let ast = Ast::Loop {
+            pattern: Box::new(pattern),
+            join,
+            expr: Box::new(expr),
+            stream,
+        };
+
+        Ok((req, ast))
+    }
+
+    /// Parse a count-based repetition: `repeat <count> [join (<quoted>)] =>
+    /// <quoted>`.
+    ///
+    /// This is lowered into a loop over `0..count`, where the current
+    /// repetition is bound to `index`.
+    fn parse_repeat(&self, input: ParseStream) -> Result<(Requirements, Ast)> {
+        let repeat = input.parse::<kw::repeat>()?;
+        let count = syn::Expr::parse_without_eager_brace(input)?;
+
+        let (req, join, stream) = self.parse_loop_body(input)?;
+
+        let index = syn::Ident::new("index", repeat.span);
+
+        let ast = Ast::Loop {
+            pattern: Box::new(syn::parse_quote!(#index)),
+            join,
+            expr: Box::new(syn::parse_quote!(0usize..(#count))),
+            stream: q::quote!(let _ = #index; #stream),
+        };
+
+        Ok((req, ast))
+    }
+
+    /// Parse `once <key> => <quoted>` or `once <key> { <quoted> }`.
+    fn parse_once(&self, input: ParseStream) -> Result<(Requirements, Ast)> {
+        input.parse::<kw::once>()?;
+        let key = syn::Expr::parse_without_eager_brace(input)?;
+
+        let content;
+
+        let input = if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            input
+        } else {
+            syn::braced!(content in input);
+            &content
+        };
+
+        let (req, stream) = Quote::new(self.cx).parse(input)?;
+        Ok((req, Ast::Once { key, stream }))
+    }
+
+    /// Parse `spread <expr> with <separator>`.
+    ///
+    /// This is lowered into a loop which appends every item produced by
+    /// `<expr>`, joined by the separator as a literal.
+    fn parse_spread(&self, input: ParseStream) -> Result<Ast> {
+        input.parse::<kw::spread>()?;
         let expr = syn::Expr::parse_without_eager_brace(input)?;
+        input.parse::<kw::with>()?;
+        let separator = input.parse::<syn::Expr>()?;
+
+        Ok(Ast::Spread {
+            expr: Box::new(expr),
+            separator: Box::new(separator),
+        })
+    }
+
+    /// Parse `concat <quoted>`.
+    fn parse_concat(&self, input: ParseStream) -> Result<(Requirements, Ast)> {
+        input.parse::<kw::concat>()?;
+        let (req, stream) = Quote::new(self.cx).parse(input)?;
+        Ok((req, Ast::Concat { stream }))
+    }
+
+    /// Parse the optional join and the body of a loop, like `join (<quoted>)
+    /// => <quoted>`.
+    fn parse_loop_body(
+        &self,
+        input: ParseStream,
+    ) -> Result<(Requirements, Option<TokenStream>, TokenStream)> {
+        let mut req = Requirements::default();
 
-        let join = if input.peek(join) {
-            input.parse::<join>()?;
+        let join = if input.peek(kw::join) {
+            input.parse::<kw::join>()?;
 
             let content;
             let paren = syn::parenthesized!(content in input);
@@ -175,14 +297,7 @@ impl<'a> Quote<'a> {
         let (r, stream) = parser.parse(input)?;
         req.merge_with(r);
 
-        let ast = Ast::Loop {
-            pattern: Box::new(pattern),
-            join,
-            expr: Box::new(expr),
-            stream,
-        };
-
-        Ok((req, ast))
+        Ok((req, join, stream))
     }
 
     fn parse_match(&self, input: ParseStream) -> Result<(Requirements, Ast)> {
@@ -244,7 +359,8 @@ impl<'a> Quote<'a> {
         Ok((req, Ast::Match { condition, arms }))
     }
 
-    /// Parse evaluation: `[*]<binding> => <expr>`.
+    /// Parse a scope: `ref <binding> => <expr>` or `ref <binding> { <expr> }`,
+    /// where `<binding>` may be `_` to not bind the token stream.
     fn parse_scope(&self, input: ParseStream) -> Result<Ast> {
         input.parse::<Token![ref]>()?;
 
@@ -300,8 +416,51 @@ impl<'a> Quote<'a> {
             let (req, ast) = self.parse_match(&scope)?;
             encoder.requirements.merge_with(req);
             ast
+        } else if is_concat_opt(scope.fork()) {
+            let (req, ast) = self.parse_concat(&scope)?;
+            encoder.requirements.merge_with(req);
+            ast
+        } else if is_spread_opt(scope.fork()) {
+            self.parse_spread(&scope)?
+        } else if is_once_opt(scope.fork()) {
+            let (req, ast) = self.parse_once(&scope)?;
+            encoder.requirements.merge_with(req);
+            ast
+        } else if is_repeat_opt(scope.fork()) {
+            let (req, ast) = self.parse_repeat(&scope)?;
+            encoder.requirements.merge_with(req);
+            ast
         } else if scope.peek(Token![ref]) {
             self.parse_scope(&scope)?
+        } else if scope.peek(Token![try]) {
+            let try_token = scope.parse::<Token![try]>()?;
+            let expr = scope.parse::<syn::Expr>()?;
+
+            Ast::Eval {
+                expr: syn::Expr::Try(syn::ExprTry {
+                    attrs: Vec::new(),
+                    expr: Box::new(expr),
+                    question_token: Token![?](try_token.span),
+                }),
+            }
+        } else if is_keyword_expr_opt::<kw::raw>(scope.fork()) {
+            scope.parse::<kw::raw>()?;
+
+            Ast::Raw {
+                expr: scope.parse()?,
+            }
+        } else if is_keyword_expr_opt::<kw::verbatim>(scope.fork()) {
+            scope.parse::<kw::verbatim>()?;
+
+            Ast::Verbatim {
+                expr: scope.parse()?,
+            }
+        } else if is_keyword_expr_opt::<kw::debug>(scope.fork()) {
+            scope.parse::<kw::debug>()?;
+
+            Ast::Debug {
+                expr: scope.parse()?,
+            }
         } else if crate::string_parser::is_lit_str_opt(scope.fork())? {
             let string = scope.parse::<syn::LitStr>()?.value();
             Ast::Literal { string }
@@ -380,10 +539,44 @@ impl<'a> Quote<'a> {
                         let cursor = self.buf.join(start.span(), end.span())?;
                         encoder.encode(cursor, Ast::Control { control })?;
                     }
+                    (LiteralName::Ident("softpush"), content) => {
+                        if let Some(content) = content {
+                            return Err(syn::Error::new(
+                                content.span(),
+                                "Control `softpush` does not expect an argument",
+                            ));
+                        }
+
+                        let control = Control {
+                            kind: ControlKind::SoftPush,
+                            span: name.span(),
+                        };
+
+                        let cursor = self.buf.join(start.span(), end.span())?;
+                        encoder.encode(cursor, Ast::Control { control })?;
+                    }
+                    (LiteralName::Ident("flush"), content) => {
+                        if let Some(content) = content {
+                            return Err(syn::Error::new(
+                                content.span(),
+                                "Control `flush` does not expect an argument",
+                            ));
+                        }
+
+                        let control = Control {
+                            kind: ControlKind::Flush,
+                            span: name.span(),
+                        };
+
+                        let cursor = self.buf.join(start.span(), end.span())?;
+                        encoder.encode(cursor, Ast::Control { control })?;
+                    }
                     (LiteralName::Ident(string), _) => {
                         return Err(syn::Error::new(
                             name.span(),
-                            format!("Unsupported function `{string}`, expected one of: str"),
+                            format!(
+                                "Unsupported function `{string}`, expected one of: str, softpush, flush"
+                            ),
                         ));
                     }
                 }
@@ -412,7 +605,7 @@ impl<'a> Quote<'a> {
                 self.parse_group(
                     encoder,
                     Delimiter::Brace,
-                    braces.span.span(),
+                    braces.span,
                     &content,
                     group_depth,
                 )?;
@@ -425,7 +618,7 @@ impl<'a> Quote<'a> {
                 self.parse_group(
                     encoder,
                     Delimiter::Parenthesis,
-                    braces.span.span(),
+                    braces.span,
                     &content,
                     group_depth,
                 )?;
@@ -438,7 +631,7 @@ impl<'a> Quote<'a> {
                 self.parse_group(
                     encoder,
                     Delimiter::Bracket,
-                    braces.span.span(),
+                    braces.span,
                     &content,
                     group_depth,
                 )?;
@@ -457,17 +650,21 @@ impl<'a> Quote<'a> {
         &mut self,
         encoder: &mut Encoder,
         delimiter: Delimiter,
-        span: Span,
+        span: DelimSpan,
         input: ParseStream,
         group_depth: usize,
     ) -> Result<()> {
-        let cursor = self.buf.cursor(span)?;
+        let cursor = self.buf.cursor(span.join())?;
 
         encoder.encode(cursor.first_character(), Ast::DelimiterOpen { delimiter })?;
 
         self.parse_inner(encoder, input, group_depth + 1)?;
 
-        encoder.encode(cursor.last_character(), Ast::DelimiterClose { delimiter })?;
+        // NB: the close delimiter is spanned by itself, so that it refers to
+        // the line it is on.
+        let mut close = cursor.last_character();
+        close.span = span.close();
+        encoder.encode(close, Ast::DelimiterClose { delimiter })?;
 
         Ok(())
     }
@@ -520,3 +717,113 @@ pub(crate) fn parse_internal_function<'a>(
 
     Ok(Some((name, content, [start.span(), end.span()])))
 }
+
+/// Test if the parenthesized content is a keyword followed by an expression,
+/// like `$(raw expr)`, `$(verbatim expr)`, or `$(debug expr)`.
+///
+/// Content which parses as an expression on its own is treated as a regular
+/// evaluation, so `$(raw)` and `$(raw.field)` evaluate a variable named `raw`
+/// and `$(raw(x))` calls a function named `raw`. The exception is a binary
+/// operation, since `$(raw &sql)` and `$(raw -1)` would otherwise never be
+/// taken as the keyword form.
+fn is_keyword_expr_opt<K>(content: ParseBuffer<'_>) -> bool
+where
+    K: syn::token::Token + syn::parse::Parse,
+{
+    let expr = content.fork();
+
+    if let Ok(parsed) = expr.parse::<syn::Expr>() {
+        if expr.is_empty() && !matches!(parsed, syn::Expr::Binary(..)) {
+            return false;
+        }
+    }
+
+    if content.parse::<Option<K>>().ok().flatten().is_none() || content.is_empty() {
+        return false;
+    }
+
+    content.parse::<syn::Expr>().is_ok() && content.is_empty()
+}
+
+/// Test if the parenthesized content is a block which is only emitted once,
+/// like `$(once "key" => <quoted>)`.
+///
+/// Something like `$(once)` or `$(once.key())` is treated as a regular
+/// evaluation of a variable named `once`.
+fn is_once_opt(content: ParseBuffer<'_>) -> bool {
+    if content.parse::<Option<kw::once>>().ok().flatten().is_none() || content.is_empty() {
+        return false;
+    }
+
+    if syn::Expr::parse_without_eager_brace(&content).is_err() {
+        return false;
+    }
+
+    content.peek(Token![=>]) || content.peek(token::Brace)
+}
+
+/// Test if the parenthesized content is a concatenation, like `$(concat get
+/// $field)`.
+///
+/// Anything which parses as an expression, like `$(concat)` or
+/// `$(concat([a, b]))`, is treated as a regular evaluation.
+fn is_concat_opt(content: ParseBuffer<'_>) -> bool {
+    let expr = content.fork();
+
+    if expr.parse::<syn::Expr>().is_ok() && expr.is_empty() {
+        return false;
+    }
+
+    content
+        .parse::<Option<kw::concat>>()
+        .ok()
+        .flatten()
+        .is_some()
+        && !content.is_empty()
+}
+
+/// Test if the parenthesized content is a spread, like `$(spread args with ",
+/// ")`.
+///
+/// Something like `$(spread)` or `$(spread.len())` is treated as a regular
+/// evaluation of a variable named `spread`.
+fn is_spread_opt(content: ParseBuffer<'_>) -> bool {
+    if content
+        .parse::<Option<kw::spread>>()
+        .ok()
+        .flatten()
+        .is_none()
+        || content.is_empty()
+    {
+        return false;
+    }
+
+    if syn::Expr::parse_without_eager_brace(&content).is_err() {
+        return false;
+    }
+
+    content.peek(kw::with)
+}
+
+/// Test if the parenthesized content is a repetition, like `$(repeat 3 =>
+/// <quoted>)`.
+///
+/// Something like `$(repeat)` or `$(repeat.count())` is treated as a regular
+/// evaluation of a variable named `repeat`.
+fn is_repeat_opt(content: ParseBuffer<'_>) -> bool {
+    if content
+        .parse::<Option<kw::repeat>>()
+        .ok()
+        .flatten()
+        .is_none()
+        || content.is_empty()
+    {
+        return false;
+    }
+
+    if syn::Expr::parse_without_eager_brace(&content).is_err() {
+        return false;
+    }
+
+    content.peek(kw::join) || content.peek(Token![=>]) || content.peek(token::Brace)
+}
@@ -25,8 +25,12 @@ impl Parse for QuoteIn {
         let Ctxt { receiver, module } = &cx;
 
         // Give the assignment its own span to improve diagnostics.
+        //
+        // Note: method call syntax is used so that the target can either be a
+        // place expression like `tokens` or `self.buf`, or an expression which
+        // evaluates to `&mut Tokens<L>`.
         let assign_mut = q::quote_spanned! { expr.span() =>
-            let #receiver: &mut #module::tokens::Tokens<_> = &mut #expr;
+            let #receiver: &mut #module::tokens::Tokens<_> = (#expr).__as_mut();
         };
 
         let stream = q::quote! {{
@@ -0,0 +1,197 @@
+//! A set of files which are formatted and written separately.
+//!
+//! See [FileSet] for details.
+
+use std::collections::btree_map::{self, BTreeMap};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use crate::lang::Lang;
+use crate::tokens;
+use crate::Tokens;
+
+/// A set of files, each with its own token stream.
+///
+/// Every file is formatted separately, so imports are resolved independently
+/// for each of them. This is useful when splitting up large amounts of
+/// generated code, like partial classes in C#.
+///
+/// This is only available with the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::FileSet;
+///
+/// let list = csharp::import("System.Collections.Generic", "List");
+/// let text = csharp::import("System.Text", "StringBuilder");
+///
+/// let mut files = FileSet::<Csharp>::new();
+///
+/// files.add("Foo.Items.cs", quote! {
+///     partial class Foo {
+///         $list<int> items;
+///     }
+/// });
+///
+/// files.add("Foo.Text.cs", quote! {
+///     partial class Foo {
+///         $text text;
+///     }
+/// });
+///
+/// let dir = std::env::temp_dir().join("genco-file-set-doc");
+/// files.write_to_dir(&dir, &csharp::Config::default())?;
+///
+/// assert_eq!(
+///     "using System.Collections.Generic;\n\npartial class Foo {\n    List<int> items;\n}\n",
+///     std::fs::read_to_string(dir.join("Foo.Items.cs"))?
+/// );
+///
+/// assert_eq!(
+///     "using System.Text;\n\npartial class Foo {\n    StringBuilder text;\n}\n",
+///     std::fs::read_to_string(dir.join("Foo.Text.cs"))?
+/// );
+/// # std::fs::remove_dir_all(&dir)?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileSet<L>
+where
+    L: Lang,
+{
+    files: BTreeMap<PathBuf, Tokens<L>>,
+}
+
+impl<L> FileSet<L>
+where
+    L: Lang,
+{
+    /// Construct a new empty set of files.
+    pub fn new() -> Self {
+        Self {
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// Add a file at the given relative `path`.
+    ///
+    /// If a file already exists at the same path it is replaced, and its
+    /// tokens are returned.
+    pub fn add<P>(&mut self, path: P, tokens: Tokens<L>) -> Option<Tokens<L>>
+    where
+        P: Into<PathBuf>,
+    {
+        self.files.insert(path.into(), tokens)
+    }
+
+    /// Get the number of files in the set.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Test if the set contains no files.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Iterate over the paths and token streams of all files, ordered by
+    /// path.
+    pub fn iter(&self) -> Iter<'_, L> {
+        Iter {
+            iter: self.files.iter(),
+        }
+    }
+
+    /// Format every file and write it relative to `dir`, creating any missing
+    /// directories along the way.
+    ///
+    /// Files are written atomically and with the same contents as
+    /// [Tokens::write_into_file].
+    ///
+    /// # Errors
+    ///
+    /// Errors if a file fails to format or can't be written. Paths which are
+    /// absolute or contain `..` are rejected before anything is written, since
+    /// they could end up outside of `dir`.
+    pub fn write_to_dir<D>(&self, dir: D, config: &L::Config) -> io::Result<()>
+    where
+        D: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+
+        for path in self.files.keys() {
+            let escapes = path.components().any(|c| {
+                matches!(
+                    c,
+                    Component::Prefix(..) | Component::RootDir | Component::ParentDir
+                )
+            });
+
+            if escapes {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("path `{}` is not relative to the directory", path.display()),
+                ));
+            }
+        }
+
+        for (path, tokens) in &self.files {
+            let out = tokens.to_file_contents(path, config)?;
+            let path = dir.join(path);
+
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            tokens::write_atomic(&path, &out)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<L> Default for FileSet<L>
+where
+    L: Lang,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iterator over the files in a [FileSet].
+///
+/// Created through [FileSet::iter].
+pub struct Iter<'a, L>
+where
+    L: Lang,
+{
+    iter: btree_map::Iter<'a, PathBuf, Tokens<L>>,
+}
+
+impl<'a, L> Iterator for Iter<'a, L>
+where
+    L: Lang,
+{
+    type Item = (&'a Path, &'a Tokens<L>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, tokens) = self.iter.next()?;
+        Some((path.as_path(), tokens))
+    }
+}
+
+impl<'a, L> IntoIterator for &'a FileSet<L>
+where
+    L: Lang,
+{
+    type Item = (&'a Path, &'a Tokens<L>);
+    type IntoIter = Iter<'a, L>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -45,6 +45,14 @@ pub struct Config {
     pub(super) indentation: Indentation,
     /// What to use as a newline.
     pub(super) newline: &'static str,
+    /// Escape all non-ASCII characters in quoted strings.
+    pub(super) ascii_only: bool,
+    /// Write a newline at the end of files.
+    pub(super) final_newline: bool,
+    /// The maximum number of consecutive blank lines to write.
+    pub(super) max_blank_lines: usize,
+    /// The number of indentation levels every line is prefixed with.
+    pub(super) base_indent: usize,
 }
 
 impl Config {
@@ -57,6 +65,10 @@ impl Config {
         Self {
             indentation: L::default_indentation(),
             newline: "\n",
+            ascii_only: false,
+            final_newline: true,
+            max_blank_lines: usize::MAX,
+            base_indent: 0,
         }
     }
 
@@ -72,4 +84,125 @@ impl Config {
     pub fn with_newline(self, newline: &'static str) -> Self {
         Self { newline, ..self }
     }
+
+    /// Escape all non-ASCII characters in quoted strings, for languages
+    /// which support it. This is useful when generated sources must be pure
+    /// ASCII.
+    ///
+    /// By default, non-ASCII characters are passed through as UTF-8 unless
+    /// the language requires them to be escaped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let tokens: rust::Tokens = quote!(let s = "Hello 😊";);
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>().with_ascii_only(true);
+    /// let mut out = String::new();
+    /// tokens.fmt_to(&mut out, &fmt)?;
+    ///
+    /// assert_eq!("let s = \"Hello \\u{1f60a}\";", out);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_ascii_only(self, ascii_only: bool) -> Self {
+        Self { ascii_only, ..self }
+    }
+
+    /// Set if a newline should be written at the end of files, which is the
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let tokens: rust::Tokens = quote!(fn main() {});
+    ///
+    /// let mut out = String::new();
+    /// tokens.fmt_file_to(&mut out, &fmt::Config::from_lang::<Rust>())?;
+    /// assert_eq!("fn main() {}\n", out);
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>().with_final_newline(false);
+    /// let mut out = String::new();
+    /// tokens.fmt_file_to(&mut out, &fmt)?;
+    /// assert_eq!("fn main() {}", out);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_final_newline(self, final_newline: bool) -> Self {
+        Self {
+            final_newline,
+            ..self
+        }
+    }
+
+    /// Limit the number of consecutive blank lines in the output, collapsing
+    /// longer runs.
+    ///
+    /// Token streams never produce more than one blank line in a row on their
+    /// own, but raw or verbatim text composed from several fragments might.
+    /// By default the number of blank lines is not limited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let header = "// Generated code.\n\n\n";
+    /// let tokens: rust::Tokens = quote! {
+    ///     $(raw header)
+    ///     fn main() {}
+    /// };
+    ///
+    /// let mut out = String::new();
+    /// tokens.fmt_to(&mut out, &fmt::Config::from_lang::<Rust>())?;
+    /// assert_eq!("// Generated code.\n\n\n\nfn main() {}", out);
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>().with_max_blank_lines(1);
+    /// let mut out = String::new();
+    /// tokens.fmt_to(&mut out, &fmt)?;
+    /// assert_eq!("// Generated code.\n\nfn main() {}", out);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_max_blank_lines(self, max_blank_lines: usize) -> Self {
+        Self {
+            max_blank_lines,
+            ..self
+        }
+    }
+
+    /// Prefix every line with the given number of indentation levels, in
+    /// addition to the indentation of the token stream itself.
+    ///
+    /// This is useful when the output is embedded into an already indented
+    /// host file. Blank lines are left empty, and lines after the first in
+    /// raw items are written verbatim.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let tokens: python::Tokens = quote! {
+    ///     def run():
+    ///         pass
+    /// };
+    ///
+    /// let fmt = fmt::Config::from_lang::<Python>().with_base_indent(2);
+    /// let mut out = String::new();
+    /// tokens.fmt_to(&mut out, &fmt)?;
+    /// assert_eq!("        def run():\n            pass", out);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_base_indent(self, base_indent: usize) -> Self {
+        Self {
+            base_indent,
+            ..self
+        }
+    }
 }
@@ -2,9 +2,12 @@ use crate::fmt;
 use crate::fmt::config::{Config, Indentation};
 use crate::fmt::cursor;
 use crate::lang::Lang;
-use crate::tokens::Item;
+use crate::tokens::{Item, ItemStr};
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
 
-use std::mem;
+use core::mem;
 
 /// Buffer used as indentation source.
 static SPACES: &str = "                                                                                                    ";
@@ -13,6 +16,7 @@ static TABS: &str =
     "\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t";
 
 #[derive(Debug, Clone, Copy)]
+#[allow(clippy::enum_variant_names)]
 enum Line {
     Initial,
     None,
@@ -58,6 +62,35 @@ pub struct Formatter<'a> {
     spaces: usize,
     /// Current indentation level.
     indent: i16,
+    /// The pending whitespace and indentation level from before an
+    /// indentation which hasn't been followed by any output yet.
+    ///
+    /// If the indentation is reverted before anything is written, the pending
+    /// whitespace is moved to `collapse`.
+    empty_block: Option<(Line, usize, i16)>,
+    /// The pending whitespace from before an empty indented block.
+    ///
+    /// This is only restored if the block is immediately closed by a closing
+    /// delimiter, so that empty blocks stay on one line like `{}` while
+    /// anything else still starts on a new line.
+    collapse: Option<(Line, usize)>,
+    /// If nothing has been written since the last line break.
+    line_empty: bool,
+    /// The number of consecutive blank lines which have been written.
+    blank_lines: usize,
+    /// The delimiter of the string currently being quoted, for languages which
+    /// support more than one.
+    quote: Option<char>,
+    /// The number of line breaks which have been written.
+    lines: usize,
+    /// The source location of the content which is about to be written.
+    source: Option<(ItemStr, u32)>,
+    /// Source locations which have been collected, as the output line
+    /// starting at 1 followed by the file and line of the source.
+    sources: Option<Vec<(usize, ItemStr, u32)>>,
+    /// Keys of blocks which should only be formatted once, and which have
+    /// already been formatted.
+    once: BTreeSet<ItemStr>,
 }
 
 impl<'a> Formatter<'a> {
@@ -68,6 +101,15 @@ impl<'a> Formatter<'a> {
             line: Line::Initial,
             spaces: 0usize,
             indent: 0i16,
+            empty_block: None,
+            collapse: None,
+            line_empty: true,
+            blank_lines: 0,
+            quote: None,
+            lines: 0,
+            source: None,
+            sources: None,
+            once: BTreeSet::new(),
             config,
         }
     }
@@ -86,26 +128,154 @@ impl<'a> Formatter<'a> {
         self.format_cursor(&mut cursor, config, format, false)
     }
 
-    /// Forcibly write a line ending, at the end of a file.
+    /// Write a line ending at the end of a file, unless disabled through
+    /// [Config::with_final_newline].
     ///
     /// This will also reset any whitespace we have pending.
     pub(crate) fn write_trailing_line(&mut self) -> fmt::Result {
         self.line = Line::default();
         self.spaces = 0;
-        self.write.write_trailing_line(self.config)?;
+        self.empty_block = None;
+        self.collapse = None;
+
+        if self.config.final_newline {
+            self.write.write_trailing_line(self.config)?;
+        }
+
         Ok(())
     }
 
     /// Write the given string.
     fn write_str(&mut self, s: &str) -> fmt::Result {
         if !s.is_empty() {
+            if let Some((line, spaces)) = self.collapse.take() {
+                if s.starts_with(['}', ')', ']']) {
+                    self.line = line;
+                    self.spaces = spaces;
+                }
+            }
+
             self.flush_whitespace()?;
+            self.write_line_content(s)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the given string at the start of a line, ignoring the current
+    /// indentation.
+    ///
+    /// This is used for things like labels in assembly, which are written at
+    /// column 0 while the surrounding code is indented.
+    pub(crate) fn write_unindented(&mut self, s: &str) -> fmt::Result {
+        let indent = mem::replace(&mut self.indent, 0);
+        let result = self.write_str(s);
+        self.indent = indent;
+        result
+    }
+
+    /// Test if the next thing written will be at the start of a line.
+    pub(crate) fn is_line_start(&self) -> bool {
+        self.line_empty || !matches!(self.line, Line::None)
+    }
+
+    /// Access the configuration of the formatter.
+    pub(crate) fn config(&self) -> &'a Config {
+        self.config
+    }
+
+    /// Test if all non-ASCII characters in quoted strings should be escaped.
+    pub(crate) fn ascii_only(&self) -> bool {
+        self.config.ascii_only
+    }
+
+    /// Get the delimiter of the string currently being quoted, if one has been
+    /// recorded through [Formatter::set_quote].
+    pub(crate) fn quote(&self) -> Option<char> {
+        self.quote
+    }
+
+    /// Record the delimiter of the string currently being quoted, so that
+    /// quoting knows which characters need to be escaped.
+    pub(crate) fn set_quote(&mut self, quote: Option<char>) {
+        self.quote = quote;
+    }
+
+    /// Start collecting the source locations of the lines being written.
+    #[cfg(feature = "sourcemap")]
+    pub(crate) fn collect_sources(&mut self) {
+        self.sources = Some(Vec::new());
+    }
+
+    /// Take the source locations which have been collected, as the output
+    /// line starting at 1 followed by the file and line of the source.
+    #[cfg(feature = "sourcemap")]
+    pub(crate) fn take_sources(&mut self) -> Vec<(usize, ItemStr, u32)> {
+        self.sources.take().unwrap_or_default()
+    }
+
+    /// Write the given string verbatim.
+    ///
+    /// Line breaks are passed on to the underlying writer, and lines following
+    /// them are written without indentation.
+    pub(crate) fn write_raw(&mut self, s: &str) -> fmt::Result {
+        if s.is_empty() {
+            return Ok(());
+        }
+
+        self.flush_whitespace()?;
+
+        let mut lines = s.split('\n');
+
+        if let Some(first) = lines.next() {
+            self.write_line_content(first.strip_suffix('\r').unwrap_or(first))?;
+        }
+
+        for line in lines {
+            self.write_line()?;
+            self.write_line_content(line.strip_suffix('\r').unwrap_or(line))?;
+        }
+
+        Ok(())
+    }
+
+    /// Write content which is part of the current line.
+    fn write_line_content(&mut self, s: &str) -> fmt::Result {
+        if !s.is_empty() {
+            if let (Some((file, line)), Some(sources)) = (self.source.take(), &mut self.sources) {
+                let output = self.lines + 1;
+
+                // NB: only the first source of each output line is recorded.
+                if sources.last().map_or(true, |&(n, ..)| n != output) {
+                    sources.push((output, file, line));
+                }
+            }
+
             self.write.write_str(s)?;
+            self.line_empty = false;
         }
 
         Ok(())
     }
 
+    /// Write a line break, unless it would exceed the configured number of
+    /// consecutive blank lines.
+    fn write_line(&mut self) -> fmt::Result {
+        if self.line_empty {
+            if self.blank_lines >= self.config.max_blank_lines {
+                return Ok(());
+            }
+
+            self.blank_lines += 1;
+        } else {
+            self.blank_lines = 0;
+        }
+
+        self.line_empty = true;
+        self.lines += 1;
+        self.write.write_line(self.config)
+    }
+
     fn push(&mut self) {
         self.line = match self.line {
             Line::Initial => return,
@@ -118,6 +288,8 @@ impl<'a> Formatter<'a> {
 
     /// Push a new line.
     fn line(&mut self) {
+        self.collapse = None;
+
         self.line = match self.line {
             Line::Initial => return,
             _ => Line::Line,
@@ -133,8 +305,24 @@ impl<'a> Formatter<'a> {
 
     /// Increase indentation level.
     fn indentation(&mut self, n: i16) {
+        self.collapse = None;
+
+        if n > 0 && self.empty_block.is_none() {
+            self.empty_block = Some((self.line, self.spaces, self.indent));
+        }
+
         self.push();
         self.indent += n;
+
+        if let Some((line, spaces, indent)) = self.empty_block {
+            if self.indent <= indent {
+                self.empty_block = None;
+
+                if self.indent == indent {
+                    self.collapse = Some((line, spaces));
+                }
+            }
+        }
     }
 
     /// Internal function for formatting.
@@ -160,6 +348,7 @@ impl<'a> Formatter<'a> {
                 in_quote,
                 has_eval,
                 end_on_eval,
+                outer_quote,
             } = head;
 
             match item {
@@ -169,7 +358,30 @@ impl<'a> Formatter<'a> {
                     if *in_quote {
                         L::write_quoted(self, literal)?;
                     } else {
-                        self.write_str(literal)?;
+                        L::write_literal(self, literal)?;
+                    }
+                }
+                Item::Raw(raw) => {
+                    self.write_raw(raw)?;
+                }
+                Item::Source(file, line) => {
+                    if self.sources.is_some() {
+                        self.source = Some((file.clone(), *line));
+                    }
+                }
+                Item::OpenOnce(key) => {
+                    if !self.once.insert(key.clone()) {
+                        skip_once(cursor);
+                    }
+                }
+                Item::CloseOnce => (),
+                Item::Char(c) => {
+                    if *in_quote {
+                        self.quoted_char::<L>(*c, &mut buf)?;
+                        L::write_quoted(self, &buf)?;
+                        buf.clear();
+                    } else {
+                        L::write_char_literal(self, *c)?;
                     }
                 }
                 Item::OpenQuote(e) if !*in_quote => {
@@ -221,28 +433,50 @@ impl<'a> Formatter<'a> {
                             in_quote: false,
                             has_eval: false,
                             end_on_eval: true,
+                            outer_quote: self.quote,
                         });
                     }
                 }
                 // Eval are only allowed within quotes.
                 Item::CloseEval if *end_on_eval => {
+                    self.quote = *outer_quote;
                     L::end_string_eval(self, config, format)?;
                     stack.pop();
                 }
                 _ => {
                     // Anything else is an illegal state for formatting.
-                    return Err(std::fmt::Error);
+                    return Err(core::fmt::Error);
                 }
             }
         }
 
         return Ok(());
 
+        /// Skip over the items of a block which has already been formatted,
+        /// up until its matching [Item::CloseOnce].
+        fn skip_once<L>(cursor: &mut cursor::Cursor<'_, L>)
+        where
+            L: Lang,
+        {
+            let mut depth = 0usize;
+
+            while let Some(item) = cursor.next() {
+                match item {
+                    Item::OpenOnce(..) => depth += 1,
+                    Item::CloseOnce if depth == 0 => break,
+                    Item::CloseOnce => depth -= 1,
+                    _ => (),
+                }
+            }
+        }
+
         #[derive(Default, Clone)]
         struct Frame {
             in_quote: bool,
             has_eval: bool,
             end_on_eval: bool,
+            /// Quote delimiter to restore once evaluation ends.
+            outer_quote: Option<char>,
         }
     }
 
@@ -267,17 +501,31 @@ impl<'a> Formatter<'a> {
         Ok(())
     }
 
+    /// Write a character literal into the given buffer, so that it can be
+    /// embedded in a quoted string.
+    fn quoted_char<L>(&mut self, c: char, buf: &mut String) -> fmt::Result<()>
+    where
+        L: Lang,
+    {
+        use crate::fmt::FmtWriter;
+
+        let mut w = FmtWriter::new(buf);
+        L::write_char_literal(&mut Formatter::new(&mut w, self.config), c)
+    }
+
     // Realize any pending whitespace just prior to writing a non-whitespace
     // item.
     fn flush_whitespace(&mut self) -> fmt::Result {
+        self.empty_block = None;
+        self.collapse = None;
         let mut spaces = mem::take(&mut self.spaces);
 
         if let Some(lines) = mem::take(&mut self.line).into_indent() {
             for _ in 0..lines {
-                self.write.write_line(self.config)?;
+                self.write_line()?;
             }
 
-            let level = i16::max(self.indent, 0) as usize;
+            let level = i16::max(self.indent, 0) as usize + self.config.base_indent;
 
             match self.config.indentation {
                 Indentation::Space(n) => {
@@ -288,7 +536,7 @@ impl<'a> Formatter<'a> {
 
                     while tabs > 0 {
                         let len = usize::min(tabs, TABS.len());
-                        self.write.write_str(&TABS[0..len])?;
+                        self.write_line_content(&TABS[0..len])?;
                         tabs -= len;
                     }
                 }
@@ -297,7 +545,7 @@ impl<'a> Formatter<'a> {
 
         while spaces > 0 {
             let len = usize::min(spaces, SPACES.len());
-            self.write.write_str(&SPACES[0..len])?;
+            self.write_line_content(&SPACES[0..len])?;
             spaces -= len;
         }
 
@@ -305,7 +553,7 @@ impl<'a> Formatter<'a> {
     }
 }
 
-impl<'a> std::fmt::Write for Formatter<'a> {
+impl<'a> core::fmt::Write for Formatter<'a> {
     fn write_str(&mut self, s: &str) -> fmt::Result {
         if !s.is_empty() {
             Formatter::write_str(self, s)?;
@@ -315,12 +563,20 @@ impl<'a> std::fmt::Write for Formatter<'a> {
     }
 }
 
-impl<'a> std::fmt::Debug for Formatter<'a> {
-    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<'a> core::fmt::Debug for Formatter<'a> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         fmt.debug_struct("Formatter")
             .field("line", &self.line)
             .field("spaces", &self.spaces)
             .field("indent", &self.indent)
+            .field("empty_block", &self.empty_block)
+            .field("collapse", &self.collapse)
+            .field("line_empty", &self.line_empty)
+            .field("blank_lines", &self.blank_lines)
+            .field("quote", &self.quote)
+            .field("lines", &self.lines)
+            .field("source", &self.source)
+            .field("sources", &self.sources)
             .field("config", self.config)
             .finish()
     }
@@ -4,6 +4,9 @@ use std::io;
 /// Helper struct to format a token stream to an underlying writer implementing
 /// [io::Write][std::io::Write].
 ///
+/// This is only available with the `std` feature. Under `no_std`, format to a
+/// [fmt::Write][std::fmt::Write] through [FmtWriter][fmt::FmtWriter] instead.
+///
 /// # Examples
 ///
 /// ```
@@ -0,0 +1,46 @@
+use crate::fmt;
+
+/// Writer which counts the lines that would be produced, without keeping any
+/// of the written text.
+///
+/// Lines are counted the same way as [VecWriter][fmt::VecWriter] splits them.
+pub(crate) struct LineCounter {
+    lines: usize,
+}
+
+impl LineCounter {
+    /// Construct a new line counter.
+    pub(crate) fn new() -> Self {
+        Self { lines: 1 }
+    }
+
+    /// Convert into a formatter.
+    pub(crate) fn as_formatter<'a>(&'a mut self, config: &'a fmt::Config) -> fmt::Formatter<'a> {
+        fmt::Formatter::new(self, config)
+    }
+
+    /// Get the number of lines counted.
+    pub(crate) fn lines(&self) -> usize {
+        self.lines
+    }
+}
+
+impl core::fmt::Write for LineCounter {
+    #[inline(always)]
+    fn write_str(&mut self, _: &str) -> core::fmt::Result {
+        Ok(())
+    }
+}
+
+impl fmt::Write for LineCounter {
+    #[inline(always)]
+    fn write_line(&mut self, _: &fmt::Config) -> fmt::Result {
+        self.lines += 1;
+        Ok(())
+    }
+
+    // NB: trailing line is ignored, like for the vector writer.
+    fn write_trailing_line(&mut self, _: &fmt::Config) -> fmt::Result {
+        Ok(())
+    }
+}
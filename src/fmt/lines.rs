@@ -0,0 +1,81 @@
+use core::iter::FusedIterator;
+
+use crate::fmt;
+use alloc::collections::VecDeque;
+use alloc::string::String;
+
+/// Iterator over formatted lines.
+///
+/// This is a sink which yields a line every time the formatter completes one,
+/// so lines are split the same way as [VecWriter][fmt::VecWriter] splits them.
+/// Since [Lang::format_file][crate::lang::Lang::format_file] formats a file in
+/// one call, the lines it yields are queued up until they are requested, but
+/// the output is never formatted into a single string.
+///
+/// Created through [Tokens::lines][crate::Tokens::lines].
+#[derive(Debug, Default)]
+pub struct Lines {
+    line_buffer: String,
+    lines: VecDeque<String>,
+}
+
+impl Lines {
+    /// Construct a new line sink.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convert into a formatter.
+    pub(crate) fn as_formatter<'a>(&'a mut self, config: &'a fmt::Config) -> fmt::Formatter<'a> {
+        fmt::Formatter::new(self, config)
+    }
+
+    /// Finish writing, yielding the last line.
+    pub(crate) fn finish(mut self) -> Self {
+        let line = core::mem::take(&mut self.line_buffer);
+        self.lines.push_back(line);
+        self
+    }
+}
+
+impl Iterator for Lines {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.lines.len(), Some(self.lines.len()))
+    }
+}
+
+impl ExactSizeIterator for Lines {}
+
+impl FusedIterator for Lines {}
+
+impl core::fmt::Write for Lines {
+    #[inline(always)]
+    fn write_char(&mut self, c: char) -> core::fmt::Result {
+        self.line_buffer.write_char(c)
+    }
+
+    #[inline(always)]
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.line_buffer.write_str(s)
+    }
+}
+
+impl fmt::Write for Lines {
+    #[inline(always)]
+    fn write_line(&mut self, _: &fmt::Config) -> fmt::Result {
+        let line = core::mem::take(&mut self.line_buffer);
+        self.lines.push_back(line);
+        Ok(())
+    }
+
+    // NB: trailing line is ignored, like for the vector writer.
+    fn write_trailing_line(&mut self, _: &fmt::Config) -> fmt::Result {
+        Ok(())
+    }
+}
@@ -9,7 +9,7 @@
 //! * [fmt::FmtWriter][FmtWriter] - To write the result into something
 //!   implementing [fmt::Write][std::fmt::Write].
 //! * [fmt::IoWriter][IoWriter]- To write the result into something implementing
-//!   [io::Write][std::io::Write].
+//!   [io::Write][std::io::Write]. This requires the `std` feature.
 //!
 //! # Examples
 //!
@@ -51,22 +51,30 @@ mod config;
 mod cursor;
 mod fmt_writer;
 mod formatter;
+#[cfg(feature = "std")]
 mod io_writer;
+mod line_counter;
+mod lines;
+mod space_operators;
 mod vec_writer;
 
 pub use self::config::{Config, Indentation};
 pub use self::fmt_writer::FmtWriter;
 pub use self::formatter::Formatter;
+#[cfg(feature = "std")]
 pub use self::io_writer::IoWriter;
+pub(crate) use self::line_counter::LineCounter;
+pub use self::lines::Lines;
+pub use self::space_operators::space_operators;
 pub use self::vec_writer::VecWriter;
 
 /// Result type for the `fmt` module.
-pub type Result<T = ()> = std::result::Result<T, std::fmt::Error>;
+pub type Result<T = ()> = core::result::Result<T, core::fmt::Error>;
 /// Error for the `fmt` module.
-pub type Error = std::fmt::Error;
+pub type Error = core::fmt::Error;
 
 /// Trait that defines a line writer.
-pub(crate) trait Write: std::fmt::Write {
+pub(crate) trait Write: core::fmt::Write {
     /// Implement for writing a line.
     fn write_line(&mut self, config: &Config) -> Result;
 
@@ -0,0 +1,116 @@
+use core::fmt::Write as _;
+
+use crate::fmt;
+
+/// Write a literal run of text, adding spaces around binary operators which
+/// are written without them, so that `a+b` is written as `a + b`.
+///
+/// A run of operator characters like `+`, `==`, or `&&` is only spaced if it
+/// is directly preceded by an operand, like an identifier, a number, or a
+/// closing bracket, and directly followed by one. So unary operators like the
+/// `-` in `f(-x)` and `a=-b` are left alone, as are exponents like `1e-5`. Operators at
+/// the start or the end of the literal are written as-is, since the spacing
+/// between separate literals is decided by the token stream.
+///
+/// This is intended to be used by languages which opt into it through
+/// [Lang::write_literal][crate::lang::Lang::write_literal].
+///
+/// # Examples
+///
+/// ```
+/// use genco::fmt;
+///
+/// let mut out = String::new();
+///
+/// {
+///     let config = fmt::Config::from_lang::<()>();
+///     let mut w = fmt::FmtWriter::new(&mut out);
+///     let mut f = w.as_formatter(&config);
+///     fmt::space_operators(&mut f, "x=f(-a)*b.c[1]+1e-5 y=-x")?;
+/// }
+///
+/// assert_eq!("x = f(-a) * b.c[1] + 1e-5 y = -x", out);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn space_operators(out: &mut fmt::Formatter<'_>, literal: &str) -> fmt::Result {
+    let mut start = 0;
+    // Start of the word currently being written, used to detect exponents.
+    let mut word = 0;
+    let mut it = literal.char_indices().peekable();
+
+    while let Some((n, c)) = it.next() {
+        if !is_operator(c) {
+            if !is_word(c) {
+                word = n + c.len_utf8();
+            }
+
+            continue;
+        }
+
+        let mut end = n + c.len_utf8();
+
+        while let Some(&(e, c)) = it.peek() {
+            if !is_operator(c) {
+                break;
+            }
+
+            end = e + c.len_utf8();
+            it.next();
+        }
+
+        let op = &literal[n..end];
+        let before = literal[..n].chars().next_back();
+        let after = literal[end..].chars().next();
+
+        let operand_before = before.map(ends_operand).unwrap_or(false);
+        let operand_after = after.map(starts_operand).unwrap_or(false);
+
+        if operand_before && operand_after && !is_exponent(&literal[word..n], op) {
+            let (op, unary) = split_unary(op);
+            out.write_str(&literal[start..n])?;
+            out.write_char(' ')?;
+            out.write_str(op)?;
+            out.write_char(' ')?;
+            out.write_str(unary)?;
+            start = end;
+        }
+
+        word = end;
+    }
+
+    out.write_str(&literal[start..])
+}
+
+fn is_operator(c: char) -> bool {
+    matches!(
+        c,
+        '+' | '-' | '*' | '/' | '%' | '=' | '<' | '>' | '!' | '&' | '|' | '^'
+    )
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn ends_operand(c: char) -> bool {
+    is_word(c) || matches!(c, ')' | ']' | '}' | '"' | '\'')
+}
+
+fn starts_operand(c: char) -> bool {
+    is_word(c) || matches!(c, '(' | '[' | '"' | '\'')
+}
+
+/// Split off a trailing unary operator, like the `-` in `a=-b`.
+fn split_unary(op: &str) -> (&str, &str) {
+    match op.as_bytes() {
+        [.., a, b @ (b'!' | b'-' | b'+')] if a != b => op.split_at(op.len() - 1),
+        _ => (op, ""),
+    }
+}
+
+/// Test if the operator is the sign of an exponent in a number, like `1e-5`.
+fn is_exponent(word: &str, op: &str) -> bool {
+    matches!(op, "+" | "-")
+        && word.starts_with(|c: char| c.is_ascii_digit())
+        && word.ends_with(&['e', 'E'][..])
+}
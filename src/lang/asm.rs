@@ -0,0 +1,251 @@
+//! Specialization for textual assembly code generation.
+//!
+//! Labels are always written at column 0, while instructions follow the
+//! indentation of the token stream. The operands of instructions are aligned
+//! into a column, whose width is set through [Config::with_mnemonic_width].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: asm::Tokens = quote! {
+//!     section .text
+//!     global _start
+//!
+//!     $(asm::label("_start"))
+//!         $(asm::instr("mov", ["rax", "60"]))
+//!         $(asm::instr("xor", ["rdi", "rdi"]))
+//!         syscall
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "section .text",
+//!         "global _start",
+//!         "",
+//!         "_start:",
+//!         "\tmov     rax, 60",
+//!         "\txor     rdi, rdi",
+//!         "\tsyscall",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::fmt;
+use crate::tokens::ItemStr;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+/// Tokens container specialization for assembly.
+pub type Tokens = crate::Tokens<Asm>;
+
+impl_lang! {
+    /// Language specialization for textual assembly.
+    pub Asm {
+        type Config = Config;
+        type Format = Format;
+        type Item = Any;
+
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn line_comment(out: &mut Tokens, line: &str) {
+            out.append(ItemStr::Static(";"));
+
+            if !line.is_empty() {
+                out.space();
+                out.append(line);
+            }
+        }
+
+        fn default_indentation() -> fmt::Indentation {
+            fmt::Indentation::Tab
+        }
+    }
+
+    Label {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+            out.write_unindented(&self.name)?;
+            out.write_str(":")?;
+            Ok(())
+        }
+    }
+
+    Instr {
+        fn format(&self, out: &mut fmt::Formatter<'_>, config: &Config, _: &Format) -> fmt::Result {
+            out.write_str(&self.mnemonic)?;
+
+            if self.operands.is_empty() {
+                return Ok(());
+            }
+
+            let len = self.mnemonic.chars().count();
+
+            for _ in 0..usize::max(config.mnemonic_width.saturating_sub(len), 1) {
+                out.write_char(' ')?;
+            }
+
+            let mut it = self.operands.iter().peekable();
+
+            while let Some(operand) = it.next() {
+                out.write_str(operand)?;
+
+                if it.peek().is_some() {
+                    out.write_str(", ")?;
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl_serde_any!(Label, Instr);
+
+/// Formatting state for assembly.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for assembly.
+#[derive(Debug)]
+pub struct Config {
+    /// The width of the mnemonic column, after which operands start.
+    mnemonic_width: usize,
+}
+
+impl Config {
+    /// Set the width of the mnemonic column, after which the operands of an
+    /// instruction start. Mnemonics which are as long or longer are followed
+    /// by a single space.
+    ///
+    /// This defaults to 8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let toks: asm::Tokens = quote! {
+    ///     $(asm::instr("mov", ["eax", "1"]))
+    ///     $(asm::instr("cmovne", ["eax", "ebx"]))
+    /// };
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// let fmt = fmt::Config::from_lang::<Asm>();
+    /// let config = asm::Config::default().with_mnemonic_width(4);
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(vec!["mov eax, 1", "cmovne eax, ebx"], w.into_vec());
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_mnemonic_width(self, mnemonic_width: usize) -> Self {
+        Self { mnemonic_width }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { mnemonic_width: 8 }
+    }
+}
+
+/// A label, like `main:`.
+///
+/// Created through the [label()] function.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Label {
+    /// The name of the label.
+    name: ItemStr,
+}
+
+/// An instruction with aligned operands, like `mov     eax, 1`.
+///
+/// Created through the [instr()] function.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Instr {
+    /// The mnemonic of the instruction.
+    mnemonic: ItemStr,
+    /// The operands of the instruction.
+    operands: Vec<ItemStr>,
+}
+
+/// Construct a label, which is written at column 0 regardless of the current
+/// indentation.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: asm::Tokens = quote! {
+///     $(asm::label("loop"))
+///         dec ecx
+///         jnz loop
+///         $(asm::label(".done"))
+///         ret
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "loop:",
+///         "\tdec ecx",
+///         "\tjnz loop",
+///         ".done:",
+///         "\tret",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn label<N>(name: N) -> Label
+where
+    N: Into<ItemStr>,
+{
+    Label { name: name.into() }
+}
+
+/// Construct an instruction, whose operands are aligned into a column and
+/// separated by commas.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: asm::Tokens = quote! {
+///     $(asm::instr("push", ["rbp"]))
+///     $(asm::instr("mov", ["rbp", "rsp"]))
+///     $(asm::instr("ret", Vec::<&str>::new()))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "push    rbp",
+///         "mov     rbp, rsp",
+///         "ret",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn instr<M, I>(mnemonic: M, operands: I) -> Instr
+where
+    M: Into<ItemStr>,
+    I: IntoIterator,
+    I::Item: Into<ItemStr>,
+{
+    Instr {
+        mnemonic: mnemonic.into(),
+        operands: operands.into_iter().map(Into::into).collect(),
+    }
+}
@@ -0,0 +1,220 @@
+//! Specialization for Bash and shell script generation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let src = "/home/user/My Documents/it's here.txt";
+//!
+//! let toks: bash::Tokens = quote! {
+//!     #!/bin/bash
+//!     set -e
+//!     cp $(bash::single_quoted(src)) $(bash::var("TARGET"))
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "#!/bin/bash",
+//!         "set -e",
+//!         "cp '/home/user/My Documents/it'\\''s here.txt' \"$TARGET\"",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in Bash
+//!
+//! Strings are double-quoted, where `$`, `` ` ``, `"`, and `\` are escaped so
+//! that they aren't expanded by the shell. The same quoting is available
+//! through [double_quoted()], and [single_quoted()] quotes a string so that
+//! nothing in it is expanded.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: bash::Tokens = quote!("costs $5 `now`");
+//! assert_eq!("\"costs \\$5 \\`now\\`\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::fmt;
+use crate::lang::Lang;
+use crate::tokens::{quoted, FormatInto, ItemStr};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Tokens container specialization for Bash.
+pub type Tokens = crate::Tokens<Bash>;
+
+/// Language specialization for Bash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bash(());
+
+impl Lang for Bash {
+    type Config = Config;
+    type Format = Format;
+    type Item = ();
+
+    fn default_config() -> Self::Config {
+        Config::default()
+    }
+
+    fn concat(out: &mut Tokens, parts: Vec<Tokens>) {
+        // NB: words which are written next to each other are concatenated.
+        for part in parts {
+            out.append(part);
+        }
+    }
+
+    fn line_comment(out: &mut Tokens, line: &str) {
+        out.append(ItemStr::Static("#"));
+
+        if !line.is_empty() {
+            out.space();
+            out.append(line);
+        }
+    }
+
+    fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+        use core::fmt::Write as _;
+
+        // From: https://www.gnu.org/software/bash/manual/html_node/Double-Quotes.html
+        for c in input.chars() {
+            match c {
+                '$' | '`' | '"' | '\\' => {
+                    out.write_char('\\')?;
+                    out.write_char(c)?;
+                }
+                c => out.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Formatting state for Bash.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for Bash.
+#[derive(Debug, Default)]
+pub struct Config {}
+
+/// A single-quoted string, like `'hello world'`.
+///
+/// Created through the [single_quoted()] function.
+#[derive(Debug, Clone)]
+pub struct SingleQuoted(ItemStr);
+
+impl FormatInto<Bash> for SingleQuoted {
+    fn format_into(self, tokens: &mut Tokens) {
+        let mut out = String::with_capacity(self.0.len() + 2);
+        out.push('\'');
+
+        for c in self.0.chars() {
+            match c {
+                '\'' => out.push_str("'\\''"),
+                c => out.push(c),
+            }
+        }
+
+        out.push('\'');
+        tokens.append(ItemStr::from(out));
+    }
+}
+
+/// A double-quoted string, like `"hello world"`.
+///
+/// Created through the [double_quoted()] function.
+#[derive(Debug, Clone)]
+pub struct DoubleQuoted(ItemStr);
+
+impl FormatInto<Bash> for DoubleQuoted {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(quoted(self.0));
+    }
+}
+
+/// A reference to a variable, like `"$name"`.
+///
+/// Created through the [var()] function.
+#[derive(Debug, Clone)]
+pub struct Var(ItemStr);
+
+impl FormatInto<Bash> for Var {
+    fn format_into(self, tokens: &mut Tokens) {
+        let mut out = String::with_capacity(self.0.len() + 3);
+        out.push_str("\"$");
+        out.push_str(&self.0);
+        out.push('"');
+        tokens.append(ItemStr::from(out));
+    }
+}
+
+/// Quote a string in single quotes, so that nothing in it is expanded by the
+/// shell.
+///
+/// Single quotes can't be escaped inside of a single-quoted string, so they
+/// are written by closing the string, adding an escaped quote, and opening it
+/// again like `'\''`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: bash::Tokens = quote!(echo $(bash::single_quoted("it's $HOME")));
+/// assert_eq!("echo 'it'\\''s $HOME'", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn single_quoted<S>(s: S) -> SingleQuoted
+where
+    S: Into<ItemStr>,
+{
+    SingleQuoted(s.into())
+}
+
+/// Quote a string in double quotes, where `$`, `` ` ``, `"`, and `\` are
+/// escaped so that they aren't expanded by the shell.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: bash::Tokens = quote!(echo $(bash::double_quoted("say \"$HOME\"")));
+/// assert_eq!("echo \"say \\\"\\$HOME\\\"\"", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn double_quoted<S>(s: S) -> DoubleQuoted
+where
+    S: Into<ItemStr>,
+{
+    DoubleQuoted(s.into())
+}
+
+/// Reference a variable in double quotes, like `"$name"`, so that its value
+/// isn't split on whitespace.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: bash::Tokens = quote!(rm -r $(bash::var("BUILD_DIR")));
+/// assert_eq!("rm -r \"$BUILD_DIR\"", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn var<N>(name: N) -> Var
+where
+    N: Into<ItemStr>,
+{
+    Var(name.into())
+}
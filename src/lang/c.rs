@@ -2,10 +2,11 @@
 
 use crate as genco;
 use crate::fmt;
+use crate::lang::import_order::{self, ImportOrder, ImportSet};
 use crate::quote_in;
 use crate::tokens::{quoted, ItemStr};
-use std::collections::BTreeSet;
-use std::fmt::Write as _;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
 
 /// Tokens container specialization for C.
 pub type Tokens = crate::Tokens<C>;
@@ -17,6 +18,18 @@ impl_lang! {
         type Format = Format;
         type Item = Import;
 
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn concat(out: &mut Tokens, parts: Vec<Tokens>) {
+            // NB: C has no operator for concatenating strings, but adjacent
+            // string literals are concatenated by the compiler. This is only
+            // valid C if every part is a string literal, or a macro which
+            // expands to one.
+            quote_in!(*out => $(for part in parts join ( ) => $part));
+        }
+
         fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
             super::c_family_write_quoted(out, input)
         }
@@ -28,7 +41,7 @@ impl_lang! {
         ) -> fmt::Result {
             let mut header = Tokens::new();
 
-            Self::imports(&mut header, tokens);
+            Self::imports(&mut header, tokens, config);
             let format = Format::default();
             header.format(out, config, &format)?;
             tokens.format(out, config, &format)?;
@@ -49,6 +62,7 @@ impl_lang! {
 ///
 /// Created using the [include()] function.
 #[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Import {
     /// Path to included file.
     path: ItemStr,
@@ -64,13 +78,23 @@ pub struct Format {}
 
 /// Config data for C.
 #[derive(Debug, Default)]
-pub struct Config {}
+pub struct Config {
+    /// The order in which includes are rendered.
+    import_order: ImportOrder,
+}
+
+impl Config {
+    /// Set the order in which includes are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self { import_order }
+    }
+}
 
 impl C {
-    fn imports(out: &mut Tokens, tokens: &Tokens) {
-        let mut includes = BTreeSet::new();
+    fn imports(out: &mut Tokens, tokens: &Tokens, config: &Config) {
+        let mut includes = ImportSet::new(config.import_order);
 
-        for include in tokens.walk_imports() {
+        for include in import_order::walk_imports(tokens) {
             includes.insert((&include.path, include.system));
         }
 
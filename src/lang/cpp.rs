@@ -0,0 +1,457 @@
+//! Specialization for C++ code generation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let vector = cpp::imported("std", "vector");
+//! let widget = cpp::include("widget.h", "Widget");
+//!
+//! let toks: cpp::Tokens = quote! {
+//!     $(vector.clone().with_arguments([quote!(int)])) sizes(const $(vector.with_arguments([&widget]))& widgets);
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "#include <vector>",
+//!         "#include \"widget.h\"",
+//!         "",
+//!         "std::vector<int> sizes(const std::vector<Widget>& widgets);",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use crate as genco;
+use crate::fmt;
+use crate::lang::import_order::{self, ImportOrder, ImportSet};
+use crate::quote_in;
+use crate::tokens::{quoted, FormatInto, ItemStr};
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+/// Tokens container specialization for C++.
+pub type Tokens = crate::Tokens<Cpp>;
+
+impl_lang! {
+    /// Language specialization for C++.
+    pub Cpp {
+        type Config = Config;
+        type Format = Format;
+        type Item = Import;
+
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            super::c_family_write_quoted(out, input)
+        }
+
+        fn format_file(
+            tokens: &Tokens,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut header = Tokens::new();
+
+            Self::imports(&mut header, tokens, config);
+            let format = Format::default();
+            header.format(out, config, &format)?;
+            tokens.format(out, config, &format)?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, config: &Config, _: &Format) -> fmt::Result {
+            if let Some(namespace) = &self.namespace {
+                if !config.using_namespaces.contains(namespace) {
+                    out.write_str(namespace)?;
+                    out.write_str("::")?;
+                }
+            }
+
+            out.write_str(&self.name)?;
+            Ok(())
+        }
+    }
+}
+
+/// An item declared in a header, which causes an `#include "foo.h"` or
+/// `#include <vector>` to be added to the file.
+///
+/// Created using the [include()], [include_system()], and [imported()]
+/// functions.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Import {
+    /// Path to the included header.
+    path: ItemStr,
+    /// True if the include is a system header using `<>`, false if a local
+    /// header using `""`.
+    system: bool,
+    /// Namespace the item is declared in.
+    namespace: Option<ItemStr>,
+    /// Name of the item.
+    name: ItemStr,
+}
+
+impl Import {
+    /// Include the item from the given header instead of the one it was
+    /// created with.
+    ///
+    /// The header is still a system header or a local header like before, use
+    /// [Import::with_local_header] to include an item imported through
+    /// [imported()] from a local header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let unique_ptr = cpp::imported("std", "unique_ptr").with_header("memory");
+    ///
+    /// let toks = quote!($unique_ptr);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "#include <memory>",
+    ///         "",
+    ///         "std::unique_ptr",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_header<P>(self, path: P) -> Self
+    where
+        P: Into<ItemStr>,
+    {
+        Self {
+            path: path.into(),
+            ..self
+        }
+    }
+
+    /// Include the item from the given local header, like
+    /// `#include "foo/bar.h"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let widget = cpp::imported("ui", "Widget").with_local_header("ui/widget.h");
+    ///
+    /// let toks = quote!($widget w;);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "#include \"ui/widget.h\"",
+    ///         "",
+    ///         "ui::Widget w;",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_local_header<P>(self, path: P) -> Self
+    where
+        P: Into<ItemStr>,
+    {
+        Self {
+            path: path.into(),
+            system: false,
+            ..self
+        }
+    }
+
+    /// Add template arguments to the item, like `std::vector<int>`.
+    ///
+    /// Arguments are regular token streams, so any items they use are
+    /// included as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let map = cpp::imported("std", "map");
+    /// let string = cpp::imported("std", "string");
+    ///
+    /// let toks = quote!($(map.with_arguments([quote!($string), quote!(int)])) counts;);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "#include <map>",
+    ///         "#include <string>",
+    ///         "",
+    ///         "std::map<std::string, int> counts;",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_arguments<A>(self, arguments: A) -> Template
+    where
+        A: IntoIterator,
+        A::Item: FormatInto<Cpp>,
+    {
+        Template {
+            import: self,
+            arguments: arguments
+                .into_iter()
+                .map(|argument| {
+                    let mut t = Tokens::new();
+                    t.append(argument);
+                    t
+                })
+                .collect(),
+        }
+    }
+}
+
+/// An item with template arguments, like `std::vector<int>`.
+///
+/// Created through [Import::with_arguments].
+#[derive(Debug, Clone)]
+pub struct Template {
+    import: Import,
+    arguments: Vec<Tokens>,
+}
+
+impl FormatInto<Cpp> for Template {
+    fn format_into(self, tokens: &mut Tokens) {
+        quote_in!(*tokens => $(self.import)<$(for a in self.arguments join (, ) => $a)>);
+    }
+}
+
+impl FormatInto<Cpp> for &Template {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(self.clone());
+    }
+}
+
+/// Format for C++.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Config data for C++.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// The order in which includes are rendered.
+    import_order: ImportOrder,
+    /// Namespaces which are brought into scope with `using namespace`.
+    using_namespaces: Vec<ItemStr>,
+}
+
+impl Config {
+    /// Set the order in which includes are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self {
+            import_order,
+            ..self
+        }
+    }
+
+    /// Bring the given namespace into scope with `using namespace`, so that
+    /// items in it are rendered without qualification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let vector = cpp::imported("std", "vector");
+    /// let toks: cpp::Tokens = quote!($(vector.with_arguments([quote!(int)])) values;);
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// let fmt = fmt::Config::from_lang::<Cpp>();
+    /// let config = cpp::Config::default().with_using_namespace("std");
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "#include <vector>",
+    ///         "",
+    ///         "using namespace std;",
+    ///         "",
+    ///         "vector<int> values;",
+    ///     ],
+    ///     w.into_vec()
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_using_namespace<N>(mut self, namespace: N) -> Self
+    where
+        N: Into<ItemStr>,
+    {
+        self.using_namespaces.push(namespace.into());
+        self
+    }
+}
+
+impl Cpp {
+    fn imports(out: &mut Tokens, tokens: &Tokens, config: &Config) {
+        let mut includes = ImportSet::new(config.import_order);
+
+        for include in import_order::walk_imports(tokens) {
+            includes.insert((&include.path, include.system));
+        }
+
+        if !includes.is_empty() {
+            for (file, system_header) in includes {
+                if system_header {
+                    quote_in!(*out => #include <$(file)>);
+                } else {
+                    quote_in!(*out => #include $(quoted(file)));
+                }
+                out.push();
+            }
+
+            out.line();
+        }
+
+        if !config.using_namespaces.is_empty() {
+            for namespace in &config.using_namespaces {
+                quote_in!(*out => using namespace $namespace;);
+                out.push();
+            }
+
+            out.line();
+        }
+    }
+}
+
+/// Include an item declared in a local header file such as
+/// `#include "foo/bar.h"`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let widget = cpp::include("ui/widget.h", "Widget");
+///
+/// let toks = quote!($widget w;);
+///
+/// assert_eq!(
+///     vec![
+///         "#include \"ui/widget.h\"",
+///         "",
+///         "Widget w;",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn include<P, N>(path: P, name: N) -> Import
+where
+    P: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    Import {
+        path: path.into(),
+        system: false,
+        namespace: None,
+        name: name.into(),
+    }
+}
+
+/// Include an item declared in a system header such as `#include <cstdio>`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let printf = cpp::include_system("cstdio", "printf");
+///
+/// let toks = quote!($printf("hello\n"););
+///
+/// assert_eq!(
+///     vec![
+///         "#include <cstdio>",
+///         "",
+///         "printf(\"hello\\n\");",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn include_system<P, N>(path: P, name: N) -> Import
+where
+    P: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    Import {
+        path: path.into(),
+        system: true,
+        namespace: None,
+        name: name.into(),
+    }
+}
+
+/// Import an item from a namespace, which includes the system header of the
+/// same name as the item, like `#include <vector>` for `std::vector`.
+///
+/// Use [Import::with_header] if the item is declared in a different system
+/// header, or [Import::with_local_header] if it's declared in a local one.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let vector = cpp::imported("std", "vector");
+///
+/// let toks = quote! {
+///     int sum(const $(vector.with_arguments([quote!(int)]))& values) {
+///         int total = 0;
+///
+///         for (int v : values) {
+///             total += v;
+///         }
+///
+///         return total;
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "#include <vector>",
+///         "",
+///         "int sum(const std::vector<int>& values) {",
+///         "    int total = 0;",
+///         "",
+///         "    for (int v : values) {",
+///         "        total += v;",
+///         "    }",
+///         "",
+///         "    return total;",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn imported<M, N>(namespace: M, name: N) -> Import
+where
+    M: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    let name = name.into();
+
+    Import {
+        path: name.clone(),
+        system: true,
+        namespace: Some(namespace.into()),
+        name,
+    }
+}
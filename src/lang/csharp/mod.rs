@@ -22,10 +22,13 @@ mod comment;
 
 use crate as genco;
 use crate::fmt;
+use crate::lang::import_order::{self, ImportOrder, ImportSet};
 use crate::quote_in;
 use crate::tokens::ItemStr;
-use std::collections::{BTreeSet, HashMap, HashSet};
-use std::fmt::Write as _;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::string::ToString;
+use core::fmt::Write as _;
 
 pub use self::block_comment::BlockComment;
 pub use self::comment::Comment;
@@ -40,6 +43,10 @@ impl_lang! {
         type Format = Format;
         type Item = Import;
 
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
         fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
             // From: https://csharpindepth.com/articles/Strings
             super::c_family_write_quoted(out, input)
@@ -119,7 +126,7 @@ pub struct Format {
     /// their use has to be qualified or not.
     ///
     /// A missing name means that it has to be used in a qualified manner.
-    imported_names: HashMap<String, String>,
+    imported_names: BTreeMap<String, String>,
 }
 
 /// Config data for Csharp formatting.
@@ -127,6 +134,8 @@ pub struct Format {
 pub struct Config {
     /// namespace to use.
     namespace: Option<ItemStr>,
+    /// The order in which using statements are rendered.
+    import_order: ImportOrder,
 }
 
 impl Config {
@@ -137,6 +146,15 @@ impl Config {
     {
         Self {
             namespace: Some(namespace.into()),
+            ..self
+        }
+    }
+
+    /// Set the order in which using statements are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self {
+            import_order,
+            ..self
         }
     }
 }
@@ -145,6 +163,7 @@ impl Config {
 ///
 /// Created through the [import()] function.
 #[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Import {
     /// namespace of the class.
     namespace: ItemStr,
@@ -170,11 +189,11 @@ impl Csharp {
         out: &mut Tokens,
         tokens: &Tokens,
         config: &Config,
-        imported_names: &mut HashMap<String, String>,
+        imported_names: &mut BTreeMap<String, String>,
     ) {
-        let mut modules = BTreeSet::new();
+        let mut modules = ImportSet::new(config.import_order);
 
-        for import in tokens.walk_imports() {
+        for import in import_order::walk_imports(tokens) {
             modules.insert((&*import.namespace, &*import.name));
         }
 
@@ -182,7 +201,7 @@ impl Csharp {
             return;
         }
 
-        let mut imported = HashSet::new();
+        let mut imported = BTreeSet::new();
 
         for (namespace, name) in modules {
             if Some(namespace) == config.namespace.as_deref() {
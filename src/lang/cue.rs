@@ -0,0 +1,348 @@
+//! Specialization for CUE code generation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: cue::Tokens = quote! {
+//!     server: $(cue::struct_(quote! {
+//!         host: "localhost"
+//!         ports: $(cue::list([quote!(80), quote!(443)]))
+//!     }))
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "server: {",
+//!         "\thost: \"localhost\"",
+//!         "\tports: [",
+//!         "\t\t80,",
+//!         "\t\t443,",
+//!         "\t]",
+//!         "}",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in CUE
+//!
+//! Since CUE interpolates strings through `\(`, backslashes are always
+//! escaped so that text which looks like an interpolation is kept as-is.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: cue::Tokens = quote!("hello \\(name) \"world\"\n");
+//! assert_eq!("\"hello \\\\(name) \\\"world\\\"\\n\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Interpolation in CUE
+//!
+//! Strings can be interpolated by using the special `$[str](<string>)` escape
+//! sequence.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: cue::Tokens = quote!($[str](Listening on $host:$(port + 1)));
+//! assert_eq!("\"Listening on \\(host):\\(port + 1)\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate as genco;
+use crate::fmt;
+use crate::lang::Lang;
+use crate::quote_in;
+use crate::tokens::{FormatInto, ItemStr};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+/// Tokens container specialization for CUE.
+pub type Tokens = crate::Tokens<Cue>;
+
+/// Language specialization for CUE.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cue(());
+
+impl crate::lang::LangSupportsEval for Cue {}
+
+impl Lang for Cue {
+    type Config = Config;
+    type Format = Format;
+    type Item = ();
+
+    fn default_config() -> Self::Config {
+        Config::default()
+    }
+
+    fn default_indentation() -> fmt::Indentation {
+        fmt::Indentation::Tab
+    }
+
+    fn ternary(out: &mut Tokens, cond: Tokens, then: Tokens, els: Tokens) {
+        // NB: CUE has no conditional expression, so a list with one element
+        // guarded by each outcome of the condition is indexed instead.
+        quote_in!(*out => [if $(&cond) { $then }, if !($cond) { $els }][0]);
+    }
+
+    fn string_eval_literal(
+        out: &mut fmt::Formatter<'_>,
+        _config: &Self::Config,
+        _format: &Self::Format,
+        literal: &str,
+    ) -> fmt::Result {
+        write!(out, "\\({})", literal)?;
+        Ok(())
+    }
+
+    fn start_string_eval(
+        out: &mut fmt::Formatter<'_>,
+        _config: &Self::Config,
+        _format: &Self::Format,
+    ) -> fmt::Result {
+        out.write_str("\\(")?;
+        Ok(())
+    }
+
+    fn end_string_eval(
+        out: &mut fmt::Formatter<'_>,
+        _config: &Self::Config,
+        _format: &Self::Format,
+    ) -> fmt::Result {
+        out.write_char(')')?;
+        Ok(())
+    }
+
+    fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+        // From: https://cuelang.org/docs/reference/spec/#string-and-byte-sequence-literals
+        for c in input.chars() {
+            match c {
+                '\u{0007}' => out.write_str("\\a")?,
+                '\u{0008}' => out.write_str("\\b")?,
+                '\u{000c}' => out.write_str("\\f")?,
+                '\n' => out.write_str("\\n")?,
+                '\r' => out.write_str("\\r")?,
+                '\t' => out.write_str("\\t")?,
+                '\u{000b}' => out.write_str("\\v")?,
+                '"' => out.write_str("\\\"")?,
+                '\\' => out.write_str("\\\\")?,
+                c if c.is_control() || (!c.is_ascii() && out.ascii_only()) => {
+                    if (c as u32) < 0x10000 {
+                        write!(out, "\\u{:04x}", c as u32)?;
+                    } else {
+                        write!(out, "\\U{:08x}", c as u32)?;
+                    }
+                }
+                c => out.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Formatting state for CUE.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for CUE.
+#[derive(Debug, Default)]
+pub struct Config {}
+
+/// A struct, like `{ name: "value" }`.
+///
+/// Created through the [struct_()] function.
+#[derive(Debug, Clone)]
+pub struct Struct {
+    /// The fields of the struct.
+    body: Tokens,
+}
+
+impl FormatInto<Cue> for Struct {
+    fn format_into(self, tokens: &mut Tokens) {
+        quote_in! { *tokens =>
+            {
+                $(self.body)
+            }
+        }
+    }
+}
+
+/// A list, like `[1, 2, 3]`.
+///
+/// Created through the [list()] function.
+#[derive(Debug, Clone)]
+pub struct List {
+    /// The elements of the list.
+    items: Vec<Tokens>,
+}
+
+impl FormatInto<Cue> for List {
+    fn format_into(self, tokens: &mut Tokens) {
+        quote_in! { *tokens =>
+            [
+                $(for item in self.items join ($['\r']) => $item,)
+            ]
+        }
+    }
+}
+
+/// A multiline string, which starts and ends with `"""`.
+///
+/// Created through the [multiline()] function.
+#[derive(Debug, Clone)]
+pub struct Multiline(ItemStr);
+
+impl FormatInto<Cue> for Multiline {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(ItemStr::Static("\"\"\""));
+        tokens.indent();
+
+        for line in self.0.split('\n') {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            if line.is_empty() {
+                tokens.line();
+                continue;
+            }
+
+            let mut escaped = String::with_capacity(line.len());
+
+            for (n, c) in line.char_indices() {
+                match c {
+                    '\\' => escaped.push_str("\\\\"),
+                    '"' if line[n..].starts_with("\"\"\"") => escaped.push_str("\\\""),
+                    c if c.is_control() && c != '\t' => {
+                        let _ = write!(escaped, "\\u{:04x}", c as u32);
+                    }
+                    c => escaped.push(c),
+                }
+            }
+
+            tokens.push();
+            tokens.append(ItemStr::from(escaped));
+        }
+
+        tokens.push();
+        tokens.append(ItemStr::Static("\"\"\""));
+        tokens.unindent();
+    }
+}
+
+/// Construct a struct with the given fields, which are indented inside of
+/// braces.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: cue::Tokens = quote! {
+///     a: $(cue::struct_(quote! {
+///         b: $(cue::struct_(quote!(c: int)))
+///         d: $(cue::struct_(cue::Tokens::new()))
+///     }))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "a: {",
+///         "\tb: {",
+///         "\t\tc: int",
+///         "\t}",
+///         "\td: {}",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn struct_(body: Tokens) -> Struct {
+    Struct { body }
+}
+
+/// Construct a list with one element per line, each followed by a comma.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: cue::Tokens = quote! {
+///     names: $(cue::list(["a", "b"].iter().map(quoted)))
+///     empty: $(cue::list(Vec::<cue::Tokens>::new()))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "names: [",
+///         "\t\"a\",",
+///         "\t\"b\",",
+///         "]",
+///         "empty: []",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn list<I>(items: I) -> List
+where
+    I: IntoIterator,
+    I::Item: FormatInto<Cue>,
+{
+    List {
+        items: items
+            .into_iter()
+            .map(|item| {
+                let mut tokens = Tokens::new();
+                tokens.append(item);
+                tokens
+            })
+            .collect(),
+    }
+}
+
+/// Construct a multiline string, where each line is indented one level
+/// deeper than the surrounding code.
+///
+/// Backslashes and `"""` are escaped, so text like `\(name)` is kept as-is
+/// instead of being interpolated. Note that consecutive blank lines are
+/// collapsed into one, like everywhere else in genco.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: cue::Tokens = quote! {
+///     script: $(cue::multiline("echo \\(date)\nexit 0"))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "script: \"\"\"",
+///         "\techo \\\\(date)",
+///         "\texit 0",
+///         "\t\"\"\"",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn multiline<S>(s: S) -> Multiline
+where
+    S: Into<ItemStr>,
+{
+    Multiline(s.into())
+}
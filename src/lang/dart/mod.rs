@@ -39,9 +39,10 @@ pub use self::doc_comment::DocComment;
 
 use crate as genco;
 use crate::fmt;
+use crate::lang::import_order::{self, ImportOrder, ImportSet};
 use crate::quote_in;
 use crate::tokens::{quoted, ItemStr};
-use std::fmt::Write as _;
+use core::fmt::Write as _;
 
 const SEP: &str = ".";
 /// dart:core package.
@@ -59,6 +60,10 @@ impl_lang! {
         type Format = Format;
         type Item = Import;
 
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
         fn string_eval_literal(
             out: &mut fmt::Formatter<'_>,
             _config: &Self::Config,
@@ -89,6 +94,10 @@ impl_lang! {
             Ok(())
         }
 
+        fn is_builtin_import(import: &Self::Item) -> bool {
+            &*import.path == DART_CORE
+        }
+
         fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
             // Note: Dart is like C escape, but since it supports string
             // interpolation, `$` also needs to be escaped!
@@ -112,6 +121,11 @@ impl_lang! {
                     '"' => out.write_str("\\\"")?,
                     '\\' => out.write_str("\\\\")?,
                     '$' => out.write_str("\\$")?,
+                    c if !c.is_ascii() && out.ascii_only() => {
+                        for c in c.encode_utf16(&mut [0u16; 2]) {
+                            write!(out, "\\u{:04x}", c)?;
+                        }
+                    }
                     c if !c.is_control() => out.write_char(c)?,
                     c if (c as u32) < 0x100 => {
                         write!(out, "\\x{:02x}", c as u32)?;
@@ -160,12 +174,23 @@ pub struct Format {}
 
 /// Config data for Dart formatting.
 #[derive(Debug, Default)]
-pub struct Config {}
+pub struct Config {
+    /// The order in which imports are rendered.
+    import_order: ImportOrder,
+}
+
+impl Config {
+    /// Set the order in which imports are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self { import_order }
+    }
+}
 
 /// The import of a Dart type `import "dart:math";`.
 ///
 /// Created through the [import()] function.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Import {
     /// Path to import.
     path: ItemStr,
@@ -187,16 +212,10 @@ impl Import {
 
 impl Dart {
     /// Resolve all imports.
-    fn imports(out: &mut Tokens, input: &Tokens, _: &Config) {
-        use std::collections::BTreeSet;
-
-        let mut modules = BTreeSet::new();
-
-        for import in input.walk_imports() {
-            if &*import.path == DART_CORE {
-                continue;
-            }
+    fn imports(out: &mut Tokens, input: &Tokens, config: &Config) {
+        let mut modules = ImportSet::new(config.import_order);
 
+        for import in import_order::walk_imports(input) {
             modules.insert((import.path.clone(), import.alias.clone()));
         }
 
@@ -0,0 +1,473 @@
+//! Specialization for Dockerfile generation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: dockerfile::Tokens = quote! {
+//!     $(dockerfile::from("debian:bookworm-slim"))
+//!     $(dockerfile::run(["apt-get update", "apt-get install -y curl"]))
+//!     $(dockerfile::copy("app", "/usr/local/bin/app"))
+//!     $(dockerfile::entrypoint(["/usr/local/bin/app", "--serve"]))
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "FROM debian:bookworm-slim",
+//!         "RUN apt-get update \\",
+//!         "    && apt-get install -y curl",
+//!         "COPY app /usr/local/bin/app",
+//!         "ENTRYPOINT [\"/usr/local/bin/app\", \"--serve\"]",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in Dockerfiles
+//!
+//! Strings are quoted as JSON strings, which is what the exec form of
+//! instructions like `CMD` and `ENTRYPOINT` expects.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: dockerfile::Tokens = quote!($(quoted("C:\\app \"x\"\n")));
+//! assert_eq!("\"C:\\\\app \\\"x\\\"\\n\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::fmt;
+use crate::lang::Lang;
+use crate::tokens::{quoted, FormatInto, ItemStr};
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+/// Tokens container specialization for Dockerfiles.
+pub type Tokens = crate::Tokens<Dockerfile>;
+
+/// Language specialization for Dockerfiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Dockerfile(());
+
+impl Lang for Dockerfile {
+    type Config = Config;
+    type Format = Format;
+    type Item = ();
+
+    fn default_config() -> Self::Config {
+        Config::default()
+    }
+
+    fn line_comment(out: &mut Tokens, line: &str) {
+        out.append(ItemStr::Static("#"));
+
+        if !line.is_empty() {
+            out.space();
+            out.append(line);
+        }
+    }
+
+    fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+        // From: https://www.json.org/json-en.html
+        for c in input.chars() {
+            match c {
+                '"' => out.write_str("\\\"")?,
+                '\\' => out.write_str("\\\\")?,
+                '\n' => out.write_str("\\n")?,
+                '\r' => out.write_str("\\r")?,
+                '\t' => out.write_str("\\t")?,
+                c if c.is_control() => {
+                    for c in c.encode_utf16(&mut [0u16; 2]) {
+                        write!(out, "\\u{:04x}", c)?;
+                    }
+                }
+                c => out.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Formatting state for Dockerfiles.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for Dockerfiles.
+#[derive(Debug, Default)]
+pub struct Config {}
+
+/// A `FROM` instruction, like `FROM rust:1 AS build`.
+///
+/// Created through the [from()] function.
+#[derive(Debug, Clone)]
+pub struct FromImage {
+    /// The base image.
+    image: ItemStr,
+    /// The name of the build stage.
+    name: Option<ItemStr>,
+}
+
+impl FromImage {
+    /// Name the build stage, like `FROM rust:1 AS build`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let toks: dockerfile::Tokens = quote!($(dockerfile::from("rust:1").with_name("build")));
+    /// assert_eq!("FROM rust:1 AS build", toks.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_name<N>(self, name: N) -> Self
+    where
+        N: Into<ItemStr>,
+    {
+        Self {
+            name: Some(name.into()),
+            ..self
+        }
+    }
+}
+
+impl FormatInto<Dockerfile> for FromImage {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(ItemStr::Static("FROM"));
+        tokens.space();
+        tokens.append(self.image);
+
+        if let Some(name) = self.name {
+            tokens.space();
+            tokens.append(ItemStr::Static("AS"));
+            tokens.space();
+            tokens.append(name);
+        }
+    }
+}
+
+/// A `RUN` instruction, where multiple commands are chained with `&&` on
+/// continuation lines.
+///
+/// Created through the [run()] function.
+#[derive(Debug, Clone)]
+pub struct Run {
+    /// The commands to run.
+    commands: Vec<ItemStr>,
+}
+
+impl FormatInto<Dockerfile> for Run {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(ItemStr::Static("RUN"));
+
+        let mut it = self.commands.into_iter();
+
+        if let Some(first) = it.next() {
+            tokens.space();
+            tokens.append(first);
+        }
+
+        let mut indented = false;
+
+        for command in it {
+            tokens.space();
+            tokens.append(ItemStr::Static("\\"));
+
+            // NB: indenting also moves to the next line.
+            if !indented {
+                tokens.indent();
+                indented = true;
+            } else {
+                tokens.push();
+            }
+
+            tokens.append(ItemStr::Static("&&"));
+            tokens.space();
+            tokens.append(command);
+        }
+
+        if indented {
+            tokens.unindent();
+        }
+    }
+}
+
+/// A `COPY` instruction, like `COPY --from=build src dest`.
+///
+/// Created through the [copy()] function.
+#[derive(Debug, Clone)]
+pub struct CopyFiles {
+    /// The build stage to copy from.
+    stage: Option<ItemStr>,
+    /// The source path.
+    src: ItemStr,
+    /// The destination path.
+    dest: ItemStr,
+}
+
+impl CopyFiles {
+    /// Copy from the given build stage, like `COPY --from=build src dest`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let toks: dockerfile::Tokens = quote!($(dockerfile::copy("/out/app", "/app").with_stage("build")));
+    /// assert_eq!("COPY --from=build /out/app /app", toks.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_stage<S>(self, stage: S) -> Self
+    where
+        S: Into<ItemStr>,
+    {
+        Self {
+            stage: Some(stage.into()),
+            ..self
+        }
+    }
+}
+
+impl FormatInto<Dockerfile> for CopyFiles {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(ItemStr::Static("COPY"));
+        tokens.space();
+
+        if let Some(stage) = self.stage {
+            tokens.append(ItemStr::Static("--from="));
+            tokens.append(stage);
+            tokens.space();
+        }
+
+        tokens.append(self.src);
+        tokens.space();
+        tokens.append(self.dest);
+    }
+}
+
+/// The form of a `CMD` or `ENTRYPOINT` instruction.
+#[derive(Debug, Clone)]
+enum Form {
+    /// The exec form, like `["executable", "arg"]`.
+    Exec(Vec<ItemStr>),
+    /// The shell form, like `executable arg`.
+    Shell(ItemStr),
+}
+
+/// A `CMD` or `ENTRYPOINT` instruction, in either exec or shell form.
+///
+/// Created through the [cmd()], [cmd_shell()], [entrypoint()], and
+/// [entrypoint_shell()] functions.
+#[derive(Debug, Clone)]
+pub struct Command {
+    /// The name of the instruction.
+    instruction: &'static str,
+    /// The form of the instruction.
+    form: Form,
+}
+
+impl FormatInto<Dockerfile> for Command {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(ItemStr::Static(self.instruction));
+        tokens.space();
+
+        match self.form {
+            Form::Exec(args) => {
+                tokens.append(ItemStr::Static("["));
+
+                let mut it = args.into_iter().peekable();
+
+                while let Some(arg) = it.next() {
+                    tokens.append(quoted(arg));
+
+                    if it.peek().is_some() {
+                        tokens.append(ItemStr::Static(","));
+                        tokens.space();
+                    }
+                }
+
+                tokens.append(ItemStr::Static("]"));
+            }
+            Form::Shell(command) => {
+                tokens.append(command);
+            }
+        }
+    }
+}
+
+/// Construct a `FROM` instruction for the given base image.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: dockerfile::Tokens = quote!($(dockerfile::from("alpine:3")));
+/// assert_eq!("FROM alpine:3", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn from<I>(image: I) -> FromImage
+where
+    I: Into<ItemStr>,
+{
+    FromImage {
+        image: image.into(),
+        name: None,
+    }
+}
+
+/// Construct a `RUN` instruction which runs the given commands in a single
+/// layer.
+///
+/// Commands after the first are chained with `&&` on indented continuation
+/// lines, so each line ends with a `\`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: dockerfile::Tokens = quote! {
+///     $(dockerfile::run(["make"]))
+///     $(dockerfile::run(["make", "make install", "make clean"]))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "RUN make",
+///         "RUN make \\",
+///         "    && make install \\",
+///         "    && make clean",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn run<I>(commands: I) -> Run
+where
+    I: IntoIterator,
+    I::Item: Into<ItemStr>,
+{
+    Run {
+        commands: commands.into_iter().map(Into::into).collect(),
+    }
+}
+
+/// Construct a `COPY` instruction which copies `src` to `dest`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: dockerfile::Tokens = quote!($(dockerfile::copy(".", "/src")));
+/// assert_eq!("COPY . /src", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn copy<S, D>(src: S, dest: D) -> CopyFiles
+where
+    S: Into<ItemStr>,
+    D: Into<ItemStr>,
+{
+    CopyFiles {
+        stage: None,
+        src: src.into(),
+        dest: dest.into(),
+    }
+}
+
+/// Construct a `CMD` instruction in exec form, where each argument is quoted
+/// as a JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: dockerfile::Tokens = quote!($(dockerfile::cmd(["echo", "say \"hi\""])));
+/// assert_eq!("CMD [\"echo\", \"say \\\"hi\\\"\"]", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn cmd<I>(args: I) -> Command
+where
+    I: IntoIterator,
+    I::Item: Into<ItemStr>,
+{
+    Command {
+        instruction: "CMD",
+        form: Form::Exec(args.into_iter().map(Into::into).collect()),
+    }
+}
+
+/// Construct a `CMD` instruction in shell form, which is run through
+/// `/bin/sh -c`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: dockerfile::Tokens = quote!($(dockerfile::cmd_shell("echo $HOME")));
+/// assert_eq!("CMD echo $HOME", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn cmd_shell<C>(command: C) -> Command
+where
+    C: Into<ItemStr>,
+{
+    Command {
+        instruction: "CMD",
+        form: Form::Shell(command.into()),
+    }
+}
+
+/// Construct an `ENTRYPOINT` instruction in exec form, where each argument
+/// is quoted as a JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: dockerfile::Tokens = quote!($(dockerfile::entrypoint(["/bin/app"])));
+/// assert_eq!("ENTRYPOINT [\"/bin/app\"]", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn entrypoint<I>(args: I) -> Command
+where
+    I: IntoIterator,
+    I::Item: Into<ItemStr>,
+{
+    Command {
+        instruction: "ENTRYPOINT",
+        form: Form::Exec(args.into_iter().map(Into::into).collect()),
+    }
+}
+
+/// Construct an `ENTRYPOINT` instruction in shell form, which is run through
+/// `/bin/sh -c`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: dockerfile::Tokens = quote!($(dockerfile::entrypoint_shell("exec /bin/app")));
+/// assert_eq!("ENTRYPOINT exec /bin/app", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn entrypoint_shell<C>(command: C) -> Command
+where
+    C: Into<ItemStr>,
+{
+    Command {
+        instruction: "ENTRYPOINT",
+        form: Form::Shell(command.into()),
+    }
+}
@@ -0,0 +1,396 @@
+//! Specialization for Elixir code generation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let user = elixir::alias("MyApp.Accounts.User");
+//! let repo = elixir::alias("MyApp.Repo");
+//!
+//! let toks: elixir::Tokens = quote! {
+//!     defmodule MyApp.Accounts do
+//!         def get_user!(id) do
+//!             $repo.get!($(&user), id)
+//!         end
+//!
+//!         def new_user(name) do
+//!             %$user{name: name}
+//!         end
+//!     end
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "alias MyApp.Accounts.User",
+//!         "alias MyApp.Repo",
+//!         "",
+//!         "defmodule MyApp.Accounts do",
+//!         "  def get_user!(id) do",
+//!         "    Repo.get!(User, id)",
+//!         "  end",
+//!         "",
+//!         "  def new_user(name) do",
+//!         "    %User{name: name}",
+//!         "  end",
+//!         "end",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in Elixir
+//!
+//! Elixir strings are UTF-8 and support interpolation with `#{}`, so any
+//! literal `#{` sequences are escaped.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: elixir::Tokens = quote!("hello \n #{world} # ÿ");
+//! assert_eq!("\"hello \\n \\#{world} # ÿ\"", toks.to_string()?);
+//!
+//! let name = "name";
+//! let toks: elixir::Tokens = quote!($[str](hello $name));
+//! assert_eq!("\"hello #{name}\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate as genco;
+use crate::fmt;
+use crate::lang::import_order::{self, ImportOrder, ImportSet};
+use crate::quote_in;
+use crate::tokens::ItemStr;
+use core::fmt::Write as _;
+
+/// Tokens container specialization for Elixir.
+pub type Tokens = crate::Tokens<Elixir>;
+
+impl crate::lang::LangSupportsEval for Elixir {}
+
+impl_lang! {
+    /// Language specialization for Elixir.
+    pub Elixir {
+        type Config = Config;
+        type Format = Format;
+        type Item = Any;
+
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn line_comment(out: &mut Tokens, line: &str) {
+            out.append(ItemStr::Static("#"));
+
+            if !line.is_empty() {
+                out.space();
+                out.append(line);
+            }
+        }
+
+        fn ternary(out: &mut Tokens, cond: Tokens, then: Tokens, els: Tokens) {
+            use crate as genco;
+            use crate::quote_in;
+
+            quote_in!(*out => if $cond, do: $then, else: $els);
+        }
+
+        fn default_indentation() -> fmt::Indentation {
+            fmt::Indentation::Space(2)
+        }
+
+        fn concat_operator() -> &'static str {
+            "<>"
+        }
+
+        fn start_string_eval(
+            out: &mut fmt::Formatter<'_>,
+            _config: &Self::Config,
+            _format: &Self::Format,
+        ) -> fmt::Result {
+            out.write_str("#{")?;
+            Ok(())
+        }
+
+        fn end_string_eval(
+            out: &mut fmt::Formatter<'_>,
+            _config: &Self::Config,
+            _format: &Self::Format,
+        ) -> fmt::Result {
+            out.write_char('}')?;
+            Ok(())
+        }
+
+        fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            // From: https://hexdocs.pm/elixir/syntax-reference.html#strings
+            let mut it = input.chars().peekable();
+
+            while let Some(c) = it.next() {
+                match c {
+                    '\n' => out.write_str("\\n")?,
+                    '\r' => out.write_str("\\r")?,
+                    '\t' => out.write_str("\\t")?,
+                    '\\' => out.write_str("\\\\")?,
+                    '"' => out.write_str("\\\"")?,
+                    // Note: escaped to avoid being treated as interpolation.
+                    '#' if it.peek() == Some(&'{') => out.write_str("\\#")?,
+                    c if (c as u32) < 0x20 || c == '\x7f' => {
+                        write!(out, "\\x{:02X}", c as u32)?;
+                    }
+                    c if !c.is_ascii() && out.ascii_only() => {
+                        write!(out, "\\u{{{:X}}}", c as u32)?;
+                    }
+                    c => out.write_char(c)?,
+                }
+            }
+
+            Ok(())
+        }
+
+        fn write_char_literal(out: &mut fmt::Formatter<'_>, c: char) -> fmt::Result {
+            // From: https://hexdocs.pm/elixir/syntax-reference.html#integers-in-other-bases-and-unicode-code-points
+            out.write_char('?')?;
+
+            match c {
+                ' ' => out.write_str("\\s")?,
+                c => Self::write_quoted(out, c.encode_utf8(&mut [0; 4]))?,
+            }
+
+            Ok(())
+        }
+
+        fn format_file(
+            tokens: &Tokens,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut directives = Tokens::new();
+            Self::directives(&mut directives, tokens, config);
+            let format = Format::default();
+            directives.format(out, config, &format)?;
+            tokens.format(out, config, &format)?;
+            Ok(())
+        }
+    }
+
+    Alias {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+            out.write_str(self.name())?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+            out.write_str(&self.module)?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl_serde_any!(Alias, Import);
+
+/// Formatting state for Elixir.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for Elixir.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// The order in which directives are rendered.
+    import_order: ImportOrder,
+}
+
+impl Config {
+    /// Set the order in which directives are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self { import_order }
+    }
+}
+
+static SEP: &str = ".";
+
+/// An aliased Elixir module, like `alias MyApp.User`.
+///
+/// Created through the [alias()] function.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Alias {
+    /// The fully qualified module being aliased.
+    module: ItemStr,
+    /// Custom name of the alias.
+    alias: Option<ItemStr>,
+}
+
+impl Alias {
+    /// Alias the module with a custom name, like `alias MyApp.User, as: U`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let toks: elixir::Tokens = quote! {
+    ///     $(elixir::alias("MyApp.User").with_alias("U"))
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "alias MyApp.User, as: U",
+    ///         "",
+    ///         "U",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_alias<A>(self, alias: A) -> Self
+    where
+        A: Into<ItemStr>,
+    {
+        Self {
+            alias: Some(alias.into()),
+            ..self
+        }
+    }
+
+    /// The name the module is referenced through.
+    fn name(&self) -> &str {
+        match &self.alias {
+            Some(alias) => alias,
+            None => self.module.rsplit(SEP).next().unwrap_or(&self.module),
+        }
+    }
+}
+
+/// An imported Elixir module, like `import Enum`.
+///
+/// Created through the [import()] function.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Import {
+    /// The module being imported.
+    module: ItemStr,
+}
+
+impl Elixir {
+    fn directives(out: &mut Tokens, tokens: &Tokens, config: &Config) {
+        let mut imports = ImportSet::new(config.import_order);
+        let mut aliases = ImportSet::new(config.import_order);
+
+        for item in import_order::walk_imports(tokens) {
+            match item {
+                Any::Alias(alias) => {
+                    aliases.insert((&alias.module, alias.alias.as_ref()));
+                }
+                Any::Import(import) => {
+                    imports.insert(&import.module);
+                }
+            }
+        }
+
+        if imports.is_empty() && aliases.is_empty() {
+            return;
+        }
+
+        for module in imports {
+            out.push();
+            quote_in!(*out => import $module);
+        }
+
+        for (module, alias) in aliases {
+            out.push();
+            quote_in!(*out => alias $module$(if let Some(alias) = alias => , as: $alias));
+        }
+
+        out.line();
+    }
+}
+
+/// Alias the given module, like `alias MyApp.User`.
+///
+/// The module is referenced through the last component of its name, like
+/// `User`. Aliases are collated and deduplicated at the top of the file.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: elixir::Tokens = quote! {
+///     $(elixir::alias("MyApp.User"))
+///     $(elixir::alias("MyApp.User"))
+///     $(elixir::alias("Ecto.Changeset"))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "alias Ecto.Changeset",
+///         "alias MyApp.User",
+///         "",
+///         "User",
+///         "User",
+///         "Changeset",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn alias<M>(module: M) -> Alias
+where
+    M: Into<ItemStr>,
+{
+    Alias {
+        module: module.into(),
+        alias: None,
+    }
+}
+
+/// Import the given module, like `import Enum`.
+///
+/// The module is referenced through its full name. Use [register()] to only
+/// add the import without referencing the module.
+///
+/// [register()]: crate::tokens::register()
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: elixir::Tokens = quote! {
+///     $(register(elixir::import("Enum")))
+///     $(register(elixir::alias("MyApp.User")))
+///
+///     def names(users) do
+///         map(users, fn user -> user.name end)
+///     end
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "import Enum",
+///         "alias MyApp.User",
+///         "",
+///         "def names(users) do",
+///         "  map(users, fn user -> user.name end)",
+///         "end",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn import<M>(module: M) -> Import
+where
+    M: Into<ItemStr>,
+{
+    Import {
+        module: module.into(),
+    }
+}
@@ -0,0 +1,379 @@
+//! Specialization for GDScript code generation, the scripting language of the
+//! Godot engine.
+//!
+//! Since `class_name` and `extends` have to come before anything else in a
+//! script, they are specified through [Config] and rendered above any
+//! preloaded scripts.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//! use genco::fmt;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let bullet = gdscript::preload("Bullet", "res://bullet.gd");
+//!
+//! let toks: gdscript::Tokens = quote! {
+//!     func shoot():
+//!         var b = $bullet.new()
+//!         add_child(b)
+//! };
+//!
+//! let mut w = fmt::VecWriter::new();
+//! let fmt = fmt::Config::from_lang::<GDScript>();
+//! let config = gdscript::Config::default()
+//!     .with_class_name("Player")
+//!     .with_extends("CharacterBody2D");
+//! toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+//!
+//! assert_eq!(
+//!     vec![
+//!         "class_name Player",
+//!         "extends CharacterBody2D",
+//!         "",
+//!         "const Bullet = preload(\"res://bullet.gd\")",
+//!         "",
+//!         "func shoot():",
+//!         "\tvar b = Bullet.new()",
+//!         "\tadd_child(b)",
+//!     ],
+//!     w.into_vec()
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in GDScript
+//!
+//! Strings are double-quoted, and non-ASCII characters are only escaped if
+//! ASCII-only output is requested.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: gdscript::Tokens = quote!("hello \n \"world\" ÿ");
+//! assert_eq!("\"hello \\n \\\"world\\\" ÿ\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate as genco;
+use crate::fmt;
+use crate::lang::import_order::{self, ImportMap, ImportOrder};
+use crate::quote_in;
+use crate::tokens::{quoted, ItemStr};
+use core::fmt::Write as _;
+
+/// Tokens container specialization for GDScript.
+pub type Tokens = crate::Tokens<GDScript>;
+
+impl_lang! {
+    /// Language specialization for GDScript.
+    pub GDScript {
+        type Config = Config;
+        type Format = Format;
+        type Item = Import;
+
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn line_comment(out: &mut Tokens, line: &str) {
+            out.append(ItemStr::Static("#"));
+
+            if !line.is_empty() {
+                out.space();
+                out.append(line);
+            }
+        }
+
+        fn ternary(out: &mut Tokens, cond: Tokens, then: Tokens, els: Tokens) {
+            quote_in!(*out => $then if $cond else $els);
+        }
+
+        fn default_indentation() -> fmt::Indentation {
+            fmt::Indentation::Tab
+        }
+
+        fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            // From: https://docs.godotengine.org/en/stable/tutorials/scripting/gdscript/gdscript_basics.html#literals
+            for c in input.chars() {
+                match c {
+                    '\n' => out.write_str("\\n")?,
+                    '\r' => out.write_str("\\r")?,
+                    '\t' => out.write_str("\\t")?,
+                    '\\' => out.write_str("\\\\")?,
+                    '"' => out.write_str("\\\"")?,
+                    c if c.is_control() => {
+                        write!(out, "\\u{:04x}", c as u32)?;
+                    }
+                    c if !c.is_ascii() && out.ascii_only() => {
+                        if (c as u32) < 0x10000 {
+                            write!(out, "\\u{:04x}", c as u32)?;
+                        } else {
+                            write!(out, "\\U{:06x}", c as u32)?;
+                        }
+                    }
+                    c => out.write_char(c)?,
+                }
+            }
+
+            Ok(())
+        }
+
+        fn format_file(
+            tokens: &Tokens,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut header = Tokens::new();
+
+            if let Some(class_name) = &config.class_name {
+                header.push();
+                quote_in!(header => class_name $class_name);
+            }
+
+            if let Some(extends) = &config.extends {
+                header.push();
+                quote_in!(header => extends $extends);
+            }
+
+            if !header.is_empty() {
+                header.line();
+            }
+
+            Self::imports(&mut header, tokens, config);
+            let format = Format::default();
+            header.format(out, config, &format)?;
+            tokens.format(out, config, &format)?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+            out.write_str(&self.name)
+        }
+    }
+}
+
+/// Formatting state for GDScript.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for GDScript.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// The global class name of the script.
+    class_name: Option<ItemStr>,
+    /// The class or script being extended.
+    extends: Option<ItemStr>,
+    /// The order in which imports are rendered.
+    import_order: ImportOrder,
+}
+
+impl Config {
+    /// Set the global class name of the script, rendered as
+    /// `class_name <name>` at the top of the file.
+    pub fn with_class_name<N>(self, class_name: N) -> Self
+    where
+        N: Into<ItemStr>,
+    {
+        Self {
+            class_name: Some(class_name.into()),
+            ..self
+        }
+    }
+
+    /// Set the class or script being extended, rendered as `extends <base>`
+    /// at the top of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let toks: gdscript::Tokens = quote! {
+    ///     func _ready():
+    ///         pass
+    /// };
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// let fmt = fmt::Config::from_lang::<GDScript>();
+    /// let config = gdscript::Config::default().with_extends("Node");
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "extends Node",
+    ///         "",
+    ///         "func _ready():",
+    ///         "\tpass",
+    ///     ],
+    ///     w.into_vec()
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_extends<B>(self, extends: B) -> Self
+    where
+        B: Into<ItemStr>,
+    {
+        Self {
+            extends: Some(extends.into()),
+            ..self
+        }
+    }
+
+    /// Set the order in which imports are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self {
+            import_order,
+            ..self
+        }
+    }
+}
+
+/// How a script or resource is loaded.
+#[derive(Debug, Clone, Copy, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Kind {
+    /// Loaded when the script is parsed, through `const <name> = preload(..)`.
+    Preload,
+    /// Loaded at runtime, through `var <name> = load(..)`.
+    Load,
+}
+
+/// A script or resource bound to a name at the top of the file.
+///
+/// Created through the [preload()] and [load()] functions.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Import {
+    /// How the resource is loaded.
+    kind: Kind,
+    /// The name the resource is bound to.
+    name: ItemStr,
+    /// The path of the resource, like `res://player.gd`.
+    path: ItemStr,
+}
+
+impl GDScript {
+    fn imports(out: &mut Tokens, tokens: &Tokens, config: &Config) {
+        let mut bindings = ImportMap::new(config.import_order);
+
+        for import in import_order::walk_imports(tokens) {
+            bindings.get_or_insert_with(&import.name, || import);
+        }
+
+        if bindings.is_empty() {
+            return;
+        }
+
+        for (name, import) in bindings {
+            out.push();
+
+            match import.kind {
+                Kind::Preload => {
+                    quote_in!(*out => const $name = preload($(quoted(&import.path))));
+                }
+                Kind::Load => {
+                    quote_in!(*out => var $name = load($(quoted(&import.path))));
+                }
+            }
+        }
+
+        out.line();
+    }
+}
+
+/// Bind a script or resource to a constant which is loaded when the script is
+/// parsed, like `const Player = preload("res://player.gd")`.
+///
+/// Bindings are deduplicated by name, so only the first binding of a given
+/// name is rendered. Binding the same name to different paths is a conflict
+/// which is not detected, and the path of the first binding is used.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let player = gdscript::preload("Player", "res://player.gd");
+/// let icon = gdscript::preload("Icon", "res://icon.svg");
+///
+/// let toks: gdscript::Tokens = quote! {
+///     var p = $(&player).new()
+///     var q = $player.new()
+///     $(gdscript::preload("Player", "res://other.gd"))
+///     $icon
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "const Icon = preload(\"res://icon.svg\")",
+///         "const Player = preload(\"res://player.gd\")",
+///         "",
+///         "var p = Player.new()",
+///         "var q = Player.new()",
+///         "Player",
+///         "Icon",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn preload<N, P>(name: N, path: P) -> Import
+where
+    N: Into<ItemStr>,
+    P: Into<ItemStr>,
+{
+    Import {
+        kind: Kind::Preload,
+        name: name.into(),
+        path: path.into(),
+    }
+}
+
+/// Bind a script or resource to a variable which is loaded at runtime, like
+/// `var Level = load("res://level.tscn")`.
+///
+/// Bindings are deduplicated by name in the same way as [preload()], including
+/// how conflicting paths are handled.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let level = gdscript::load("Level", "res://level.tscn");
+///
+/// let toks: gdscript::Tokens = quote! {
+///     func start():
+///         get_tree().root.add_child($level.instantiate())
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "var Level = load(\"res://level.tscn\")",
+///         "",
+///         "func start():",
+///         "\tget_tree().root.add_child(Level.instantiate())",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn load<N, P>(name: N, path: P) -> Import
+where
+    N: Into<ItemStr>,
+    P: Into<ItemStr>,
+{
+    Import {
+        kind: Kind::Load,
+        name: name.into(),
+        path: path.into(),
+    }
+}
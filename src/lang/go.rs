@@ -44,10 +44,12 @@
 
 use crate as genco;
 use crate::fmt;
+use crate::lang::import_order::{self, ImportComments, ImportMap, ImportOrder};
 use crate::quote_in;
 use crate::tokens::{quoted, ItemStr};
-use std::collections::BTreeSet;
-use std::fmt::Write as _;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
 
 const MODULE_SEP: &str = "/";
 const SEP: &str = ".";
@@ -62,11 +64,40 @@ impl_lang! {
         type Format = Format;
         type Item = Import;
 
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn default_indentation() -> fmt::Indentation {
+            // NB: gofmt indents with tabs.
+            fmt::Indentation::Tab
+        }
+
+        fn ternary(out: &mut Tokens, cond: Tokens, then: Tokens, els: Tokens) {
+            // NB: Go has no conditional expression, so an immediately invoked
+            // function literal is used instead. Its result type is `any`
+            // since the type of the branches isn't known.
+            quote_in!(*out => func() any { if $cond { return $then }; return $els }());
+        }
+
         fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
             // From: https://golang.org/src/strconv/quote.go
             super::c_family_write_quoted(out, input)
         }
 
+        fn write_char_literal(out: &mut fmt::Formatter<'_>, c: char) -> fmt::Result {
+            // Note: double quotes may not be escaped in rune literals.
+            if c == '"' {
+                out.write_str("'\"'")?;
+                return Ok(());
+            }
+
+            out.write_char('\'')?;
+            super::c_family_write_quoted(out, c.encode_utf8(&mut [0; 4]))?;
+            out.write_char('\'')?;
+            Ok(())
+        }
+
         fn format_file(
             tokens: &Tokens,
             out: &mut fmt::Formatter<'_>,
@@ -79,10 +110,24 @@ impl_lang! {
                 header.line();
             }
 
-            Self::imports(&mut header, tokens);
+            Self::imports(&mut header, tokens, config);
             let format = Format::default();
-            header.format(out, config, &format)?;
-            tokens.format(out, config, &format)?;
+
+            if !config.align_struct_fields {
+                header.format(out, config, &format)?;
+                tokens.format(out, config, &format)?;
+                return Ok(());
+            }
+
+            let mut w = fmt::FmtWriter::new(String::new());
+
+            {
+                let mut buf = w.as_formatter(out.config());
+                header.format(&mut buf, config, &format)?;
+                tokens.format(&mut buf, config, &format)?;
+            }
+
+            out.write_raw(&align_struct_fields(&w.into_inner()))?;
             Ok(())
         }
     }
@@ -104,11 +149,57 @@ impl_lang! {
 ///
 /// Created using the [import()] function.
 #[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Import {
     /// Module of the imported name.
     module: ItemStr,
     /// Name imported.
     name: ItemStr,
+    /// Comment to render above the import statement.
+    comment: Option<ItemStr>,
+}
+
+impl Import {
+    /// Attach a comment which is rendered above the import statement of the
+    /// module.
+    ///
+    /// Comments stay with their import when imports are sorted. If several
+    /// imports of the same module have comments, each distinct comment is
+    /// rendered once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let uuid = go::import("github.com/google/uuid", "New").with_comment("external deps");
+    /// let println = go::import("fmt", "Println");
+    ///
+    /// let toks = quote! {
+    ///     $println($uuid())
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import \"fmt\"",
+    ///         "// external deps",
+    ///         "import \"github.com/google/uuid\"",
+    ///         "",
+    ///         "fmt.Println(uuid.New())",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_comment<C>(self, comment: C) -> Self
+    where
+        C: Into<ItemStr>,
+    {
+        Self {
+            comment: Some(comment.into()),
+            ..self
+        }
+    }
 }
 
 /// Format for Go.
@@ -119,6 +210,10 @@ pub struct Format {}
 #[derive(Debug, Default)]
 pub struct Config {
     package: Option<ItemStr>,
+    /// The order in which imports are rendered.
+    import_order: ImportOrder,
+    /// Align the types of struct fields to a common column.
+    align_struct_fields: bool,
 }
 
 impl Config {
@@ -126,23 +221,215 @@ impl Config {
     pub fn with_package<P: Into<ItemStr>>(self, package: P) -> Self {
         Self {
             package: Some(package.into()),
+            ..self
+        }
+    }
+
+    /// Set the order in which imports are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self {
+            import_order,
+            ..self
+        }
+    }
+
+    /// Align the types of consecutive struct fields to a common column, like
+    /// gofmt does.
+    ///
+    /// This is applied as a pass over the formatted file. A run of aligned
+    /// fields is broken up by empty lines, comments, and embedded fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let duration = go::import("time", "Duration");
+    ///
+    /// let toks: go::Tokens = quote! {
+    ///     type Config struct {
+    ///         Name string
+    ///         Timeout $duration
+    ///         ID, ParentID int
+    ///         $(go::import("sync", "Mutex"))
+    ///         Debug bool
+    ///         LogLevel int
+    ///     }
+    /// };
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    ///
+    /// let fmt = fmt::Config::from_lang::<Go>();
+    /// let config = go::Config::default().with_align_struct_fields(true);
+    ///
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import \"sync\"",
+    ///         "import \"time\"",
+    ///         "",
+    ///         "type Config struct {",
+    ///         "\tName         string",
+    ///         "\tTimeout      time.Duration",
+    ///         "\tID, ParentID int",
+    ///         "\tsync.Mutex",
+    ///         "\tDebug    bool",
+    ///         "\tLogLevel int",
+    ///         "}",
+    ///     ],
+    ///     w.into_vec(),
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_align_struct_fields(self, align_struct_fields: bool) -> Self {
+        Self {
+            align_struct_fields,
+            ..self
+        }
+    }
+}
+
+/// A single field in a struct, split into its columns.
+struct Field<'a> {
+    indent: &'a str,
+    names: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Field<'a> {
+    /// Split a line into a field, unless it's something which can't be
+    /// aligned like an embedded field or a comment.
+    fn parse(line: &'a str) -> Option<Self> {
+        let content = line.trim_start();
+        let indent = &line[..line.len() - content.len()];
+
+        if content.is_empty() || content.starts_with("//") || content.starts_with('}') {
+            return None;
         }
+
+        // Names are separated by commas, like `ID, ParentID int`.
+        let mut end = 0;
+
+        for (n, c) in content.char_indices() {
+            if c.is_whitespace() && !content[..n].ends_with(',') {
+                end = n;
+                break;
+            }
+        }
+
+        if end == 0 {
+            return None;
+        }
+
+        let rest = content[end..].trim_start();
+
+        if rest.is_empty() || rest.starts_with("//") {
+            return None;
+        }
+
+        Some(Self {
+            indent,
+            names: &content[..end],
+            rest,
+        })
     }
 }
 
+/// Align the types of consecutive fields in struct declarations.
+fn align_struct_fields(input: &str) -> String {
+    let lines = input
+        .split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .collect::<Vec<_>>();
+
+    let mut fields = Vec::with_capacity(lines.len());
+    let mut in_struct = Vec::new();
+
+    for line in &lines {
+        let content = line.trim();
+
+        if content.starts_with('}') {
+            in_struct.pop();
+        }
+
+        fields.push(match in_struct.last() {
+            Some(true) => Field::parse(line),
+            _ => None,
+        });
+
+        if content.ends_with('{') {
+            in_struct.push(content.ends_with("struct {"));
+        }
+    }
+
+    let mut out = String::with_capacity(input.len());
+    let mut n = 0;
+
+    while n < lines.len() {
+        if n > 0 {
+            out.push('\n');
+        }
+
+        let indent = match &fields[n] {
+            Some(field) => field.indent,
+            None => {
+                out.push_str(lines[n]);
+                n += 1;
+                continue;
+            }
+        };
+
+        let run = fields[n..]
+            .iter()
+            .take_while(|field| matches!(field, Some(field) if field.indent == indent))
+            .flatten()
+            .collect::<Vec<_>>();
+
+        let width = run
+            .iter()
+            .map(|field| field.names.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        for (i, field) in run.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+
+            out.push_str(field.indent);
+            out.push_str(field.names);
+
+            for _ in field.names.chars().count()..=width {
+                out.push(' ');
+            }
+
+            out.push_str(field.rest);
+        }
+
+        n += run.len();
+    }
+
+    out
+}
+
 impl Go {
-    fn imports(out: &mut Tokens, tokens: &Tokens) {
-        let mut modules = BTreeSet::new();
+    fn imports(out: &mut Tokens, tokens: &Tokens, config: &Config) {
+        let mut modules = ImportMap::new(config.import_order);
 
-        for import in tokens.walk_imports() {
-            modules.insert(&import.module);
+        for import in import_order::walk_imports(tokens) {
+            modules
+                .get_or_insert_with(&import.module, ImportComments::new)
+                .insert(import.comment.as_ref());
         }
 
         if modules.is_empty() {
             return;
         }
 
-        for module in modules {
+        for (module, comments) in modules {
+            comments.write(out, "//");
             quote_in!(*out => import $(quoted(module)));
             out.push();
         }
@@ -182,5 +469,6 @@ where
     Import {
         module: module.into(),
         name: name.into(),
+        comment: None,
     }
 }
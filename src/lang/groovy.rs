@@ -0,0 +1,321 @@
+//! Specialization for Groovy code generation, like Gradle build scripts.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let list = groovy::imported("java.util", "List");
+//!
+//! let toks: groovy::Tokens = quote! {
+//!     $(groovy::task("hello", quote! {
+//!         doLast {
+//!             $list<String> names = ["world"]
+//!             println $[str](Hello $(names[0]))
+//!         }
+//!     }))
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "import java.util.List",
+//!         "",
+//!         "task hello {",
+//!         "    doLast {",
+//!         "        List<String> names = [\"world\"]",
+//!         "        println \"Hello ${names[0]}\"",
+//!         "    }",
+//!         "}",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in Groovy
+//!
+//! Strings are double-quoted GStrings, so `$` is escaped to prevent it from
+//! being interpolated.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: groovy::Tokens = quote!("costs $5 \"each\"\n");
+//! assert_eq!("\"costs \\$5 \\\"each\\\"\\n\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Interpolation in Groovy
+//!
+//! Strings can be interpolated through GStrings, by using the special
+//! `$[str](<string>)` escape sequence.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: groovy::Tokens = quote!($[str](Hello $name));
+//! assert_eq!("\"Hello $name\"", toks.to_string()?);
+//!
+//! let toks: groovy::Tokens = quote!($[str](Version $(project.version)));
+//! assert_eq!("\"Version ${project.version}\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate as genco;
+use crate::fmt;
+use crate::lang::import_order::{self, ImportOrder, ImportSet};
+use crate::quote_in;
+use crate::tokens::{FormatInto, ItemStr};
+use core::fmt::Write as _;
+
+/// Tokens container specialization for Groovy.
+pub type Tokens = crate::Tokens<Groovy>;
+
+impl crate::lang::LangSupportsEval for Groovy {}
+
+impl_lang! {
+    /// Language specialization for Groovy.
+    pub Groovy {
+        type Config = Config;
+        type Format = Format;
+        type Item = Import;
+
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn string_eval_literal(
+            out: &mut fmt::Formatter<'_>,
+            _config: &Self::Config,
+            _format: &Self::Format,
+            literal: &str,
+        ) -> fmt::Result {
+            // NB: only plain identifiers can be interpolated without braces,
+            // since something like `$names[0]` would only interpolate `names`.
+            if literal.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                write!(out, "${}", literal)?;
+            } else {
+                write!(out, "${{{}}}", literal)?;
+            }
+
+            Ok(())
+        }
+
+        fn start_string_eval(
+            out: &mut fmt::Formatter<'_>,
+            _config: &Self::Config,
+            _format: &Self::Format,
+        ) -> fmt::Result {
+            out.write_str("${")?;
+            Ok(())
+        }
+
+        fn end_string_eval(
+            out: &mut fmt::Formatter<'_>,
+            _config: &Self::Config,
+            _format: &Self::Format,
+        ) -> fmt::Result {
+            out.write_char('}')?;
+            Ok(())
+        }
+
+        fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            // From: https://groovy-lang.org/syntax.html#_escaping_special_characters
+            for c in input.chars() {
+                match c {
+                    '\u{0008}' => out.write_str("\\b")?,
+                    '\u{000c}' => out.write_str("\\f")?,
+                    '\n' => out.write_str("\\n")?,
+                    '\r' => out.write_str("\\r")?,
+                    '\t' => out.write_str("\\t")?,
+                    '"' => out.write_str("\\\"")?,
+                    '\\' => out.write_str("\\\\")?,
+                    '$' => out.write_str("\\$")?,
+                    c if c.is_control() || (!c.is_ascii() && out.ascii_only()) => {
+                        for c in c.encode_utf16(&mut [0u16; 2]) {
+                            write!(out, "\\u{:04x}", c)?;
+                        }
+                    }
+                    c => out.write_char(c)?,
+                }
+            }
+
+            Ok(())
+        }
+
+        fn format_file(
+            tokens: &Tokens,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut imports = Tokens::new();
+            Self::imports(&mut imports, tokens, config);
+            let format = Format::default();
+            imports.format(out, config, &format)?;
+            tokens.format(out, config, &format)?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+            out.write_str(&self.name)?;
+            Ok(())
+        }
+    }
+}
+
+const SEP: &str = ".";
+
+/// Formatting state for Groovy.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for Groovy.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// The order in which imports are rendered.
+    import_order: ImportOrder,
+}
+
+impl Config {
+    /// Set the order in which imports are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self { import_order }
+    }
+}
+
+/// The import of a Groovy or Java type, like `import java.util.List`.
+///
+/// Created through the [imported()] function.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Import {
+    /// Package of the type.
+    package: ItemStr,
+    /// Name of the type.
+    name: ItemStr,
+}
+
+impl Groovy {
+    fn imports(out: &mut Tokens, tokens: &Tokens, config: &Config) {
+        let mut modules = ImportSet::new(config.import_order);
+
+        for import in import_order::walk_imports(tokens) {
+            modules.insert((&import.package, &import.name));
+        }
+
+        if modules.is_empty() {
+            return;
+        }
+
+        for (package, name) in modules {
+            out.push();
+            quote_in!(*out => import $package$(SEP)$name);
+        }
+
+        out.line();
+    }
+}
+
+/// A `task` block in a Gradle build script, like `task name { ... }`.
+///
+/// Created through the [task()] function.
+#[derive(Debug, Clone)]
+pub struct Task {
+    /// Name of the task.
+    name: ItemStr,
+    /// The body of the task.
+    body: Tokens,
+}
+
+impl FormatInto<Groovy> for Task {
+    fn format_into(self, tokens: &mut Tokens) {
+        quote_in! { *tokens =>
+            task $(self.name) {
+                $(self.body)
+            }
+        }
+    }
+}
+
+/// Import a Groovy or Java type, like `import java.util.List`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: groovy::Tokens = quote! {
+///     $(groovy::imported("java.util", "List"))
+///     $(groovy::imported("java.io", "File"))
+///     $(groovy::imported("java.util", "List"))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "import java.io.File",
+///         "import java.util.List",
+///         "",
+///         "List",
+///         "File",
+///         "List",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn imported<P, N>(package: P, name: N) -> Import
+where
+    P: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    Import {
+        package: package.into(),
+        name: name.into(),
+    }
+}
+
+/// Construct a Gradle `task` block with the given name, like
+/// `task name { ... }`.
+///
+/// The body is indented inside of the block.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: groovy::Tokens = quote! {
+///     $(groovy::task("clean", quote!(delete rootProject.buildDir)))
+///
+///     $(groovy::task("empty", groovy::Tokens::new()))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "task clean {",
+///         "    delete rootProject.buildDir",
+///         "}",
+///         "",
+///         "task empty {}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn task<N>(name: N, body: Tokens) -> Task
+where
+    N: Into<ItemStr>,
+{
+    Task {
+        name: name.into(),
+        body,
+    }
+}
@@ -0,0 +1,268 @@
+//! Specialization for HCL code generation, as used by Terraform.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: hcl::Tokens = quote! {
+//!     $(hcl::resource("aws_instance", "web", quote! {
+//!         ami = "ami-a1b2c3d4"
+//!         instance_type = "t2.micro"
+//!     }))
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "resource \"aws_instance\" \"web\" {",
+//!         "  ami = \"ami-a1b2c3d4\"",
+//!         "  instance_type = \"t2.micro\"",
+//!         "}",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in HCL
+//!
+//! Since `${` and `%{` start template sequences in HCL strings, they are
+//! escaped as `$${` and `%%{` so that quoted strings are always literal.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: hcl::Tokens = quote!("echo ${HOME} \"%{x}\" \n");
+//! assert_eq!("\"echo $${HOME} \\\"%%{x}\\\" \\n\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Interpolation is supported through string evaluation:
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: hcl::Tokens = quote!(name = $[str](web-$(var.env)));
+//! assert_eq!("name = \"web-${var.env}\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate as genco;
+use crate::fmt;
+use crate::lang::Lang;
+use crate::quote_in;
+use crate::tokens::{quoted, FormatInto, ItemStr};
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+/// Tokens container specialization for HCL.
+pub type Tokens = crate::Tokens<Hcl>;
+
+/// Language specialization for HCL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hcl(());
+
+impl Lang for Hcl {
+    type Config = Config;
+    type Format = Format;
+    type Item = ();
+
+    fn default_config() -> Self::Config {
+        Config::default()
+    }
+
+    fn line_comment(out: &mut Tokens, line: &str) {
+        out.append(ItemStr::Static("#"));
+
+        if !line.is_empty() {
+            out.space();
+            out.append(line);
+        }
+    }
+
+    fn default_indentation() -> fmt::Indentation {
+        fmt::Indentation::Space(2)
+    }
+
+    fn concat(out: &mut Tokens, parts: Vec<Tokens>) {
+        // NB: HCL has no operator for concatenating strings, so each part is
+        // interpolated into a template instead.
+        out.append(ItemStr::Static("\""));
+
+        for part in parts {
+            out.append(ItemStr::Static("${"));
+            out.append(part);
+            out.append(ItemStr::Static("}"));
+        }
+
+        out.append(ItemStr::Static("\""));
+    }
+
+    fn start_string_eval(
+        out: &mut fmt::Formatter<'_>,
+        _config: &Self::Config,
+        _format: &Self::Format,
+    ) -> fmt::Result {
+        out.write_str("${")?;
+        Ok(())
+    }
+
+    fn end_string_eval(
+        out: &mut fmt::Formatter<'_>,
+        _config: &Self::Config,
+        _format: &Self::Format,
+    ) -> fmt::Result {
+        out.write_char('}')?;
+        Ok(())
+    }
+
+    fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+        // From: https://github.com/hashicorp/hcl/blob/main/hclsyntax/spec.md#template-literals
+        let mut it = input.chars().peekable();
+
+        while let Some(c) = it.next() {
+            match c {
+                '\n' => out.write_str("\\n")?,
+                '\r' => out.write_str("\\r")?,
+                '\t' => out.write_str("\\t")?,
+                '\\' => out.write_str("\\\\")?,
+                '"' => out.write_str("\\\"")?,
+                // Note: escaped to avoid being treated as a template sequence.
+                '$' if it.peek() == Some(&'{') => out.write_str("$$")?,
+                '%' if it.peek() == Some(&'{') => out.write_str("%%")?,
+                c if c.is_control() || (!c.is_ascii() && out.ascii_only()) => {
+                    if (c as u32) < 0x10000 {
+                        write!(out, "\\u{:04x}", c as u32)?;
+                    } else {
+                        write!(out, "\\U{:08x}", c as u32)?;
+                    }
+                }
+                c => out.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl crate::lang::LangSupportsEval for Hcl {}
+
+/// Formatting state for HCL.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for HCL.
+#[derive(Debug, Default)]
+pub struct Config {}
+
+/// A block, like `resource "aws_instance" "web" { ... }`.
+///
+/// Created through the [block()] and [resource()] functions.
+#[derive(Debug, Clone)]
+pub struct Block {
+    kind: ItemStr,
+    labels: Vec<ItemStr>,
+    body: Tokens,
+}
+
+impl FormatInto<Hcl> for Block {
+    fn format_into(self, tokens: &mut Tokens) {
+        let labels = self.labels.into_iter().map(quoted);
+
+        if self.body.is_empty() {
+            quote_in!(*tokens => $(self.kind) $(for l in labels => $l$[' '])$[' ']{});
+            return;
+        }
+
+        quote_in! { *tokens =>
+            $(self.kind) $(for l in labels => $l$[' ']){
+                $(self.body)
+            }
+        }
+    }
+}
+
+/// Construct a block of the given kind with the given labels, which are
+/// quoted.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: hcl::Tokens = quote! {
+///     $(hcl::block("terraform", [] as [&str; 0], quote! {
+///         $(hcl::block("backend", ["s3"], quote!(bucket = "state")))
+///     }))
+///
+///     $(hcl::block("locals", [] as [&str; 0], Tokens::new()))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "terraform {",
+///         "  backend \"s3\" {",
+///         "    bucket = \"state\"",
+///         "  }",
+///         "}",
+///         "",
+///         "locals {}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn block<K, I, B>(kind: K, labels: I, body: B) -> Block
+where
+    K: Into<ItemStr>,
+    I: IntoIterator,
+    I::Item: Into<ItemStr>,
+    B: FormatInto<Hcl>,
+{
+    let mut t = Tokens::new();
+    t.append(body);
+
+    Block {
+        kind: kind.into(),
+        labels: labels.into_iter().map(Into::into).collect(),
+        body: t,
+    }
+}
+
+/// Construct a `resource` block with the given type and name.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: hcl::Tokens = quote! {
+///     $(hcl::resource("null_resource", "echo", quote! {
+///         command = "echo ${self.id}"
+///     }))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "resource \"null_resource\" \"echo\" {",
+///         "  command = \"echo $${self.id}\"",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn resource<T, N, B>(ty: T, name: N, body: B) -> Block
+where
+    T: Into<ItemStr>,
+    N: Into<ItemStr>,
+    B: FormatInto<Hcl>,
+{
+    block("resource", [ty.into(), name.into()], body)
+}
@@ -0,0 +1,335 @@
+//! Specialization for HTML code generation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: html::Tokens = quote! {
+//!     <p $(html::attr("title", "Tom & Jerry"))>
+//!         $(html::text("1 < 2"))
+//!     </p>
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "<p title=\"Tom &amp; Jerry\">",
+//!         "    1 &lt; 2",
+//!         "</p>",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in HTML
+//!
+//! Quoted strings are treated as attribute values, where `&`, `<`, `>`, and `"`
+//! are escaped as character references.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: html::Tokens = quote!(<a href="?a=1&b=2">);
+//! assert_eq!("<a href=\"?a=1&amp;b=2\">", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::fmt;
+use crate::lang::{Lang, LangItem};
+use crate::tokens::{quoted, FormatInto, Item, ItemStr};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// Tokens container specialization for HTML.
+pub type Tokens = crate::Tokens<Html>;
+
+/// Elements which are not permitted to have any content, and therefore must
+/// not have a closing tag.
+///
+/// See <https://html.spec.whatwg.org/multipage/syntax.html#void-elements>.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Language specialization for HTML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Html(());
+
+impl Lang for Html {
+    type Config = Config;
+    type Format = Format;
+    type Item = Text;
+
+    fn default_config() -> Self::Config {
+        Config::default()
+    }
+
+    fn line_comment(out: &mut Tokens, line: &str) {
+        out.append(ItemStr::Static("<!--"));
+
+        if !line.is_empty() {
+            out.space();
+            out.append(line);
+        }
+
+        out.space();
+        out.append(ItemStr::Static("-->"));
+    }
+
+    fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+        escape(out, input)
+    }
+}
+
+/// Format state for HTML.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for HTML.
+#[derive(Debug, Default)]
+pub struct Config {}
+
+/// Escape the given string so that it can be used as HTML text content or as
+/// a quoted attribute value.
+fn escape(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+    use core::fmt::Write as _;
+
+    for c in input.chars() {
+        match c {
+            '&' => out.write_str("&amp;")?,
+            '<' => out.write_str("&lt;")?,
+            '>' => out.write_str("&gt;")?,
+            '"' => out.write_str("&quot;")?,
+            c if !c.is_ascii() && out.ascii_only() => write!(out, "&#x{:x};", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Test if the given element name is a [void element], which never has a
+/// closing tag.
+///
+/// [void element]: https://html.spec.whatwg.org/multipage/syntax.html#void-elements
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// assert!(html::is_void_element("br"));
+/// assert!(html::is_void_element("IMG"));
+/// assert!(!html::is_void_element("div"));
+/// ```
+pub fn is_void_element(name: &str) -> bool {
+    VOID_ELEMENTS
+        .iter()
+        .any(|void| void.eq_ignore_ascii_case(name))
+}
+
+/// Escaped HTML text content.
+///
+/// Created through the [text()] function. The text is escaped when it's
+/// formatted, so that it respects the configuration of the formatter.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Text {
+    text: ItemStr,
+}
+
+impl LangItem<Html> for Text {
+    fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+        escape(out, &self.text)
+    }
+}
+
+impl FormatInto<Html> for Text {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(Item::Lang(0, Box::new(self)));
+    }
+}
+
+/// Text content where `&`, `<`, `>`, and `"` are escaped.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: html::Tokens = quote!(<b>$(html::text("a < b && c"))</b>);
+/// assert_eq!("<b>a &lt; b &amp;&amp; c</b>", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn text<T>(text: T) -> Text
+where
+    T: Into<ItemStr>,
+{
+    Text { text: text.into() }
+}
+
+/// An attribute on an HTML element.
+///
+/// Created through the [attr()] function.
+#[derive(Debug, Clone)]
+pub struct Attr {
+    name: ItemStr,
+    value: ItemStr,
+}
+
+impl FormatInto<Html> for Attr {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(self.name);
+        tokens.append(ItemStr::Static("="));
+        tokens.append(quoted(self.value));
+    }
+}
+
+/// An attribute rendered as `name="value"`, where the value is escaped.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: html::Tokens = quote!(<input $(html::attr("value", "\"quoted\" <value>"))>);
+/// assert_eq!("<input value=\"&quot;quoted&quot; &lt;value&gt;\">", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn attr<N, V>(name: N, value: V) -> Attr
+where
+    N: Into<ItemStr>,
+    V: Into<ItemStr>,
+{
+    Attr {
+        name: name.into(),
+        value: value.into(),
+    }
+}
+
+/// An HTML element with attributes and content.
+///
+/// Created through the [element()] function.
+#[derive(Debug, Clone)]
+pub struct Element {
+    name: ItemStr,
+    attrs: Vec<Attr>,
+    content: Tokens,
+}
+
+impl Element {
+    /// Add an attribute to the element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let img = html::element("img")
+    ///     .with_attr("src", "cat.png")
+    ///     .with_attr("alt", "A \"cat\"");
+    ///
+    /// let toks: html::Tokens = quote!($img);
+    /// assert_eq!("<img src=\"cat.png\" alt=\"A &quot;cat&quot;\">", toks.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_attr<N, V>(mut self, name: N, value: V) -> Self
+    where
+        N: Into<ItemStr>,
+        V: Into<ItemStr>,
+    {
+        self.attrs.push(attr(name, value));
+        self
+    }
+
+    /// Append content to the element.
+    ///
+    /// Content is ignored for [void elements][is_void_element].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let p = html::element("p")
+    ///     .with_content(html::text("Fish & Chips"))
+    ///     .with_content(html::element("br"));
+    ///
+    /// let toks: html::Tokens = quote!($p);
+    /// assert_eq!("<p>Fish &amp; Chips<br></p>", toks.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_content<T>(mut self, content: T) -> Self
+    where
+        T: FormatInto<Html>,
+    {
+        self.content.append(content);
+        self
+    }
+}
+
+impl FormatInto<Html> for Element {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(ItemStr::Static("<"));
+        tokens.append(&self.name);
+
+        for attr in self.attrs {
+            tokens.space();
+            tokens.append(attr);
+        }
+
+        tokens.append(ItemStr::Static(">"));
+
+        if is_void_element(&self.name) {
+            return;
+        }
+
+        tokens.append(self.content);
+        tokens.append(ItemStr::Static("</"));
+        tokens.append(self.name);
+        tokens.append(ItemStr::Static(">"));
+    }
+}
+
+/// Construct an HTML element.
+///
+/// [Void elements][is_void_element] like `<br>` and `<img>` are rendered
+/// without a closing tag.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: html::Tokens = quote! {
+///     $(html::element("div").with_attr("class", "a&b").with_content(html::text("<hi>")))
+///     $(html::element("br"))
+///     $(html::element("img").with_attr("src", "x.png"))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "<div class=\"a&amp;b\">&lt;hi&gt;</div>",
+///         "<br>",
+///         "<img src=\"x.png\">",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn element<N>(name: N) -> Element
+where
+    N: Into<ItemStr>,
+{
+    Element {
+        name: name.into(),
+        attrs: Vec::new(),
+        content: Tokens::new(),
+    }
+}
@@ -0,0 +1,231 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::lang::Lang;
+use crate::tokens::ItemStr;
+use crate::Tokens;
+
+/// The order in which collated imports are rendered.
+///
+/// Collation never depends on hashing, so either order produces the same
+/// output for the same token stream across runs and platforms.
+///
+/// This is configured through the `Config` of each language which collates
+/// imports, like [rust::Config::with_import_order].
+///
+/// [rust::Config::with_import_order]: crate::lang::rust::Config::with_import_order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ImportOrder {
+    /// Imports are sorted. This is the default.
+    Sorted,
+    /// Imports are rendered in the order in which they are first encountered
+    /// in the token stream.
+    AsEncountered,
+}
+
+impl Default for ImportOrder {
+    fn default() -> Self {
+        Self::Sorted
+    }
+}
+
+/// Walk the imports of a token stream in the order in which they were added.
+///
+/// Imports which are builtin according to [Lang::is_builtin_import] are
+/// skipped.
+pub(crate) fn walk_imports<L>(tokens: &Tokens<L>) -> vec::IntoIter<&L::Item>
+where
+    L: Lang,
+{
+    let mut imports = tokens
+        .walk_imports()
+        .filter(|import| !L::is_builtin_import(import))
+        .collect::<Vec<_>>();
+    imports.reverse();
+    imports.into_iter()
+}
+
+/// A map used to collate imports, which is iterated over according to an
+/// [ImportOrder].
+#[derive(Debug)]
+pub(crate) struct ImportMap<K, V> {
+    order: ImportOrder,
+    index: BTreeMap<K, usize>,
+    entries: Vec<(K, V)>,
+}
+
+impl<K, V> ImportMap<K, V>
+where
+    K: Clone + Ord,
+{
+    /// Construct a new empty map.
+    pub(crate) fn new(order: ImportOrder) -> Self {
+        Self {
+            order,
+            index: BTreeMap::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Get the value for the given key, inserting one constructed by `f` if
+    /// it's missing.
+    pub(crate) fn get_or_insert_with<F>(&mut self, key: K, f: F) -> &mut V
+    where
+        F: FnOnce() -> V,
+    {
+        let entries = &mut self.entries;
+
+        let index = *self.index.entry(key.clone()).or_insert_with(|| {
+            entries.push((key, f()));
+            entries.len() - 1
+        });
+
+        &mut self.entries[index].1
+    }
+
+    /// Test if the map contains the given key.
+    pub(crate) fn contains_key(&self, key: &K) -> bool {
+        self.index.contains_key(key)
+    }
+
+    /// Test if the map is empty.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Only retain entries matching the given predicate.
+    pub(crate) fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        let entries = core::mem::take(&mut self.entries);
+        self.index.clear();
+
+        for (k, mut v) in entries {
+            if f(&k, &mut v) {
+                self.index.insert(k.clone(), self.entries.len());
+                self.entries.push((k, v));
+            }
+        }
+    }
+
+    /// Iterate over the entries of the map in the configured order.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn iter(&self) -> vec::IntoIter<(&K, &V)> {
+        let mut entries = self.entries.iter().map(|(k, v)| (k, v)).collect::<Vec<_>>();
+
+        if let ImportOrder::Sorted = self.order {
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+        }
+
+        entries.into_iter()
+    }
+}
+
+impl<K, V> IntoIterator for ImportMap<K, V>
+where
+    K: Ord,
+{
+    type Item = (K, V);
+    type IntoIter = vec::IntoIter<(K, V)>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        if let ImportOrder::Sorted = self.order {
+            self.entries.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+
+        self.entries.into_iter()
+    }
+}
+
+/// A set used to collate imports, which is iterated over according to an
+/// [ImportOrder].
+#[derive(Debug)]
+pub(crate) struct ImportSet<T> {
+    map: ImportMap<T, ()>,
+}
+
+impl<T> ImportSet<T>
+where
+    T: Clone + Ord,
+{
+    /// Construct a new empty set.
+    pub(crate) fn new(order: ImportOrder) -> Self {
+        Self {
+            map: ImportMap::new(order),
+        }
+    }
+
+    /// Insert a value into the set, unless it's already present.
+    pub(crate) fn insert(&mut self, value: T) {
+        self.map.get_or_insert_with(value, || ());
+    }
+
+    /// Test if the set contains the given value.
+    pub(crate) fn contains(&self, value: &T) -> bool {
+        self.map.contains_key(value)
+    }
+
+    /// Test if the set is empty.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Iterate over the values of the set in the configured order.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &T> {
+        self.map.iter().map(|(value, ())| value)
+    }
+}
+
+impl<T> IntoIterator for ImportSet<T>
+where
+    T: Ord,
+{
+    type Item = T;
+    type IntoIter = core::iter::Map<vec::IntoIter<(T, ())>, fn((T, ())) -> T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_iter().map(|(value, ())| value)
+    }
+}
+
+/// Comments attached to the imports which make up a single import statement.
+///
+/// Comments are deduplicated and kept in the order in which they were first
+/// encountered, regardless of the [ImportOrder] in use.
+#[derive(Debug)]
+pub(crate) struct ImportComments<'a> {
+    comments: ImportSet<&'a ItemStr>,
+}
+
+impl<'a> ImportComments<'a> {
+    /// Construct a new empty collection of comments.
+    pub(crate) fn new() -> Self {
+        Self {
+            comments: ImportSet::new(ImportOrder::AsEncountered),
+        }
+    }
+
+    /// Add the comment of an import, if it has one.
+    pub(crate) fn insert(&mut self, comment: Option<&'a ItemStr>) {
+        if let Some(comment) = comment {
+            self.comments.insert(comment);
+        }
+    }
+
+    /// Write each comment on its own line prefixed with the given line comment
+    /// marker, like `//`.
+    pub(crate) fn write<L>(self, out: &mut Tokens<L>, marker: &'static str)
+    where
+        L: Lang,
+    {
+        for comment in self.comments {
+            out.append(ItemStr::Static(marker));
+            out.space();
+            out.append(comment.clone());
+            out.push();
+        }
+    }
+}
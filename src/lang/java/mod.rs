@@ -21,10 +21,14 @@ pub use self::block_comment::BlockComment;
 
 use crate as genco;
 use crate::fmt;
+use crate::lang::import_order::{self, ImportOrder, ImportSet};
+use crate::lang::Lang;
 use crate::tokens::ItemStr;
 use crate::{quote, quote_in};
-use std::collections::{BTreeSet, HashMap};
-use std::fmt::Write as _;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::string::ToString;
+use core::fmt::Write as _;
 
 /// Tokens container specialized for Java.
 pub type Tokens = crate::Tokens<Java>;
@@ -36,9 +40,13 @@ impl_lang! {
         type Format = Format;
         type Item = Import;
 
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
         fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
             // From: https://docs.oracle.com/javase/tutorial/java/data/characters.html
-            use std::fmt::Write as _;
+            use core::fmt::Write as _;
 
             for c in input.chars() {
                 match c {
@@ -63,6 +71,10 @@ impl_lang! {
             Ok(())
         }
 
+        fn is_builtin_import(import: &Self::Item) -> bool {
+            &*import.package == JAVA_LANG
+        }
+
         fn format_file(
             tokens: &Tokens,
             out: &mut fmt::Formatter<'_>,
@@ -89,7 +101,7 @@ impl_lang! {
             let imported = format.imported.get(self.name.as_ref()).map(String::as_str);
             let pkg = Some(self.package.as_ref());
 
-            if &*self.package != JAVA_LANG && imported != pkg && file_package != pkg {
+            if !Java::is_builtin_import(self) && imported != pkg && file_package != pkg {
                 out.write_str(self.package.as_ref())?;
                 out.write_str(SEP)?;
             }
@@ -107,7 +119,7 @@ const SEP: &str = ".";
 #[derive(Debug, Default)]
 pub struct Format {
     /// Types which has been imported into the local namespace.
-    imported: HashMap<String, String>,
+    imported: BTreeMap<String, String>,
 }
 
 /// Configuration for Java.
@@ -115,6 +127,8 @@ pub struct Format {
 pub struct Config {
     /// Package to use.
     package: Option<ItemStr>,
+    /// The order in which imports are rendered.
+    import_order: ImportOrder,
 }
 
 impl Config {
@@ -153,6 +167,15 @@ impl Config {
     {
         Self {
             package: Some(package.into()),
+            ..self
+        }
+    }
+
+    /// Set the order in which imports are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self {
+            import_order,
+            ..self
         }
     }
 }
@@ -161,6 +184,7 @@ impl Config {
 ///
 /// Created through the [import()] function.
 #[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Import {
     /// Package of the class.
     package: ItemStr,
@@ -173,13 +197,13 @@ impl Java {
         out: &mut Tokens,
         tokens: &Tokens,
         config: &Config,
-        imported: &mut HashMap<String, String>,
+        imported: &mut BTreeMap<String, String>,
     ) {
-        let mut modules = BTreeSet::new();
+        let mut modules = ImportSet::new(config.import_order);
 
         let file_package = config.package.as_ref().map(|p| p.as_ref());
 
-        for import in tokens.walk_imports() {
+        for import in import_order::walk_imports(tokens) {
             modules.insert((import.package.clone(), import.name.clone()));
         }
 
@@ -192,10 +216,6 @@ impl Java {
                 continue;
             }
 
-            if &*package == JAVA_LANG {
-                continue;
-            }
-
             if Some(&*package) == file_package {
                 continue;
             }
@@ -1,5 +1,8 @@
 //! Specialization for JavaScript code generation.
 //!
+//! This requires the `std` feature, since module paths are resolved through
+//! [relative_path].
+//!
 //! # Examples
 //!
 //! Basic example:
@@ -50,10 +53,10 @@
 //! ```
 
 use crate::fmt;
-use crate::tokens::ItemStr;
+use crate::lang::import_order::{ImportMap, ImportOrder, ImportSet};
+use crate::tokens::{Assoc, FormatInto, Item, ItemStr};
+use core::fmt::Write as _;
 use relative_path::{RelativePath, RelativePathBuf};
-use std::collections::{BTreeMap, BTreeSet};
-use std::fmt::Write as _;
 
 /// Tokens container specialization for Rust.
 pub type Tokens = crate::Tokens<JavaScript>;
@@ -65,41 +68,69 @@ impl_lang! {
     pub JavaScript {
         type Config = Config;
         type Format = Format;
-        type Item = Import;
+        type Item = Any;
+
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn operator_precedence(op: &str) -> Option<(u8, Assoc)> {
+            // Reference: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Operator_precedence
+            Some(match op {
+                "**" => (14, Assoc::Right),
+                "*" | "/" | "%" => (13, Assoc::Left),
+                "+" | "-" => (12, Assoc::Left),
+                "<<" | ">>" | ">>>" => (11, Assoc::Left),
+                "<" | "<=" | ">" | ">=" | "in" | "instanceof" => (10, Assoc::Left),
+                "==" | "!=" | "===" | "!==" => (9, Assoc::Left),
+                "&" => (8, Assoc::Left),
+                "^" => (7, Assoc::Left),
+                "|" => (6, Assoc::Left),
+                "&&" => (5, Assoc::Left),
+                "||" => (4, Assoc::Left),
+                "=" | "+=" | "-=" | "**=" | "*=" | "/=" | "%=" | "<<=" | ">>=" | ">>>=" | "&="
+                | "^=" | "|=" | "&&=" | "||=" | "??=" => (2, Assoc::Right),
+                _ => return None,
+            })
+        }
 
         /// Start a string quote.
         fn open_quote(
             out: &mut fmt::Formatter<'_>,
-            _config: &Self::Config,
+            config: &Self::Config,
             _format: &Self::Format,
             has_eval: bool,
         ) -> fmt::Result {
-            use std::fmt::Write as _;
+            use core::fmt::Write as _;
 
-            if has_eval {
-                out.write_char('`')?;
+            let quote = if has_eval {
+                '`'
             } else {
-                out.write_char('"')?;
-            }
+                config.string_quote.as_char()
+            };
 
+            out.write_char(quote)?;
+            out.set_quote(Some(quote));
             Ok(())
         }
 
         /// End a string quote.
         fn close_quote(
             out: &mut fmt::Formatter<'_>,
-            _config: &Self::Config,
+            config: &Self::Config,
             _format: &Self::Format,
             has_eval: bool,
         ) -> fmt::Result {
-            use std::fmt::Write as _;
+            use core::fmt::Write as _;
 
-            if has_eval {
-                out.write_char('`')?;
+            let quote = if has_eval {
+                '`'
             } else {
-                out.write_char('"')?;
-            }
+                config.string_quote.as_char()
+            };
 
+            out.write_char(quote)?;
+            out.set_quote(None);
             Ok(())
         }
 
@@ -124,7 +155,10 @@ impl_lang! {
         fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
             // Reference: https://mathiasbynens.be/notes/javascript-escapes
 
-            for c in input.chars() {
+            let quote = out.quote().unwrap_or('"');
+            let mut chars = input.chars().peekable();
+
+            while let Some(c) = chars.next() {
                 match c {
                     // backspace
                     '\u{0008}' => out.write_str("\\b")?,
@@ -140,10 +174,16 @@ impl_lang! {
                     '\u{0011}' => out.write_str("\\v")?,
                     // null character.
                     '\0' => out.write_str("\\0")?,
-                    // Note: only relevant if we were to use single-quoted strings.
-                    // '\'' => out.write_str("\\'")?,
-                    '"' => out.write_str("\\\"")?,
+                    '\'' if quote == '\'' => out.write_str("\\'")?,
+                    '"' if quote == '"' => out.write_str("\\\"")?,
+                    '`' if quote == '`' => out.write_str("\\`")?,
+                    '$' if quote == '`' && chars.peek() == Some(&'{') => out.write_str("\\$")?,
                     '\\' => out.write_str("\\\\")?,
+                    c if !c.is_ascii() && out.ascii_only() => {
+                        for c in c.encode_utf16(&mut [0u16; 2]) {
+                            write!(out, "\\u{:04x}", c)?;
+                        }
+                    }
                     c if !c.is_control() => out.write_char(c)?,
                     c if (c as u32) < 0x100 => {
                         write!(out, "\\x{:02x}", c as u32)?;
@@ -164,9 +204,12 @@ impl_lang! {
         ) -> fmt::Result {
             let mut imports = Tokens::new();
             Self::imports(&mut imports, tokens, config);
+            let mut exports = Tokens::new();
+            Self::exports(&mut exports, tokens, config);
             let format = Format::default();
             imports.format(out, config, &format)?;
             tokens.format(out, config, &format)?;
+            exports.format(out, config, &format)?;
             Ok(())
         }
     }
@@ -181,8 +224,24 @@ impl_lang! {
             out.write_str(name)
         }
     }
+
+    Export {
+        fn format(&self, out: &mut fmt::Formatter<'_>, config: &Config, _: &Format) -> fmt::Result {
+            if let ExportStyle::Inline = config.export_style {
+                match self.kind {
+                    ExportKind::Named => out.write_str("export ")?,
+                    ExportKind::Default => out.write_str("export default ")?,
+                }
+            }
+
+            Ok(())
+        }
+    }
 }
 
+#[cfg(feature = "serde")]
+impl_serde_any!(Import, Export);
+
 /// Format state for JavaScript.
 #[derive(Debug, Default)]
 pub struct Format {}
@@ -191,6 +250,10 @@ pub struct Format {}
 #[derive(Debug, Default)]
 pub struct Config {
     module_path: Option<RelativePathBuf>,
+    module_system: ModuleSystem,
+    import_order: ImportOrder,
+    string_quote: QuoteChar,
+    export_style: ExportStyle,
 }
 
 impl Config {
@@ -199,7 +262,11 @@ impl Config {
     /// This setting will determine what path imports are renderer relative
     /// towards. So importing a module from `"foo/bar.js"`, and setting this to
     /// `"foo/baz.js"` will cause the import to be rendered relatively as
-    /// `"../bar.js"`.
+    /// `"./bar.js"`, while importing `"bar.js"` is rendered as `"../bar.js"`.
+    ///
+    /// Relativized paths without an extension have `.js` added to them, as is
+    /// required by ECMAScript modules. Paths are used as-is if no module path
+    /// is configured.
     ///
     /// # Examples
     ///
@@ -209,11 +276,13 @@ impl Config {
     ///
     /// let foo1 = js::import(js::Module::Path("foo/bar.js".into()), "Foo1");
     /// let foo2 = js::import(js::Module::Path("foo/bar.js".into()), "Foo2");
+    /// let util = js::import(js::Module::Path("util".into()), "util");
     /// let react = js::import("react", "React").into_default();
     ///
     /// let toks: js::Tokens = quote! {
     ///     $foo1
     ///     $foo2
+    ///     $util
     ///     $react
     /// };
     ///
@@ -226,11 +295,13 @@ impl Config {
     ///
     /// assert_eq!(
     ///     vec![
-    ///         "import {Foo1, Foo2} from \"../bar.js\";",
+    ///         "import {Foo1, Foo2} from \"./bar.js\";",
+    ///         "import {util} from \"../util.js\";",
     ///         "import React from \"react\";",
     ///         "",
     ///         "Foo1",
     ///         "Foo2",
+    ///         "util",
     ///         "React"
     ///     ],
     ///     w.into_vec()
@@ -243,12 +314,215 @@ impl Config {
     {
         Self {
             module_path: Some(module_path.into()),
+            ..self
+        }
+    }
+
+    /// Configure the module system used when rendering imports.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let vec = js::import("collections", "vec");
+    /// let list = js::import("collections", "vec").with_alias("list");
+    /// let all = js::import("collections", "all").into_wildcard();
+    /// let react = js::import("react", "React").into_default();
+    ///
+    /// let toks: js::Tokens = quote! {
+    ///     $vec
+    ///     $list
+    ///     $all
+    ///     $react
+    /// };
+    ///
+    /// let fmt = fmt::Config::from_lang::<JavaScript>();
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// let config = js::Config::default().with_module_system(js::ModuleSystem::Esm);
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import * as all from \"collections\";",
+    ///         "import {vec, vec as list} from \"collections\";",
+    ///         "import React from \"react\";",
+    ///         "",
+    ///         "vec",
+    ///         "list",
+    ///         "all",
+    ///         "React",
+    ///     ],
+    ///     w.into_vec()
+    /// );
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// let config = js::Config::default().with_module_system(js::ModuleSystem::CommonJs);
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "const all = require(\"collections\");",
+    ///         "const { vec, vec: list } = require(\"collections\");",
+    ///         "const React = require(\"react\");",
+    ///         "",
+    ///         "vec",
+    ///         "list",
+    ///         "all",
+    ///         "React",
+    ///     ],
+    ///     w.into_vec()
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_module_system(self, module_system: ModuleSystem) -> Self {
+        Self {
+            module_system,
+            ..self
+        }
+    }
+
+    /// Set the order in which imports are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self {
+            import_order,
+            ..self
+        }
+    }
+
+    /// Set the quote character used for string literals.
+    ///
+    /// Strings which contain evaluation are always rendered as template
+    /// literals using backticks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let toks: js::Tokens = quote!(const greeting = $(quoted("it's \"here\"")););
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// let fmt = fmt::Config::from_lang::<JavaScript>();
+    /// let config = js::Config::default().with_string_quote(js::QuoteChar::Single);
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec!["const greeting = 'it\\'s \"here\"';"],
+    ///     w.into_vec()
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_string_quote(self, string_quote: QuoteChar) -> Self {
+        Self {
+            string_quote,
+            ..self
+        }
+    }
+
+    /// Set how declarations marked through [export()] are exported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let toks: js::Tokens = quote! {
+    ///     $(js::export("answer", quote!(const answer = 42;)))
+    /// };
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// let fmt = fmt::Config::from_lang::<JavaScript>();
+    /// let config = js::Config::default().with_export_style(js::ExportStyle::Inline);
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(vec!["export const answer = 42;"], w.into_vec());
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_export_style(self, export_style: ExportStyle) -> Self {
+        Self {
+            export_style,
+            ..self
         }
     }
 }
 
+/// How exported declarations are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExportStyle {
+    /// Exports are collected into statements at the end of the file, like
+    /// `export {a, b};` and `export default c;`.
+    ///
+    /// This is the default.
+    Trailing,
+    /// Declarations are prefixed with the `export` keyword, like `export
+    /// function a() {}` and `export default function c() {}`.
+    Inline,
+}
+
+impl Default for ExportStyle {
+    fn default() -> Self {
+        Self::Trailing
+    }
+}
+
+/// The quote character used for string literals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum QuoteChar {
+    /// Double quotes, like `"hello"`.
+    ///
+    /// This is the default.
+    Double,
+    /// Single quotes, like `'hello'`.
+    Single,
+    /// Backticks, like `` `hello` ``.
+    Backtick,
+}
+
+impl QuoteChar {
+    fn as_char(self) -> char {
+        match self {
+            Self::Double => '"',
+            Self::Single => '\'',
+            Self::Backtick => '`',
+        }
+    }
+}
+
+impl Default for QuoteChar {
+    fn default() -> Self {
+        Self::Double
+    }
+}
+
+/// The module system to use when rendering imports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ModuleSystem {
+    /// ECMAScript modules, like `import {vec} from "collections";`.
+    ///
+    /// This is the default.
+    Esm,
+    /// CommonJS modules as used by Node, like `const { vec } =
+    /// require("collections");`.
+    ///
+    /// Both default and wildcard imports bind the whole module, like `const
+    /// all = require("collections");`.
+    CommonJs,
+}
+
+impl Default for ModuleSystem {
+    fn default() -> Self {
+        Self::Esm
+    }
+}
+
 /// Internal type to determine the kind of import used.
 #[derive(Debug, Clone, Copy, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum ImportKind {
     Named,
     Default,
@@ -259,6 +533,7 @@ enum ImportKind {
 ///
 /// Created through the [import()] function.
 #[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Import {
     /// The kind of the import.
     kind: ImportKind,
@@ -381,18 +656,117 @@ impl Import {
     }
 }
 
+/// Internal type to determine the kind of export used.
+#[derive(Debug, Clone, Copy, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum ExportKind {
+    Named,
+    Default,
+}
+
+/// Marker for an exported declaration, which renders the `export` keyword
+/// when using [ExportStyle::Inline].
+///
+/// This is added to the token stream by [Exported].
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Export {
+    /// The kind of the export.
+    kind: ExportKind,
+    /// Name being exported.
+    name: ItemStr,
+}
+
+/// A declaration which is exported from the module.
+///
+/// Created through the [export()] function.
+#[derive(Debug, Clone)]
+pub struct Exported {
+    export: Export,
+    declaration: Tokens,
+}
+
+impl Exported {
+    /// Convert into the default export of the module.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let main = js::export("main", quote! {
+    ///     function main() {
+    ///     }
+    /// });
+    ///
+    /// let toks = quote!($(main.into_default()));
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "function main() {",
+    ///         "}",
+    ///         "",
+    ///         "export default main;",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn into_default(self) -> Self {
+        Self {
+            export: Export {
+                kind: ExportKind::Default,
+                ..self.export
+            },
+            ..self
+        }
+    }
+}
+
+impl FormatInto<JavaScript> for Exported {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(self.export);
+        tokens.append(self.declaration);
+    }
+}
+
 /// A module being imported.
 #[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Module {
     /// A module imported from a specific path.
     ///
     /// The path will be relativized according to the module specified in the
-    /// [Config::with_module_path].
-    Path(RelativePathBuf),
+    /// [Config::with_module_path], in which case it has `.js` added to it if it
+    /// is missing an extension. Without a module path, it's used as-is.
+    Path(#[cfg_attr(feature = "serde", serde(with = "serde_path"))] RelativePathBuf),
     /// A globally imported module.
     Global(ItemStr),
 }
 
+/// Serialize module paths as strings, since [RelativePathBuf] only supports
+/// serde through a feature of its own.
+#[cfg(feature = "serde")]
+mod serde_path {
+    use alloc::string::String;
+    use relative_path::RelativePathBuf;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S>(path: &RelativePathBuf, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(path.as_str())
+    }
+
+    pub(super) fn deserialize<'de, D>(deserializer: D) -> Result<RelativePathBuf, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(RelativePathBuf::from(String::deserialize(deserializer)?))
+    }
+}
+
 impl<'a> From<&'a str> for Module {
     fn from(value: &'a str) -> Self {
         Self::Global(value.into())
@@ -417,13 +791,20 @@ impl JavaScript {
         use crate as genco;
         use crate::prelude::*;
 
-        let mut modules = BTreeMap::<&Module, ResolvedModule<'_>>::new();
-        let mut wildcards = BTreeSet::new();
+        let order = config.import_order;
+        let mut modules = ImportMap::<&Module, ResolvedModule<'_>>::new(order);
+        let mut wildcards = ImportSet::new(order);
+
+        for item in import_order::walk_imports(tokens) {
+            let import = match item {
+                Any::Import(import) => import,
+                Any::Export(..) => continue,
+            };
 
-        for import in tokens.walk_imports() {
             match import.kind {
                 ImportKind::Named => {
-                    let module = modules.entry(&import.module).or_default();
+                    let module =
+                        modules.get_or_insert_with(&import.module, || ResolvedModule::new(order));
 
                     module.set.insert(match &import.alias {
                         None => ImportedElement::Plain(&import.name),
@@ -431,7 +812,8 @@ impl JavaScript {
                     });
                 }
                 ImportKind::Default => {
-                    let module = modules.entry(&import.module).or_default();
+                    let module =
+                        modules.get_or_insert_with(&import.module, || ResolvedModule::new(order));
                     module.default_import = Some(&import.name);
                 }
                 ImportKind::Wildcard => {
@@ -444,6 +826,41 @@ impl JavaScript {
             return;
         }
 
+        if let ModuleSystem::CommonJs = config.module_system {
+            let module_path = config.module_path.as_deref();
+
+            for (module, name) in wildcards {
+                out.push();
+                quote_in! { *out =>
+                    const $name = require($(ref t => render_from(t, module_path, module)));
+                }
+            }
+
+            for (module, resolved) in modules {
+                if let Some(default) = resolved.default_import {
+                    out.push();
+                    quote_in! { *out =>
+                        const $default = require($(ref t => render_from(t, module_path, module)));
+                    }
+                }
+
+                if resolved.set.is_empty() {
+                    continue;
+                }
+
+                out.push();
+                quote_in! { *out =>
+                    const { $(for el in resolved.set join (, ) => $(match el {
+                        ImportedElement::Plain(name) => $name,
+                        ImportedElement::Aliased(name, alias) => $name: $alias,
+                    })) } = require($(ref t => render_from(t, module_path, module)));
+                }
+            }
+
+            out.line();
+            return;
+        }
+
         for (module, name) in wildcards {
             out.push();
             quote_in! { *out =>
@@ -493,13 +910,21 @@ impl JavaScript {
 
         out.line();
 
-        #[derive(Default)]
         struct ResolvedModule<'a> {
             default_import: Option<&'a ItemStr>,
-            set: BTreeSet<ImportedElement<'a>>,
+            set: ImportSet<ImportedElement<'a>>,
         }
 
-        #[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+        impl ResolvedModule<'_> {
+            fn new(order: ImportOrder) -> Self {
+                Self {
+                    default_import: None,
+                    set: ImportSet::new(order),
+                }
+            }
+        }
+
+        #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
         enum ImportedElement<'a> {
             Plain(&'a ItemStr),
             Aliased(&'a ItemStr, &'a ItemStr),
@@ -510,10 +935,74 @@ impl JavaScript {
                 $(match (module_path, module) {
                     (_, Module::Global(from)) => $(quoted(from)),
                     (None, Module::Path(path)) => $(quoted(path.as_str())),
-                    (Some(module_path), Module::Path(path)) => $(quoted(module_path.relative(path).as_str())),
+                    (Some(module_path), Module::Path(path)) => $(quoted(relative_specifier(module_path, path))),
                 })
             }
         }
+
+        /// Compute the import specifier of `path` relative to the directory
+        /// of the module at `module_path`, like `./b.js` or `../b.js`.
+        fn relative_specifier(module_path: &RelativePath, path: &RelativePath) -> String {
+            let dir = module_path
+                .parent()
+                .unwrap_or_else(|| RelativePath::new(""));
+            let relative = with_extension(dir.relative(path));
+
+            if relative.as_str().starts_with("../") {
+                relative.into_string()
+            } else {
+                format!("./{}", relative)
+            }
+        }
+
+        /// Add the `.js` extension to paths which are missing one.
+        fn with_extension(mut path: RelativePathBuf) -> RelativePathBuf {
+            if path.extension().is_none() {
+                path.set_extension("js");
+            }
+
+            path
+        }
+    }
+
+    /// Translate trailing exports into the necessary tokens.
+    fn exports(out: &mut Tokens, tokens: &Tokens, config: &Config) {
+        use crate as genco;
+        use crate::prelude::*;
+
+        if let ExportStyle::Inline = config.export_style {
+            return;
+        }
+
+        let mut named = ImportSet::new(ImportOrder::AsEncountered);
+        let mut default = None;
+
+        for item in import_order::walk_imports(tokens) {
+            if let Any::Export(export) = item {
+                match export.kind {
+                    ExportKind::Named => named.insert(&export.name),
+                    ExportKind::Default => default = Some(&export.name),
+                }
+            }
+        }
+
+        if named.is_empty() && default.is_none() {
+            return;
+        }
+
+        out.line();
+
+        if !named.is_empty() {
+            out.push();
+            quote_in!(*out => export {$(for name in named join (, ) => $name)};);
+        }
+
+        if let Some(name) = default {
+            out.push();
+            quote_in!(*out => export default $name;);
+        }
+
+        out.push();
     }
 }
 
@@ -562,3 +1051,250 @@ where
         alias: None,
     }
 }
+
+/// Export a declaration of the given `name` from the module.
+///
+/// By default exports are collected into an `export {..};` statement at the
+/// end of the file, while [ExportStyle::Inline] prefixes the declaration with
+/// the `export` keyword instead. Use [Exported::into_default] for the default
+/// export.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: js::Tokens = quote! {
+///     $(js::export("add", quote! {
+///         function add(a, b) {
+///             return a + b;
+///         }
+///     }))
+///
+///     $(js::export("zero", quote!(const zero = 0;)))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "function add(a, b) {",
+///         "    return a + b;",
+///         "}",
+///         "",
+///         "const zero = 0;",
+///         "",
+///         "export {add, zero};",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn export<N, D>(name: N, declaration: D) -> Exported
+where
+    N: Into<ItemStr>,
+    D: FormatInto<JavaScript>,
+{
+    let mut t = Tokens::new();
+    t.append(declaration);
+
+    Exported {
+        export: Export {
+            kind: ExportKind::Named,
+            name: name.into(),
+        },
+        declaration: t,
+    }
+}
+
+/// A template literal like `` `Hello ${name}` ``.
+///
+/// Created through the [template()] function.
+#[derive(Debug, Clone)]
+pub struct Template {
+    parts: Vec<ItemStr>,
+    exprs: Vec<Tokens>,
+}
+
+impl FormatInto<JavaScript> for Template {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.item(Item::OpenQuote(true));
+
+        let mut exprs = self.exprs.into_iter();
+
+        for part in self.parts {
+            if !part.is_empty() {
+                tokens.item(Item::Literal(part));
+            }
+
+            if let Some(expr) = exprs.next() {
+                tokens.item(Item::OpenEval);
+                tokens.append(expr);
+                tokens.item(Item::CloseEval);
+            }
+        }
+
+        for expr in exprs {
+            tokens.item(Item::OpenEval);
+            tokens.append(expr);
+            tokens.item(Item::CloseEval);
+        }
+
+        tokens.item(Item::CloseQuote);
+    }
+}
+
+impl FormatInto<JavaScript> for &Template {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(self.clone());
+    }
+}
+
+/// Build a template literal out of literal `parts` and interpolated `exprs`,
+/// like `` `Hello ${name}` ``.
+///
+/// Parts and expressions are interleaved, starting with the first part. Like
+/// with tagged templates, there is typically one more part than there are
+/// expressions. Literal parts are escaped, so any backticks or `${` they
+/// contain are rendered as text.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let name: js::Tokens = quote!(user.name);
+/// let greeting = js::template(["Hello ", "! Use `${}` to interpolate."], [name]);
+///
+/// let toks = quote!(console.log($greeting););
+///
+/// assert_eq!(
+///     "console.log(`Hello ${user.name}! Use \\`\\${}\\` to interpolate.`);",
+///     toks.to_string()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn template<P, E>(parts: P, exprs: E) -> Template
+where
+    P: IntoIterator,
+    P::Item: Into<ItemStr>,
+    E: IntoIterator,
+    E::Item: FormatInto<JavaScript>,
+{
+    Template {
+        parts: parts.into_iter().map(Into::into).collect(),
+        exprs: exprs
+            .into_iter()
+            .map(|expr| {
+                let mut t = Tokens::new();
+                t.append(expr);
+                t
+            })
+            .collect(),
+    }
+}
+
+/// A declaration or expression marked as `async`, like
+/// `async function name() { .. }`.
+///
+/// Created through the [async_()] function.
+#[derive(Debug, Clone)]
+pub struct Async {
+    inner: Tokens,
+}
+
+impl FormatInto<JavaScript> for Async {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(ItemStr::Static("async"));
+        tokens.space();
+        tokens.append(self.inner);
+    }
+}
+
+impl FormatInto<JavaScript> for &Async {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(self.clone());
+    }
+}
+
+/// Mark a function declaration, function expression, arrow function, or
+/// method as `async`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: js::Tokens = quote! {
+///     $(js::async_(quote! {
+///         function load(url) {
+///             const response = $(js::await_(quote!(fetch(url))));
+///             return $(js::await_(quote!(response.json())));
+///         }
+///     }))
+///
+///     const handler = $(js::async_(quote!(() => $(js::await_("load(url)")))));
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "async function load(url) {",
+///         "    const response = await fetch(url);",
+///         "    return await response.json();",
+///         "}",
+///         "",
+///         "const handler = async () => await load(url);",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn async_<T>(inner: T) -> Async
+where
+    T: FormatInto<JavaScript>,
+{
+    let mut t = Tokens::new();
+    t.append(inner);
+    Async { inner: t }
+}
+
+/// An await expression, like `await promise`.
+///
+/// Created through the [await_()] function.
+#[derive(Debug, Clone)]
+pub struct Await {
+    expr: Tokens,
+}
+
+impl FormatInto<JavaScript> for Await {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(ItemStr::Static("await"));
+        tokens.space();
+        tokens.append(self.expr);
+    }
+}
+
+impl FormatInto<JavaScript> for &Await {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(self.clone());
+    }
+}
+
+/// Await the given expression, which in JavaScript is the prefix
+/// `await expr`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: js::Tokens = quote!(const value = $(js::await_("promise")););
+/// assert_eq!("const value = await promise;", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn await_<E>(expr: E) -> Await
+where
+    E: FormatInto<JavaScript>,
+{
+    let mut t = Tokens::new();
+    t.append(expr);
+    Await { expr: t }
+}
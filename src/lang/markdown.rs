@@ -0,0 +1,498 @@
+//! Specialization for Markdown generation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: markdown::Tokens = quote! {
+//!     $(markdown::heading(1, markdown::text("The *genco* crate")))
+//!
+//!     See $(markdown::link("the docs", "https://docs.rs/genco")) for details.
+//!
+//!     $(markdown::list([markdown::text("fast"), markdown::text("safe_ish")]))
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "# The \\*genco\\* crate",
+//!         "",
+//!         "See [the docs](https://docs.rs/genco) for details.",
+//!         "",
+//!         "- fast",
+//!         "- safe\\_ish",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in Markdown
+//!
+//! Markdown has no string literals, so quoted strings are rendered in double
+//! quotes with their content escaped like [text()].
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: markdown::Tokens = quote!(Use "*args" here);
+//! assert_eq!("Use \"\\*args\" here", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::fmt;
+use crate::lang::{Lang, LangItem};
+use crate::tokens::{FormatInto, Item, ItemStr};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem;
+
+/// Tokens container specialization for Markdown.
+pub type Tokens = crate::Tokens<Markdown>;
+
+/// Language specialization for Markdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Markdown(());
+
+impl Lang for Markdown {
+    type Config = Config;
+    type Format = Format;
+    type Item = Text;
+
+    fn default_config() -> Self::Config {
+        Config::default()
+    }
+
+    fn default_indentation() -> fmt::Indentation {
+        fmt::Indentation::Space(2)
+    }
+
+    fn line_comment(out: &mut Tokens, line: &str) {
+        out.append(ItemStr::Static("<!--"));
+
+        if !line.is_empty() {
+            out.space();
+            out.append(line);
+        }
+
+        out.space();
+        out.append(ItemStr::Static("-->"));
+    }
+
+    fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+        escape(out, input, false)
+    }
+}
+
+/// Format state for Markdown.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for Markdown.
+#[derive(Debug, Default)]
+pub struct Config {}
+
+/// Escape characters which would otherwise be interpreted as Markdown
+/// formatting, like emphasis, links, or headings.
+///
+/// If `line_start` is set, the input starts a line, so markers which start a
+/// block like `-`, `+`, `=`, and the `.` in `1.` are escaped there as well as
+/// after every line break in the input.
+fn escape(out: &mut impl core::fmt::Write, input: &str, mut line_start: bool) -> fmt::Result {
+    // If we're in a run of digits at the start of a line, like in `1.`.
+    let mut digits = false;
+
+    for c in input.chars() {
+        if line_start && c == ' ' {
+            out.write_char(c)?;
+            continue;
+        }
+
+        let at_start = mem::take(&mut line_start);
+
+        let escaped = match c {
+            '\n' => {
+                line_start = true;
+                false
+            }
+            '-' | '+' | '=' => at_start,
+            '.' => digits,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~' | '!' | '(' | ')'
+            | '&' => true,
+            _ => false,
+        };
+
+        digits = c.is_ascii_digit() && (at_start || digits);
+
+        if escaped {
+            out.write_char('\\')?;
+        }
+
+        out.write_char(c)?;
+    }
+
+    Ok(())
+}
+
+/// Escaped Markdown text.
+///
+/// Created through the [text()] function. The text is escaped when it's
+/// formatted, so that block markers are only escaped at the start of a line.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Text {
+    text: ItemStr,
+}
+
+impl LangItem<Markdown> for Text {
+    fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+        let line_start = out.is_line_start();
+        escape(out, &self.text, line_start)
+    }
+}
+
+impl FormatInto<Markdown> for Text {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(Item::Lang(0, Box::new(self)));
+    }
+}
+
+/// Text where characters like `*`, `_`, and `[` are escaped so that they are
+/// rendered as-is instead of being interpreted as formatting.
+///
+/// Markers which start a block, like `-` or `1.`, are escaped when they
+/// appear at the start of a line.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: markdown::Tokens = quote!($(markdown::text("2 * 3 = [six] # _not_ bold")));
+/// assert_eq!("2 \\* 3 = \\[six\\] \\# \\_not\\_ bold", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn text<T>(text: T) -> Text
+where
+    T: Into<ItemStr>,
+{
+    Text { text: text.into() }
+}
+
+/// A heading, like `## Title`.
+///
+/// Created through the [heading()] function.
+#[derive(Debug, Clone)]
+pub struct Heading {
+    level: usize,
+    content: Tokens,
+}
+
+impl FormatInto<Markdown> for Heading {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.push();
+
+        for _ in 0..self.level {
+            tokens.append(ItemStr::Static("#"));
+        }
+
+        tokens.space();
+        tokens.append(self.content);
+        tokens.push();
+    }
+}
+
+/// A heading of the given `level`, like `## Title` for level 2.
+///
+/// The level is clamped to the range 1 to 6 supported by Markdown. The
+/// content is not escaped, use [text()] for that.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: markdown::Tokens = quote! {
+///     $(markdown::heading(2, "Usage"))
+///     $(markdown::heading(9, markdown::text("deep_level")))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "## Usage",
+///         "###### deep\\_level",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn heading<T>(level: usize, content: T) -> Heading
+where
+    T: FormatInto<Markdown>,
+{
+    let mut t = Tokens::new();
+    t.append(content);
+
+    Heading {
+        level: level.clamp(1, 6),
+        content: t,
+    }
+}
+
+/// A fenced code block.
+///
+/// Created through the [code_block()] function.
+#[derive(Debug, Clone)]
+pub struct CodeBlock {
+    lang: ItemStr,
+    body: ItemStr,
+}
+
+impl FormatInto<Markdown> for CodeBlock {
+    fn format_into(self, tokens: &mut Tokens) {
+        let body = self.body.trim_end_matches(&['\r', '\n'][..]);
+
+        // NB: the fence needs to be longer than any run of backticks in the
+        // body so that the body can't close it.
+        let mut longest = 0;
+        let mut run = 0;
+
+        for c in body.chars() {
+            if c == '`' {
+                run += 1;
+                longest = longest.max(run);
+            } else {
+                run = 0;
+            }
+        }
+
+        let fence = "`".repeat(longest.max(2) + 1);
+
+        tokens.push();
+        tokens.append(ItemStr::from(fence.as_str()));
+        tokens.append(self.lang);
+        tokens.push();
+
+        if !body.is_empty() {
+            tokens.append(Item::Raw(ItemStr::from(body)));
+            tokens.push();
+        }
+
+        tokens.append(ItemStr::from(fence));
+        tokens.push();
+    }
+}
+
+/// A fenced code block with the given language tag, like `` ```rust ``.
+///
+/// The body is written as-is, without escaping or indentation. If the body
+/// contains backticks, the fence is made long enough that it can't be closed
+/// by them.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let example: rust::Tokens = quote! {
+///     fn main() {
+///         println!("Hello *world*");
+///     }
+/// };
+///
+/// let toks: markdown::Tokens = quote! {
+///     $(markdown::heading(1, "Example"))
+///
+///     $(markdown::code_block("rust", example.to_file_string()?))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "# Example",
+///         "",
+///         "```rust",
+///         "fn main() {",
+///         "    println!(\"Hello *world*\");",
+///         "}",
+///         "```",
+///     ],
+///     toks.to_file_vec()?
+/// );
+///
+/// let toks: markdown::Tokens = quote!($(markdown::code_block("md", "Use ```rust to start.")));
+/// assert_eq!(vec!["````md", "Use ```rust to start.", "````"], toks.to_file_vec()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn code_block<L, B>(lang: L, body: B) -> CodeBlock
+where
+    L: Into<ItemStr>,
+    B: Into<ItemStr>,
+{
+    CodeBlock {
+        lang: lang.into(),
+        body: body.into(),
+    }
+}
+
+/// A link.
+///
+/// Created through the [link()] function.
+#[derive(Debug, Clone)]
+pub struct Link {
+    text: ItemStr,
+    url: ItemStr,
+}
+
+impl FormatInto<Markdown> for Link {
+    fn format_into(self, tokens: &mut Tokens) {
+        let mut out = String::with_capacity(self.text.len() + self.url.len() + 4);
+        out.push('[');
+
+        if escape(&mut out, &self.text, false).is_err() {
+            return;
+        }
+
+        out.push_str("](");
+
+        for c in self.url.chars() {
+            match c {
+                ' ' => out.push_str("%20"),
+                '(' | ')' | '\\' => {
+                    out.push('\\');
+                    out.push(c);
+                }
+                c => out.push(c),
+            }
+        }
+
+        out.push(')');
+        tokens.append(Item::Literal(out.into()));
+    }
+}
+
+/// A link, like `[text](url)`.
+///
+/// The text is escaped like [text()], and spaces and parenthesis in the url
+/// are encoded so that they don't end the link.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: markdown::Tokens = quote!($(markdown::link("[draft] notes", "notes (old).md")));
+/// assert_eq!("[\\[draft\\] notes](notes%20\\(old\\).md)", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn link<T, U>(text: T, url: U) -> Link
+where
+    T: Into<ItemStr>,
+    U: Into<ItemStr>,
+{
+    Link {
+        text: text.into(),
+        url: url.into(),
+    }
+}
+
+/// A list of items.
+///
+/// Created through the [list()] and [ordered_list()] functions.
+#[derive(Debug, Clone)]
+pub struct List {
+    ordered: bool,
+    items: Vec<Tokens>,
+}
+
+impl FormatInto<Markdown> for List {
+    fn format_into(self, tokens: &mut Tokens) {
+        for (n, item) in self.items.into_iter().enumerate() {
+            tokens.push();
+
+            if self.ordered {
+                tokens.append(ItemStr::from(alloc::format!("{}.", n + 1)));
+            } else {
+                tokens.append(ItemStr::Static("-"));
+            }
+
+            tokens.space();
+            tokens.append(item);
+        }
+
+        tokens.push();
+    }
+}
+
+/// An unordered list, with each item on its own line like `- item`.
+///
+/// Items are not escaped, use [text()] for that.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: markdown::Tokens = quote! {
+///     $(markdown::list(["one", "two"]))
+/// };
+///
+/// assert_eq!(vec!["- one", "- two"], toks.to_file_vec()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn list<I>(items: I) -> List
+where
+    I: IntoIterator,
+    I::Item: FormatInto<Markdown>,
+{
+    List {
+        ordered: false,
+        items: collect(items),
+    }
+}
+
+/// An ordered list, with each item numbered like `1. item`.
+///
+/// Items are not escaped, use [text()] for that.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: markdown::Tokens = quote! {
+///     $(markdown::ordered_list([markdown::text("build"), markdown::text("test_all")]))
+/// };
+///
+/// assert_eq!(vec!["1. build", "2. test\\_all"], toks.to_file_vec()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn ordered_list<I>(items: I) -> List
+where
+    I: IntoIterator,
+    I::Item: FormatInto<Markdown>,
+{
+    List {
+        ordered: true,
+        items: collect(items),
+    }
+}
+
+fn collect<I>(items: I) -> Vec<Tokens>
+where
+    I: IntoIterator,
+    I::Item: FormatInto<Markdown>,
+{
+    items
+        .into_iter()
+        .map(|item| {
+            let mut t = Tokens::new();
+            t.append(item);
+            t
+        })
+        .collect()
+}
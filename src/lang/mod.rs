@@ -17,27 +17,72 @@
 //! # }
 //! ```
 
+pub mod asm;
+pub mod bash;
 pub mod c;
+pub mod cpp;
 pub mod csharp;
+pub mod cue;
 pub mod dart;
+pub mod dockerfile;
+pub mod elixir;
+pub mod gdscript;
 pub mod go;
+pub mod groovy;
+pub mod hcl;
+pub mod html;
+pub(crate) mod import_order;
 pub mod java;
+#[cfg(feature = "std")]
 pub mod js;
+pub mod markdown;
+pub mod nim;
+pub mod ocaml;
+pub mod perl;
 pub mod python;
+pub mod r;
 pub mod rust;
+pub mod solidity;
 pub mod swift;
+pub mod verilog;
+pub mod zig;
 
+pub use self::asm::Asm;
+pub use self::bash::Bash;
 pub use self::c::C;
+pub use self::cpp::Cpp;
 pub use self::csharp::Csharp;
+pub use self::cue::Cue;
 pub use self::dart::Dart;
+pub use self::dockerfile::Dockerfile;
+pub use self::elixir::Elixir;
+pub use self::gdscript::GDScript;
 pub use self::go::Go;
+pub use self::groovy::Groovy;
+pub use self::hcl::Hcl;
+pub use self::html::Html;
+pub use self::import_order::ImportOrder;
 pub use self::java::Java;
+#[cfg(feature = "std")]
 pub use self::js::JavaScript;
+pub use self::markdown::Markdown;
+pub use self::nim::Nim;
+pub use self::ocaml::OCaml;
+pub use self::perl::Perl;
 pub use self::python::Python;
+pub use self::r::R;
 pub use self::rust::Rust;
+pub use self::solidity::Solidity;
 pub use self::swift::Swift;
+pub use self::verilog::Verilog;
+pub use self::zig::Zig;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
 
 use crate::fmt;
+use crate::tokens::{Assoc, ItemStr};
 use crate::Tokens;
 
 /// Trait to implement for language specialization.
@@ -46,7 +91,7 @@ use crate::Tokens;
 /// module.
 pub trait Lang
 where
-    Self: 'static + Sized + Copy + Eq + Ord + std::hash::Hash + std::fmt::Debug,
+    Self: 'static + Sized + Copy + Eq + Ord + core::hash::Hash + core::fmt::Debug,
 {
     /// Configuration associated with building a formatting element.
     type Config;
@@ -55,7 +100,15 @@ where
     /// The type used when resolving imports.
     type Item: LangItem<Self>;
 
-    /// Provide the default indentation.
+    /// Construct the configuration used when formatting a file without an
+    /// explicit one, like through [Tokens::to_file_vec].
+    fn default_config() -> Self::Config;
+
+    /// Provide the default indentation, which is used by
+    /// [fmt::Config::from_lang] unless it's overridden through
+    /// [fmt::Config::with_indentation].
+    ///
+    /// Defaults to four spaces.
     fn default_indentation() -> fmt::Indentation {
         fmt::Indentation::Space(4)
     }
@@ -67,7 +120,7 @@ where
         _format: &Self::Format,
         _has_eval: bool,
     ) -> fmt::Result {
-        use std::fmt::Write as _;
+        use core::fmt::Write as _;
         out.write_char('"')?;
         Ok(())
     }
@@ -79,7 +132,7 @@ where
         _format: &Self::Format,
         _has_eval: bool,
     ) -> fmt::Result {
-        use std::fmt::Write as _;
+        use core::fmt::Write as _;
         out.write_char('"')?;
         Ok(())
     }
@@ -91,7 +144,7 @@ where
         format: &Self::Format,
         literal: &str,
     ) -> fmt::Result {
-        use std::fmt::Write as _;
+        use core::fmt::Write as _;
 
         Self::start_string_eval(out, config, format)?;
         out.write_str(literal)?;
@@ -117,13 +170,143 @@ where
         Ok(())
     }
 
+    /// Escape an identifier which would otherwise collide with a keyword in the
+    /// language.
+    ///
+    /// This is applied when rendering the names of imported items and locals.
+    /// By default the identifier is returned as-is.
+    fn escape_ident(name: &str) -> Cow<'_, str> {
+        Cow::Borrowed(name)
+    }
+
+    /// Test if the given import refers to something which is always in scope,
+    /// like `java.lang.String` in Java.
+    ///
+    /// Builtin imports never produce an import statement when imports are
+    /// collated, but are still rendered by name. By default nothing is
+    /// builtin.
+    fn is_builtin_import(_import: &Self::Item) -> bool {
+        false
+    }
+
+    /// The operator used to concatenate strings, like `+` in `"a" + b`.
+    ///
+    /// This is used by the default implementation of [Lang::concat].
+    fn concat_operator() -> &'static str {
+        "+"
+    }
+
+    /// Render the concatenation of the given string expressions.
+    ///
+    /// This is used by [concat()][crate::tokens::concat()], and defaults to
+    /// separating the parts with [Lang::concat_operator]. Languages without an
+    /// infix operator for concatenating strings, like R with `paste0(a, b)`,
+    /// override this instead.
+    fn concat(out: &mut Tokens<Self>, parts: Vec<Tokens<Self>>) {
+        let mut it = parts.into_iter().peekable();
+
+        while let Some(part) = it.next() {
+            out.append(part);
+
+            if it.peek().is_some() {
+                out.space();
+                out.append(ItemStr::Static(Self::concat_operator()));
+                out.space();
+            }
+        }
+    }
+
+    /// Render a conditional expression which evaluates to `then` if `cond`
+    /// holds, and `els` otherwise.
+    ///
+    /// This is used by [ternary()][crate::tokens::ternary()], and defaults to
+    /// the C-style `cond ? then : els`.
+    fn ternary(out: &mut Tokens<Self>, cond: Tokens<Self>, then: Tokens<Self>, els: Tokens<Self>) {
+        use crate as genco;
+        use crate::quote_in;
+
+        quote_in!(*out => $cond ? $then : $els);
+    }
+
+    /// Render a single commented out line, like `// line`.
+    ///
+    /// This is used by [Tokens::comment_out], and defaults to C-style `//`
+    /// comments. The `line` is empty for blank lines.
+    fn line_comment(out: &mut Tokens<Self>, line: &str) {
+        out.append(ItemStr::Static("//"));
+
+        if !line.is_empty() {
+            out.space();
+            out.append(line);
+        }
+    }
+
+    /// The precedence and associativity of the given binary operator, or
+    /// `None` if the operator is not known.
+    ///
+    /// Higher precedences bind tighter. This is used by
+    /// [bin_op()][crate::tokens::bin_op()] to decide which operands need to
+    /// be parenthesized.
+    fn operator_precedence(_op: &str) -> Option<(u8, Assoc)> {
+        None
+    }
+
+    /// Write a literal run of text, like `a+b` in `quote!(a+b)`.
+    ///
+    /// Adjacent tokens in [quote!][crate::quote] are collapsed into a single
+    /// literal which is written as-is by default. Languages can override this
+    /// to re-space literals, like through [fmt::space_operators].
+    fn write_literal(out: &mut fmt::Formatter<'_>, literal: &str) -> fmt::Result {
+        use core::fmt::Write as _;
+
+        out.write_str(literal)
+    }
+
     /// Performing string quoting according to language convention.
     fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
-        use std::fmt::Write as _;
+        use core::fmt::Write as _;
 
         out.write_str(input)
     }
 
+    /// Write a character literal according to language convention.
+    ///
+    /// By default this is the character quoted with [Lang::write_quoted]
+    /// surrounded by single quotes, like `'a'`.
+    ///
+    /// This is used by [quoted_char()][crate::tokens::quoted_char()].
+    fn write_char_literal(out: &mut fmt::Formatter<'_>, c: char) -> fmt::Result {
+        use core::fmt::Write as _;
+
+        out.write_char('\'')?;
+
+        if c == '\'' {
+            out.write_str("\\'")?;
+        } else {
+            Self::write_quoted(out, c.encode_utf8(&mut [0; 4]))?;
+        }
+
+        out.write_char('\'')?;
+        Ok(())
+    }
+
+    /// Post-process the fully formatted output of a file, like trimming
+    /// trailing whitespace from every line.
+    ///
+    /// This is applied once the whole file has been formatted into a string,
+    /// which is done by [Tokens::to_file_string], [Tokens::fmt_file_to],
+    /// [Tokens::write_into_file], and [FileSet::write_to_dir]. It defaults to
+    /// returning the output as-is.
+    ///
+    /// Output which is written as it's being formatted is not post-processed.
+    /// This includes [Tokens::format_file] and the line-based
+    /// [Tokens::to_file_vec], [Tokens::lines], and [Tokens::count_lines].
+    ///
+    /// [FileSet::write_to_dir]: crate::FileSet::write_to_dir
+    fn post_format(output: String, _config: &Self::Config) -> String {
+        output
+    }
+
     /// Write a file according to the specified language convention.
     fn format_file(
         tokens: &Tokens<Self>,
@@ -146,6 +329,8 @@ impl Lang for () {
     type Config = ();
     type Format = ();
     type Item = ();
+
+    fn default_config() -> Self::Config {}
 }
 
 impl<L> LangItem<L> for ()
@@ -164,7 +349,7 @@ where
 pub trait LangItem<L>
 where
     L: Lang,
-    Self: 'static + Clone + Eq + Ord + std::hash::Hash + std::fmt::Debug,
+    Self: 'static + Clone + Eq + Ord + core::hash::Hash + core::fmt::Debug,
 {
     /// Format the language item appropriately.
     fn format(
@@ -182,7 +367,7 @@ where
 /// This is one of the more common escape sequences and is provided here so you
 /// can use it if a language you've implemented requires it.
 pub fn c_family_write_quoted(out: &mut fmt::Formatter, input: &str) -> fmt::Result {
-    use std::fmt::Write as _;
+    use core::fmt::Write as _;
 
     for c in input.chars() {
         match c {
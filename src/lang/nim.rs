@@ -0,0 +1,292 @@
+//! Specialization for Nim code generation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let split = nim::import("strutils", "split");
+//! let param_str = nim::import("os", "paramStr").selective();
+//!
+//! let toks: nim::Tokens = quote! {
+//!     proc firstWords(): seq[string] =
+//!         $split($param_str(1), " ")
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "from os import paramStr",
+//!         "import strutils",
+//!         "",
+//!         "proc firstWords(): seq[string] =",
+//!         "  split(paramStr(1), \" \")",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in Nim
+//!
+//! Since Nim string literals are UTF-8, only control characters, backslashes,
+//! and quotes are escaped.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: nim::Tokens = quote!("hello \n \"world\" \x1b ÿ");
+//! assert_eq!("\"hello \\n \\\"world\\\" \\x1b ÿ\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate as genco;
+use crate::fmt;
+use crate::lang::import_order::{self, ImportMap, ImportOrder, ImportSet};
+use crate::tokens::ItemStr;
+use crate::{quote, quote_in};
+use core::fmt::Write as _;
+
+/// Tokens container specialization for Nim.
+pub type Tokens = crate::Tokens<Nim>;
+
+impl_lang! {
+    /// Language specialization for Nim.
+    pub Nim {
+        type Config = Config;
+        type Format = Format;
+        type Item = Import;
+
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn line_comment(out: &mut Tokens, line: &str) {
+            out.append(ItemStr::Static("#"));
+
+            if !line.is_empty() {
+                out.space();
+                out.append(line);
+            }
+        }
+
+        fn ternary(out: &mut Tokens, cond: Tokens, then: Tokens, els: Tokens) {
+            use crate as genco;
+            use crate::quote_in;
+
+            quote_in!(*out => if $cond: $then else: $els);
+        }
+
+        fn default_indentation() -> fmt::Indentation {
+            fmt::Indentation::Space(2)
+        }
+
+        fn concat_operator() -> &'static str {
+            "&"
+        }
+
+        fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            // From: https://nim-lang.org/docs/manual.html#lexical-analysis-string-literals
+            for c in input.chars() {
+                match c {
+                    '\n' => out.write_str("\\n")?,
+                    '\r' => out.write_str("\\r")?,
+                    '\t' => out.write_str("\\t")?,
+                    '\\' => out.write_str("\\\\")?,
+                    '"' => out.write_str("\\\"")?,
+                    c if (c as u32) < 0x20 || c == '\x7f' => {
+                        write!(out, "\\x{:02x}", c as u32)?;
+                    }
+                    c if !c.is_ascii() && out.ascii_only() => {
+                        write!(out, "\\u{{{:x}}}", c as u32)?;
+                    }
+                    c => out.write_char(c)?,
+                }
+            }
+
+            Ok(())
+        }
+
+        fn format_file(
+            tokens: &Tokens,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut imports = Tokens::new();
+            Self::imports(&mut imports, tokens, config);
+            let format = Format::default();
+            imports.format(out, config, &format)?;
+            tokens.format(out, config, &format)?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+            out.write_str(&self.name)?;
+            Ok(())
+        }
+    }
+}
+
+/// Formatting state for Nim.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for Nim.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// The order in which imports are rendered.
+    import_order: ImportOrder,
+}
+
+impl Config {
+    /// Set the order in which imports are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self { import_order }
+    }
+}
+
+/// The import of a Nim name.
+///
+/// By default the whole module is imported, like `import strutils`. A
+/// selective import like `from strutils import split` can be requested through
+/// [Import::selective].
+///
+/// Created through the [import()] function.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Import {
+    /// Module of the imported name.
+    module: ItemStr,
+    /// The name that was imported.
+    name: ItemStr,
+    /// If only the name should be imported from the module.
+    selective: bool,
+}
+
+impl Import {
+    /// Only import the name from the module, like `from strutils import
+    /// split`.
+    ///
+    /// If the same module is imported in full elsewhere, the selective import
+    /// is redundant and is omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let toks: nim::Tokens = quote! {
+    ///     $(nim::import("strutils", "split").selective())
+    ///     $(nim::import("strutils", "join").selective())
+    ///     $(nim::import("os", "paramStr").selective())
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "from os import paramStr",
+    ///         "from strutils import join, split",
+    ///         "",
+    ///         "split",
+    ///         "join",
+    ///         "paramStr",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn selective(self) -> Self {
+        Self {
+            selective: true,
+            ..self
+        }
+    }
+}
+
+impl Nim {
+    fn imports(out: &mut Tokens, tokens: &Tokens, config: &Config) {
+        let order = config.import_order;
+        let mut modules = ImportSet::new(order);
+        let mut selective = ImportMap::new(order);
+
+        for import in import_order::walk_imports(tokens) {
+            if import.selective {
+                selective
+                    .get_or_insert_with(&import.module, || ImportSet::new(order))
+                    .insert(&import.name);
+            } else {
+                modules.insert(&import.module);
+            }
+        }
+
+        selective.retain(|module, _| !modules.contains(module));
+
+        if modules.is_empty() && selective.is_empty() {
+            return;
+        }
+
+        for (module, names) in selective {
+            out.push();
+
+            let names = names.into_iter().map(|name| quote!($name));
+
+            quote_in! {*out =>
+                from $module import $(for n in names join (, ) => $n)
+            }
+        }
+
+        for module in modules {
+            out.push();
+            quote_in!(*out => import $module);
+        }
+
+        out.line();
+    }
+}
+
+/// The import of a Nim name, like `import strutils`.
+///
+/// Names are always used unqualified, since importing a module brings all of
+/// its exported symbols into scope.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: nim::Tokens = quote! {
+///     $(nim::import("strutils", "split"))
+///     $(nim::import("strutils", "join"))
+///     $(nim::import("strutils", "strip").selective())
+///     $(nim::import("sequtils", "map").selective())
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "from sequtils import map",
+///         "import strutils",
+///         "",
+///         "split",
+///         "join",
+///         "strip",
+///         "map",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn import<M, N>(module: M, name: N) -> Import
+where
+    M: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    Import {
+        module: module.into(),
+        name: name.into(),
+        selective: false,
+    }
+}
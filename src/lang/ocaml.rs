@@ -0,0 +1,271 @@
+//! Specialization for OCaml code generation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let map = ocaml::imported("List", "map");
+//! let printf = ocaml::qualified("Printf", "printf");
+//!
+//! let toks: ocaml::Tokens = quote! {
+//!     let () =
+//!       $map (fun x -> $printf "%d\n" x) [1; 2; 3]
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "open List",
+//!         "",
+//!         "let () =",
+//!         "  map (fun x -> Printf.printf \"%d\\n\" x) [1; 2; 3]",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in OCaml
+//!
+//! OCaml strings are sequences of bytes, so UTF-8 is embedded directly while
+//! control characters are escaped with `\xhh`.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: ocaml::Tokens = quote!("hello \n \"world\" \x1b ÿ");
+//! assert_eq!("\"hello \\n \\\"world\\\" \\x1b ÿ\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate as genco;
+use crate::fmt;
+use crate::lang::import_order::{self, ImportOrder, ImportSet};
+use crate::quote_in;
+use crate::tokens::ItemStr;
+use core::fmt::Write as _;
+
+/// Tokens container specialization for OCaml.
+pub type Tokens = crate::Tokens<OCaml>;
+
+impl_lang! {
+    /// Language specialization for OCaml.
+    pub OCaml {
+        type Config = Config;
+        type Format = Format;
+        type Item = Import;
+
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn line_comment(out: &mut Tokens, line: &str) {
+            out.append(ItemStr::Static("(*"));
+
+            if !line.is_empty() {
+                out.space();
+                out.append(line);
+            }
+
+            out.space();
+            out.append(ItemStr::Static("*)"));
+        }
+
+        fn ternary(out: &mut Tokens, cond: Tokens, then: Tokens, els: Tokens) {
+            use crate as genco;
+            use crate::quote_in;
+
+            quote_in!(*out => if $cond then $then else $els);
+        }
+
+        fn default_indentation() -> fmt::Indentation {
+            fmt::Indentation::Space(2)
+        }
+
+        fn concat_operator() -> &'static str {
+            "^"
+        }
+
+        fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            // From: https://v2.ocaml.org/manual/lex.html#sss:stringliterals
+            for c in input.chars() {
+                match c {
+                    '\n' => out.write_str("\\n")?,
+                    '\r' => out.write_str("\\r")?,
+                    '\t' => out.write_str("\\t")?,
+                    '\u{8}' => out.write_str("\\b")?,
+                    '\\' => out.write_str("\\\\")?,
+                    '"' => out.write_str("\\\"")?,
+                    c if (c as u32) < 0x20 || c == '\x7f' => {
+                        write!(out, "\\x{:02x}", c as u32)?;
+                    }
+                    c if !c.is_ascii() && out.ascii_only() => {
+                        write!(out, "\\u{{{:x}}}", c as u32)?;
+                    }
+                    c => out.write_char(c)?,
+                }
+            }
+
+            Ok(())
+        }
+
+        fn format_file(
+            tokens: &Tokens,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut header = Tokens::new();
+            Self::opens(&mut header, tokens, config);
+            let format = Format::default();
+            header.format(out, config, &format)?;
+            tokens.format(out, config, &format)?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+            if self.qualified {
+                out.write_str(&self.module)?;
+                out.write_str(SEP)?;
+            }
+
+            out.write_str(&self.name)?;
+            Ok(())
+        }
+    }
+}
+
+/// Formatting state for OCaml.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for OCaml.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// The order in which `open` statements are rendered.
+    import_order: ImportOrder,
+}
+
+impl Config {
+    /// Set the order in which `open` statements are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self { import_order }
+    }
+}
+
+static SEP: &str = ".";
+
+/// A name from an OCaml module.
+///
+/// Created through the [imported()] and [qualified()] functions.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Import {
+    /// Module the name belongs to.
+    module: ItemStr,
+    /// The name in the module.
+    name: ItemStr,
+    /// If the name is referenced through its module instead of opening it.
+    qualified: bool,
+}
+
+impl OCaml {
+    fn opens(out: &mut Tokens, tokens: &Tokens, config: &Config) {
+        let mut modules = ImportSet::new(config.import_order);
+
+        for import in import_order::walk_imports(tokens) {
+            if !import.qualified {
+                modules.insert(&import.module);
+            }
+        }
+
+        if modules.is_empty() {
+            return;
+        }
+
+        for module in modules {
+            out.push();
+            quote_in!(*out => open $module);
+        }
+
+        out.line();
+    }
+}
+
+/// A name from a module which is opened, like `open List`, so that the name
+/// can be used unqualified.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: ocaml::Tokens = quote! {
+///     $(ocaml::imported("List", "map"))
+///     $(ocaml::imported("List", "iter"))
+///     $(ocaml::imported("Array", "length"))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "open Array",
+///         "open List",
+///         "",
+///         "map",
+///         "iter",
+///         "length",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn imported<M, N>(module: M, name: N) -> Import
+where
+    M: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    Import {
+        module: module.into(),
+        name: name.into(),
+        qualified: false,
+    }
+}
+
+/// A name which is referenced through its module, like `List.map`, without
+/// opening the module.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: ocaml::Tokens = quote! {
+///     $(ocaml::qualified("List", "map"))
+///     $(ocaml::qualified("Stdlib.String", "concat"))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "List.map",
+///         "Stdlib.String.concat",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn qualified<M, N>(module: M, name: N) -> Import
+where
+    M: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    Import {
+        module: module.into(),
+        name: name.into(),
+        qualified: true,
+    }
+}
@@ -0,0 +1,312 @@
+//! Specialization for Perl code generation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let sum = perl::imported("List::Util", "sum");
+//! let max = perl::imported("List::Util", "max");
+//! let dumper = perl::imported("Data::Dumper", "Dumper");
+//!
+//! let toks: perl::Tokens = quote! {
+//!     my @values = (1, 2, 3);
+//!     print $sum(@values), $max(@values);
+//!     print $dumper([@values]);
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "use Data::Dumper qw(Dumper);",
+//!         "use List::Util qw(max sum);",
+//!         "",
+//!         "my @values = (1, 2, 3);",
+//!         "print sum(@values), max(@values);",
+//!         "print Dumper([@values]);",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in Perl
+//!
+//! Strings are double-quoted by default, where `$` and `@` are escaped so that
+//! they aren't interpolated. Single-quoted strings can be used through
+//! [Config::with_string_quote].
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: perl::Tokens = quote!("costs $5 @ \"shop\"\n");
+//! assert_eq!("\"costs \\$5 \\@ \\\"shop\\\"\\n\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate as genco;
+use crate::fmt;
+use crate::lang::import_order::{self, ImportMap, ImportOrder, ImportSet};
+use crate::quote_in;
+use crate::tokens::ItemStr;
+use core::fmt::Write as _;
+
+/// Tokens container specialization for Perl.
+pub type Tokens = crate::Tokens<Perl>;
+
+impl_lang! {
+    /// Language specialization for Perl.
+    pub Perl {
+        type Config = Config;
+        type Format = Format;
+        type Item = Import;
+
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn line_comment(out: &mut Tokens, line: &str) {
+            out.append(ItemStr::Static("#"));
+
+            if !line.is_empty() {
+                out.space();
+                out.append(line);
+            }
+        }
+
+        fn concat_operator() -> &'static str {
+            "."
+        }
+
+        fn open_quote(
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+            _format: &Self::Format,
+            _has_eval: bool,
+        ) -> fmt::Result {
+            let quote = config.string_quote.as_char();
+            out.write_char(quote)?;
+            out.set_quote(Some(quote));
+            Ok(())
+        }
+
+        fn close_quote(
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+            _format: &Self::Format,
+            _has_eval: bool,
+        ) -> fmt::Result {
+            out.write_char(config.string_quote.as_char())?;
+            out.set_quote(None);
+            Ok(())
+        }
+
+        fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            // From: https://perldoc.perl.org/perlop#Quote-and-Quote-like-Operators
+            if let Some('\'') = out.quote() {
+                for c in input.chars() {
+                    match c {
+                        '\\' => out.write_str("\\\\")?,
+                        '\'' => out.write_str("\\'")?,
+                        c => out.write_char(c)?,
+                    }
+                }
+
+                return Ok(());
+            }
+
+            for c in input.chars() {
+                match c {
+                    '\n' => out.write_str("\\n")?,
+                    '\r' => out.write_str("\\r")?,
+                    '\t' => out.write_str("\\t")?,
+                    '\\' => out.write_str("\\\\")?,
+                    '"' => out.write_str("\\\"")?,
+                    '$' => out.write_str("\\$")?,
+                    '@' => out.write_str("\\@")?,
+                    c if (c as u32) < 0x20 || c == '\x7f' => {
+                        write!(out, "\\x{:02x}", c as u32)?;
+                    }
+                    c if !c.is_ascii() && out.ascii_only() => {
+                        write!(out, "\\x{{{:x}}}", c as u32)?;
+                    }
+                    c => out.write_char(c)?,
+                }
+            }
+
+            Ok(())
+        }
+
+        fn format_file(
+            tokens: &Tokens,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut imports = Tokens::new();
+            Self::imports(&mut imports, tokens, config);
+            let format = Format::default();
+            imports.format(out, config, &format)?;
+            tokens.format(out, config, &format)?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+            out.write_str(&self.name)?;
+            Ok(())
+        }
+    }
+}
+
+/// Formatting state for Perl.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for Perl.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// The order in which imports are rendered.
+    import_order: ImportOrder,
+    /// The quote character used for string literals.
+    string_quote: QuoteChar,
+}
+
+impl Config {
+    /// Set the order in which imports are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self {
+            import_order,
+            ..self
+        }
+    }
+
+    /// Set the quote character used for string literals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let toks: perl::Tokens = quote!(my $$path = $(quoted("C:\\it's $HOME")););
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// let fmt = fmt::Config::from_lang::<Perl>();
+    /// let config = perl::Config::default().with_string_quote(perl::QuoteChar::Single);
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(vec!["my $path = 'C:\\\\it\\'s $HOME';"], w.into_vec());
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_string_quote(self, string_quote: QuoteChar) -> Self {
+        Self {
+            string_quote,
+            ..self
+        }
+    }
+}
+
+/// The quote character used for string literals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum QuoteChar {
+    /// Double quotes, like `"hello"`, where `$` and `@` are escaped.
+    ///
+    /// This is the default.
+    Double,
+    /// Single quotes, like `'hello'`, where only `\` and `'` are escaped.
+    Single,
+}
+
+impl QuoteChar {
+    fn as_char(self) -> char {
+        match self {
+            Self::Double => '"',
+            Self::Single => '\'',
+        }
+    }
+}
+
+impl Default for QuoteChar {
+    fn default() -> Self {
+        Self::Double
+    }
+}
+
+/// The import of a name from a Perl module, like `use List::Util qw(sum);`.
+///
+/// Created through the [imported()] function.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Import {
+    /// Module of the imported name.
+    module: ItemStr,
+    /// The name that was imported.
+    name: ItemStr,
+}
+
+impl Perl {
+    fn imports(out: &mut Tokens, tokens: &Tokens, config: &Config) {
+        let order = config.import_order;
+        let mut modules = ImportMap::new(order);
+
+        for import in import_order::walk_imports(tokens) {
+            modules
+                .get_or_insert_with(&import.module, || ImportSet::new(order))
+                .insert(&import.name);
+        }
+
+        if modules.is_empty() {
+            return;
+        }
+
+        for (module, names) in modules {
+            out.push();
+            quote_in!(*out => use $module qw($(for n in names join ( ) => $n)););
+        }
+
+        out.line();
+    }
+}
+
+/// Import a name from a Perl module, like `use List::Util qw(sum);`.
+///
+/// Names imported from the same module are collated into a single `use`
+/// statement.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: perl::Tokens = quote! {
+///     $(perl::imported("List::Util", "sum"))
+///     $(perl::imported("List::Util", "first"))
+///     $(perl::imported("List::Util", "sum"))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use List::Util qw(first sum);",
+///         "",
+///         "sum",
+///         "first",
+///         "sum",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn imported<M, N>(module: M, name: N) -> Import
+where
+    M: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    Import {
+        module: module.into(),
+        name: name.into(),
+    }
+}
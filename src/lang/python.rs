@@ -19,10 +19,11 @@
 
 use crate as genco;
 use crate::fmt;
+use crate::lang::import_order::{self, ImportComments, ImportMap, ImportOrder, ImportSet};
 use crate::tokens::ItemStr;
 use crate::{quote, quote_in};
-use std::collections::{BTreeMap, BTreeSet};
-use std::fmt::Write as _;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
 
 /// Tokens container specialization for Python.
 pub type Tokens = crate::Tokens<Python>;
@@ -34,6 +35,26 @@ impl_lang! {
         type Format = Format;
         type Item = Any;
 
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn line_comment(out: &mut Tokens, line: &str) {
+            out.append(ItemStr::Static("#"));
+
+            if !line.is_empty() {
+                out.space();
+                out.append(line);
+            }
+        }
+
+        fn ternary(out: &mut Tokens, cond: Tokens, then: Tokens, els: Tokens) {
+            use crate as genco;
+            use crate::quote_in;
+
+            quote_in!(*out => $then if $cond else $els);
+        }
+
         fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
             // From: https://docs.python.org/3/reference/lexical_analysis.html#string-and-bytes-literals
             super::c_family_write_quoted(out, input)
@@ -45,7 +66,7 @@ impl_lang! {
             config: &Self::Config,
         ) -> fmt::Result {
             let mut imports = Tokens::new();
-            Self::imports(&mut imports, tokens);
+            Self::imports(&mut imports, tokens, config);
             let format = Format::default();
             imports.format(out, config, &format)?;
             tokens.format(out, config, &format)?;
@@ -83,16 +104,30 @@ impl_lang! {
     }
 }
 
+#[cfg(feature = "serde")]
+impl_serde_any!(Import, ImportModule);
+
 /// Formatting state for python.
 #[derive(Debug, Default)]
 pub struct Format {}
 /// Configuration for python.
 #[derive(Debug, Default)]
-pub struct Config {}
+pub struct Config {
+    /// The order in which imports are rendered.
+    import_order: ImportOrder,
+}
+
+impl Config {
+    /// Set the order in which imports are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self { import_order }
+    }
+}
 
 static SEP: &str = ".";
 
 #[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum TypeModule {
     Unqualified {
         /// Name of imported module.
@@ -134,6 +169,7 @@ impl TypeModule {
 ///
 /// Created through the [import()] function.
 #[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Import {
     /// Module of the imported name.
     module: TypeModule,
@@ -141,6 +177,8 @@ pub struct Import {
     name: ItemStr,
     /// Alias of the name imported.
     alias: Option<ItemStr>,
+    /// Comment to render above the import statement.
+    comment: Option<ItemStr>,
 }
 
 impl Import {
@@ -237,18 +275,64 @@ impl Import {
             ..self
         }
     }
+
+    /// Attach a comment which is rendered above the import statement the
+    /// name ends up in.
+    ///
+    /// Comments stay with their import when imports are sorted. If several
+    /// imports in the same statement have comments, each distinct comment is
+    /// rendered once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let toks = quote! {
+    ///     $(python::import("requests", "get").with_comment("third party"))
+    ///     $(python::import("requests", "post"))
+    ///     $(python::import("collections", "namedtuple"))
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "from collections import namedtuple",
+    ///         "# third party",
+    ///         "from requests import get, post",
+    ///         "",
+    ///         "get",
+    ///         "post",
+    ///         "namedtuple",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_comment<C>(self, comment: C) -> Self
+    where
+        C: Into<ItemStr>,
+    {
+        Self {
+            comment: Some(comment.into()),
+            ..self
+        }
+    }
 }
 
 /// The import of a Python module `import module`.
 ///
 /// Created through the [import_module()] function.
 #[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImportModule {
     /// Module of the imported name.
     module: ItemStr,
 
     /// Alias of module imported.
     alias: Option<ItemStr>,
+
+    /// Comment to render above the import statement.
+    comment: Option<ItemStr>,
 }
 
 impl ImportModule {
@@ -282,32 +366,76 @@ impl ImportModule {
             ..self
         }
     }
+
+    /// Attach a comment which is rendered above the import statement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let toks = quote! {
+    ///     $(python::import_module("numpy").with_alias("np").with_comment("third party"))
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "# third party",
+    ///         "import numpy as np",
+    ///         "",
+    ///         "np",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_comment<C>(self, comment: C) -> Self
+    where
+        C: Into<ItemStr>,
+    {
+        Self {
+            comment: Some(comment.into()),
+            ..self
+        }
+    }
 }
 
 impl Python {
-    fn imports(out: &mut Tokens, tokens: &Tokens) {
-        let mut imported_from = BTreeMap::new();
-        let mut imports = BTreeSet::new();
+    fn imports(out: &mut Tokens, tokens: &Tokens, config: &Config) {
+        let order = config.import_order;
+        let mut imported_from = ImportMap::new(order);
+        let mut imports = ImportMap::new(order);
 
-        for import in tokens.walk_imports() {
+        for import in import_order::walk_imports(tokens) {
             match import {
                 Any::Import(Import {
                     module,
                     alias,
                     name,
+                    comment,
                 }) => match module {
                     TypeModule::Qualified { module, alias } => {
-                        imports.insert((module, alias));
+                        imports
+                            .get_or_insert_with((module, alias), ImportComments::new)
+                            .insert(comment.as_ref());
                     }
                     TypeModule::Unqualified { module } => {
-                        imported_from
-                            .entry(module)
-                            .or_insert_with(BTreeSet::new)
-                            .insert((name, alias));
+                        let (names, comments) = imported_from.get_or_insert_with(module, || {
+                            (ImportSet::new(order), ImportComments::new())
+                        });
+
+                        names.insert((name, alias));
+                        comments.insert(comment.as_ref());
                     }
                 },
-                Any::ImportModule(ImportModule { module, alias }) => {
-                    imports.insert((module, alias));
+                Any::ImportModule(ImportModule {
+                    module,
+                    alias,
+                    comment,
+                }) => {
+                    imports
+                        .get_or_insert_with((module, alias), ImportComments::new)
+                        .insert(comment.as_ref());
                 }
             }
         }
@@ -316,8 +444,9 @@ impl Python {
             return;
         }
 
-        for (module, imports) in imported_from {
+        for (module, (imports, comments)) in imported_from {
             out.push();
+            comments.write(out, "#");
 
             let imports = imports
                 .into_iter()
@@ -335,8 +464,9 @@ impl Python {
             }
         }
 
-        for (module, alias) in imports {
+        for ((module, alias), comments) in imports {
             out.push();
+            comments.write(out, "#");
 
             quote_in! {*out =>
                 import $module$(if let Some(a) = alias => $[' ']as $a)
@@ -387,6 +517,7 @@ where
         },
         name: name.into(),
         alias: None,
+        comment: None,
     }
 }
 
@@ -421,5 +552,6 @@ where
     ImportModule {
         module: module.into(),
         alias: None,
+        comment: None,
     }
 }
@@ -0,0 +1,230 @@
+//! Specialization for R code generation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let filter = r::import("dplyr", "filter");
+//! let ggplot = r::import("ggplot2", "ggplot");
+//!
+//! let toks: r::Tokens = quote! {
+//!     adults <- $filter(people, age >= 18)
+//!     $ggplot(adults)
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "library(dplyr)",
+//!         "library(ggplot2)",
+//!         "",
+//!         "adults <- filter(people, age >= 18)",
+//!         "ggplot(adults)",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in R
+//!
+//! Strings are double-quoted, and only control characters, backslashes, and
+//! quotes are escaped.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: r::Tokens = quote!("hello \n \"world\" \\ \x1b ÿ");
+//! assert_eq!("\"hello \\n \\\"world\\\" \\\\ \\x1b ÿ\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate as genco;
+use crate::fmt;
+use crate::lang::import_order::{self, ImportOrder, ImportSet};
+use crate::quote_in;
+use crate::tokens::ItemStr;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+/// Tokens container specialization for R.
+pub type Tokens = crate::Tokens<R>;
+
+impl_lang! {
+    /// Language specialization for R.
+    pub R {
+        type Config = Config;
+        type Format = Format;
+        type Item = Import;
+
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn line_comment(out: &mut Tokens, line: &str) {
+            out.append(ItemStr::Static("#"));
+
+            if !line.is_empty() {
+                out.space();
+                out.append(line);
+            }
+        }
+
+        fn ternary(out: &mut Tokens, cond: Tokens, then: Tokens, els: Tokens) {
+            use crate as genco;
+            use crate::quote_in;
+
+            quote_in!(*out => if ($cond) $then else $els);
+        }
+
+        fn default_indentation() -> fmt::Indentation {
+            fmt::Indentation::Space(2)
+        }
+
+        fn concat(out: &mut Tokens, parts: Vec<Tokens>) {
+            quote_in!(*out => paste0($(for part in parts join (, ) => $part)));
+        }
+
+        fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            // From: https://stat.ethz.ch/R-manual/R-devel/library/base/html/Quotes.html
+            for c in input.chars() {
+                match c {
+                    '\n' => out.write_str("\\n")?,
+                    '\r' => out.write_str("\\r")?,
+                    '\t' => out.write_str("\\t")?,
+                    '\\' => out.write_str("\\\\")?,
+                    '"' => out.write_str("\\\"")?,
+                    c if (c as u32) < 0x20 || c == '\x7f' => {
+                        write!(out, "\\x{:02x}", c as u32)?;
+                    }
+                    c if !c.is_ascii() && out.ascii_only() => {
+                        if (c as u32) < 0x10000 {
+                            write!(out, "\\u{{{:04x}}}", c as u32)?;
+                        } else {
+                            write!(out, "\\U{{{:08x}}}", c as u32)?;
+                        }
+                    }
+                    c => out.write_char(c)?,
+                }
+            }
+
+            Ok(())
+        }
+
+        fn format_file(
+            tokens: &Tokens,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut imports = Tokens::new();
+            Self::imports(&mut imports, tokens, config);
+            let format = Format::default();
+            imports.format(out, config, &format)?;
+            tokens.format(out, config, &format)?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+            out.write_str(&self.name)?;
+            Ok(())
+        }
+    }
+}
+
+/// Formatting state for R.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for R.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// The order in which imports are rendered.
+    import_order: ImportOrder,
+}
+
+impl Config {
+    /// Set the order in which imports are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self { import_order }
+    }
+}
+
+/// The import of a name from an R package, which attaches the package through
+/// a `library()` call.
+///
+/// Created through the [import()] function.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Import {
+    /// Package of the imported name.
+    package: ItemStr,
+    /// The name that was imported.
+    name: ItemStr,
+}
+
+impl R {
+    fn imports(out: &mut Tokens, tokens: &Tokens, config: &Config) {
+        let mut packages = ImportSet::new(config.import_order);
+
+        for import in import_order::walk_imports(tokens) {
+            packages.insert(&import.package);
+        }
+
+        if packages.is_empty() {
+            return;
+        }
+
+        for package in packages {
+            out.push();
+            quote_in!(*out => library($package));
+        }
+
+        out.line();
+    }
+}
+
+/// The import of a name from an R package.
+///
+/// Each package is attached once with a `library()` call at the top of the
+/// file, and the name is used unqualified.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: r::Tokens = quote! {
+///     $(r::import("dplyr", "filter"))
+///     $(r::import("dplyr", "mutate"))
+///     $(r::import("tidyr", "pivot_longer"))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "library(dplyr)",
+///         "library(tidyr)",
+///         "",
+///         "filter",
+///         "mutate",
+///         "pivot_longer",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn import<P, N>(package: P, name: N) -> Import
+where
+    P: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    Import {
+        package: package.into(),
+        name: name.into(),
+    }
+}
@@ -39,12 +39,40 @@
 //! # }
 
 use crate::fmt;
-use crate::tokens::ItemStr;
-use std::collections::{BTreeMap, BTreeSet, VecDeque};
-use std::fmt::Write as _;
+use crate::lang::import_order::{self, ImportComments, ImportMap, ImportOrder, ImportSet};
+use crate::lang::Lang;
+use crate::tokens::{Assoc, FormatInto, ItemStr};
+use alloc::borrow::Cow;
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
 
 const SEP: &str = "::";
 
+/// Get the remainder of a module path which is relative to the current
+/// module, like `foo` for `self::foo`.
+fn self_relative(module: &str) -> Option<&str> {
+    match module.strip_prefix("self") {
+        Some("") => Some(""),
+        Some(rest) => rest.strip_prefix(SEP),
+        None => None,
+    }
+}
+
+/// Keywords which can be used as identifiers when escaped as raw identifiers,
+/// like `r#type`.
+///
+/// See <https://doc.rust-lang.org/reference/keywords.html>.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
 /// Tokens container specialization for Rust.
 pub type Tokens = crate::Tokens<Rust>;
 
@@ -55,6 +83,54 @@ impl_lang! {
         type Format = Format;
         type Item = Import;
 
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn ternary(out: &mut Tokens, cond: Tokens, then: Tokens, els: Tokens) {
+            use crate as genco;
+            use crate::quote_in;
+
+            quote_in!(*out => if $cond { $then } else { $els });
+        }
+
+        fn concat(out: &mut Tokens, parts: Vec<Tokens>) {
+            use crate as genco;
+            use crate::quote_in;
+
+            quote_in!(*out => [$(for part in parts join (, ) => $part)].concat());
+        }
+
+        fn escape_ident(name: &str) -> Cow<'_, str> {
+            // Note: keywords like `self` and `crate` can't be used as raw
+            // identifiers, so they are left as-is.
+            if KEYWORDS.contains(&name) {
+                Cow::Owned(format!("r#{name}"))
+            } else {
+                Cow::Borrowed(name)
+            }
+        }
+
+        fn operator_precedence(op: &str) -> Option<(u8, Assoc)> {
+            // Reference: https://doc.rust-lang.org/reference/expressions.html#expression-precedence
+            Some(match op {
+                "*" | "/" | "%" => (12, Assoc::Left),
+                "+" | "-" => (11, Assoc::Left),
+                "<<" | ">>" => (10, Assoc::Left),
+                "&" => (9, Assoc::Left),
+                "^" => (8, Assoc::Left),
+                "|" => (7, Assoc::Left),
+                "==" | "!=" | "<" | ">" | "<=" | ">=" => (6, Assoc::None),
+                "&&" => (5, Assoc::Left),
+                "||" => (4, Assoc::Left),
+                ".." | "..=" => (3, Assoc::None),
+                "=" | "+=" | "-=" | "*=" | "/=" | "%=" | "&=" | "|=" | "^=" | "<<=" | ">>=" => {
+                    (2, Assoc::Right)
+                }
+                _ => return None,
+            })
+        }
+
         fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
             // From: https://doc.rust-lang.org/reference/tokens.html#literals
 
@@ -73,6 +149,9 @@ impl_lang! {
                     // Note: only relevant if we were to use single-quoted strings.
                     // '\'' => out.write_str("\\'")?,
                     '"' => out.write_str("\\\"")?,
+                    c if !c.is_ascii() && out.ascii_only() => {
+                        write!(out, "\\u{{{:04x}}}", c as u32)?;
+                    }
                     c if !c.is_control() => out.write_char(c)?,
                     c if (c as u32) < 0x80 => {
                         write!(out, "\\x{:02x}", c as u32)?;
@@ -92,9 +171,9 @@ impl_lang! {
             config: &Self::Config,
         ) -> fmt::Result {
             let mut imports: Tokens = Tokens::new();
-            Self::imports(&mut imports, config, tokens);
+            let mut format = Format::default();
+            Self::imports(&mut imports, config, tokens, &mut format);
 
-            let format = Format::default();
             imports.format(out, config, &format)?;
             tokens.format(out, config, &format)?;
             Ok(())
@@ -102,33 +181,27 @@ impl_lang! {
     }
 
     Import {
-        fn format(&self, out: &mut fmt::Formatter<'_>, config: &Config, _: &Format) -> fmt::Result {
+        fn format(&self, out: &mut fmt::Formatter<'_>, config: &Config, format: &Format) -> fmt::Result {
             match &self.module {
-                Module::Module {
-                    import: Some(ImportMode::Direct),
-                    ..
-                } => {
-                    self.write_direct(out)?;
-                }
-                Module::Module {
-                    import: Some(ImportMode::Qualified),
-                    module,
-                } => {
-                    self.write_prefixed(out, module)?;
+                Module::Module { import, module } => {
+                    let module = config.module(module);
+
+                    match (self_relative(&module), import.unwrap_or(config.default_import)) {
+                        (Some(rest), _) if self.alias.is_none() => {
+                            self.write_relative(out, rest)?;
+                        }
+                        (_, ImportMode::Direct) => {
+                            self.write_direct(out, format.alias(&module, &self.name))?
+                        }
+                        (_, ImportMode::Qualified) => self.write_prefixed(out, &module)?,
+                    }
                 }
-                Module::Module {
-                    import: None,
-                    module,
-                } => match &config.default_import {
-                    ImportMode::Direct => self.write_direct(out)?,
-                    ImportMode::Qualified => self.write_prefixed(out, module)?,
-                },
                 Module::Aliased {
                     alias: ref module, ..
                 } => {
                     out.write_str(module)?;
                     out.write_str(SEP)?;
-                    out.write_str(&self.name)?;
+                    out.write_str(&Rust::escape_ident(&self.name))?;
                 }
             }
 
@@ -139,12 +212,26 @@ impl_lang! {
 
 /// Format state for Rust.
 #[derive(Debug, Default)]
-pub struct Format {}
+pub struct Format {
+    /// Aliases assigned to directly imported names which would otherwise
+    /// conflict with the same name imported from another module, keyed by
+    /// module path and name.
+    aliases: BTreeMap<String, BTreeMap<ItemStr, ItemStr>>,
+}
+
+impl Format {
+    /// Get the alias assigned to the given name in the given module, if any.
+    fn alias(&self, module: &str, name: &ItemStr) -> Option<&ItemStr> {
+        self.aliases.get(module)?.get(name)
+    }
+}
 
 /// Language configuration for Rust.
 #[derive(Debug)]
 pub struct Config {
     default_import: ImportMode,
+    module_prefix: Option<(ItemStr, ItemStr)>,
+    import_order: ImportOrder,
 }
 
 impl Config {
@@ -152,7 +239,117 @@ impl Config {
     ///
     /// See [Import] for more details.
     pub fn with_default_import(self, default_import: ImportMode) -> Self {
-        Self { default_import }
+        Self {
+            default_import,
+            ..self
+        }
+    }
+
+    /// Rewrite the module prefix `from` to `to` when rendering imports.
+    ///
+    /// This only affects how the tokens are formatted, so the same tokens can
+    /// be written for different targets. A prefix only matches whole path
+    /// segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let toks: rust::Tokens = quote! {
+    ///     $(rust::import("crate::foo", "Bar"))
+    ///     $(rust::import("crate::foo", "Baz").qualified())
+    ///     $(rust::import("crates::foo", "Baz"))
+    /// };
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>();
+    /// let config = rust::Config::default().with_module_prefix("crate", "my_crate");
+    ///
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "use crates::foo::Baz;",
+    ///         "use my_crate::foo::{self, Bar};",
+    ///         "",
+    ///         "Bar",
+    ///         "foo::Baz",
+    ///         "Baz",
+    ///     ],
+    ///     w.into_vec(),
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_module_prefix<F, T>(self, from: F, to: T) -> Self
+    where
+        F: Into<ItemStr>,
+        T: Into<ItemStr>,
+    {
+        Self {
+            module_prefix: Some((from.into(), to.into())),
+            ..self
+        }
+    }
+
+    /// Set the order in which `use` declarations are rendered.
+    ///
+    /// By default they are sorted by module. With
+    /// [ImportOrder::AsEncountered] they are rendered in the order in which
+    /// they are first referenced in the tokens instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    /// use genco::lang::ImportOrder;
+    ///
+    /// let toks: rust::Tokens = quote! {
+    ///     $(rust::import("std::fmt", "Debug"))
+    ///     $(rust::import("std::collections", "HashMap"))
+    ///     $(rust::import("std::fmt", "Display"))
+    /// };
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>();
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// let config = rust::Config::default().with_import_order(ImportOrder::AsEncountered);
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "use std::fmt::{Debug, Display};",
+    ///         "use std::collections::HashMap;",
+    ///         "",
+    ///         "Debug",
+    ///         "HashMap",
+    ///         "Display",
+    ///     ],
+    ///     w.into_vec(),
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self {
+            import_order,
+            ..self
+        }
+    }
+
+    /// Apply the configured module prefix rewrite to the given module.
+    fn module<'a>(&self, module: &'a str) -> Cow<'a, str> {
+        if let Some((from, to)) = &self.module_prefix {
+            if let Some(rest) = module.strip_prefix(&**from) {
+                if rest.is_empty() || rest.starts_with(SEP) {
+                    return Cow::Owned(format!("{}{}", to, rest));
+                }
+            }
+        }
+
+        Cow::Borrowed(module)
     }
 }
 
@@ -160,12 +357,15 @@ impl Default for Config {
     fn default() -> Self {
         Config {
             default_import: ImportMode::Direct,
+            module_prefix: None,
+            import_order: ImportOrder::default(),
         }
     }
 }
 
 /// The import mode to use when generating import statements.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImportMode {
     /// Import names without a module prefix.
     ///
@@ -180,6 +380,7 @@ pub enum ImportMode {
 }
 
 #[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum Module {
     /// Type imported directly from module with the specified mode.
     Module {
@@ -249,6 +450,7 @@ impl Module {
 ///
 /// Created through the [import()] function.
 #[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Import {
     /// How the type is imported.
     module: Module,
@@ -256,6 +458,8 @@ pub struct Import {
     name: ItemStr,
     /// Alias to use for the type.
     alias: Option<ItemStr>,
+    /// Comment to render above the `use` declaration.
+    comment: Option<ItemStr>,
 }
 
 impl Import {
@@ -385,85 +589,209 @@ impl Import {
         }
     }
 
-    /// Write the direct name of the type.
-    fn write_direct(&self, out: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some(alias) = &self.alias {
-            out.write_str(alias)
+    /// Attach a comment which is rendered above the `use` declaration of the
+    /// module.
+    ///
+    /// Comments stay with their import when imports are sorted. If several
+    /// imports of the same module have comments, each distinct comment is
+    /// rendered once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let value = rust::import("serde_json", "Value").with_comment("external deps");
+    /// let map = rust::import("std::collections", "HashMap");
+    /// let to_value = rust::import("serde_json", "to_value");
+    ///
+    /// let toks = quote! {
+    ///     let m: $(&map)<String, $value> = $map::new();
+    ///     let v = $to_value(&m);
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "// external deps",
+    ///         "use serde_json::{Value, to_value};",
+    ///         "use std::collections::HashMap;",
+    ///         "",
+    ///         "let m: HashMap<String, Value> = HashMap::new();",
+    ///         "let v = to_value(&m);",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_comment<C>(self, comment: C) -> Self
+    where
+        C: Into<ItemStr>,
+    {
+        Self {
+            comment: Some(comment.into()),
+            ..self
+        }
+    }
+
+    /// Write the direct name of the type, using the alias assigned to it to
+    /// resolve a conflict unless it has an explicit alias.
+    fn write_direct(
+        &self,
+        out: &mut fmt::Formatter<'_>,
+        conflict: Option<&ItemStr>,
+    ) -> fmt::Result {
+        if let Some(alias) = self.alias.as_ref().or(conflict) {
+            out.write_str(&Rust::escape_ident(alias))
         } else {
-            out.write_str(&self.name)
+            out.write_str(&Rust::escape_ident(&self.name))
+        }
+    }
+
+    /// Write the name of the type relative to the current module.
+    fn write_relative(&self, out: &mut fmt::Formatter<'_>, module: &str) -> fmt::Result {
+        if !module.is_empty() {
+            out.write_str(module)?;
+            out.write_str(SEP)?;
         }
+
+        out.write_str(&Rust::escape_ident(&self.name))?;
+        Ok(())
     }
 
     /// Write the prefixed name of the type.
-    fn write_prefixed(&self, out: &mut fmt::Formatter<'_>, module: &ItemStr) -> fmt::Result {
+    fn write_prefixed(&self, out: &mut fmt::Formatter<'_>, module: &str) -> fmt::Result {
         if let Some(module) = module.rsplit(SEP).next() {
             out.write_str(module)?;
             out.write_str(SEP)?;
         }
 
-        out.write_str(&self.name)?;
+        out.write_str(&Rust::escape_ident(&self.name))?;
         Ok(())
     }
 }
 
 impl Rust {
-    fn imports(out: &mut Tokens, config: &Config, tokens: &Tokens) {
+    /// Assign aliases to directly imported names which conflict with the
+    /// same name imported from another module.
+    ///
+    /// Explicit aliases are reserved first, after which the first import of a
+    /// name keeps it and later ones are aliased as `Name2`, `Name3`, etc.
+    fn resolve_conflicts(config: &Config, tokens: &Tokens, format: &mut Format) {
+        let mut bound = BTreeMap::<ItemStr, (Cow<'_, str>, &ItemStr)>::new();
+
+        for explicit in [true, false] {
+            for import in import_order::walk_imports(tokens) {
+                let (mode, module) = match &import.module {
+                    Module::Module { import, module } => (import, module),
+                    Module::Aliased { .. } => continue,
+                };
+
+                let path = config.module(module);
+
+                if mode.unwrap_or(config.default_import) != ImportMode::Direct
+                    || (self_relative(&path).is_some() && import.alias.is_none())
+                {
+                    continue;
+                }
+
+                match (&import.alias, explicit) {
+                    (Some(alias), true) => {
+                        bound.entry(alias.clone()).or_insert((path, &import.name));
+                    }
+                    (None, false) => {
+                        match bound.get(&import.name) {
+                            None => {
+                                bound.insert(import.name.clone(), (path, &import.name));
+                                continue;
+                            }
+                            Some((p, n)) if *p == path && *n == &import.name => continue,
+                            Some(..) => {}
+                        }
+
+                        if format.alias(&path, &import.name).is_some() {
+                            continue;
+                        }
+
+                        let mut n = 2;
+
+                        let alias = loop {
+                            let alias = ItemStr::from(format!("{}{n}", import.name));
+
+                            if !bound.contains_key(&alias) {
+                                break alias;
+                            }
+
+                            n += 1;
+                        };
+
+                        format
+                            .aliases
+                            .entry(path.clone().into_owned())
+                            .or_default()
+                            .insert(import.name.clone(), alias.clone());
+                        bound.insert(alias, (path, &import.name));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn imports(out: &mut Tokens, config: &Config, tokens: &Tokens, format: &mut Format) {
         use crate as genco;
         use crate::quote_in;
-        use std::collections::btree_set;
-
-        let mut modules = BTreeMap::<&ItemStr, Import>::new();
+        Self::resolve_conflicts(config, tokens, format);
+        let format = &*format;
+        let order = config.import_order;
+        let mut modules = ImportMap::<Cow<'_, str>, Import>::new(order);
 
         let mut queue = VecDeque::new();
 
-        for import in tokens.walk_imports() {
+        for import in import_order::walk_imports(tokens) {
             queue.push_back(import);
         }
 
         while let Some(import) = queue.pop_front() {
+            let (Module::Module { module, .. } | Module::Aliased { module, .. }) = &import.module;
+            let path = config.module(module);
+            // NB: names relative to the current module are already in scope,
+            // so they only need a `use` declaration if they are aliased.
+            let in_scope = self_relative(&path).is_some();
+            let conflict = format.alias(&path, &import.name);
+            let module = modules.get_or_insert_with(path, || Import::new(order));
+
             match &import.module {
-                Module::Module {
-                    module,
-                    import: Some(ImportMode::Direct),
-                } => {
-                    let module = modules.entry(module).or_default();
-                    module.names.insert((&import.name, import.alias.as_ref()));
-                }
-                Module::Module {
-                    module,
-                    import: Some(ImportMode::Qualified),
-                } => {
-                    let module = modules.entry(module).or_default();
-                    module.self_import = true;
-                }
-                Module::Module {
-                    module,
-                    import: None,
-                } => match config.default_import {
-                    ImportMode::Direct => {
-                        let module = modules.entry(module).or_default();
-                        module.names.insert((&import.name, import.alias.as_ref()));
-                    }
-                    ImportMode::Qualified => {
-                        let module = modules.entry(module).or_default();
-                        module.self_import = true;
+                Module::Module { import: mode, .. } => {
+                    match mode.unwrap_or(config.default_import) {
+                        ImportMode::Direct if in_scope && import.alias.is_none() => continue,
+                        ImportMode::Direct => {
+                            let alias = import.alias.as_ref().or(conflict);
+                            module.names.insert((&import.name, alias));
+                        }
+                        ImportMode::Qualified if in_scope => continue,
+                        ImportMode::Qualified => {
+                            module.self_import = true;
+                        }
                     }
-                },
-                Module::Aliased { module, alias } => {
-                    let module = modules.entry(module).or_default();
+                }
+                Module::Aliased { alias, .. } => {
                     module.self_aliases.insert(alias);
                 }
             }
+
+            module.comments.insert(import.comment.as_ref());
         }
 
         let mut has_any = false;
 
         for (m, module) in modules {
-            let mut render = module.iter(m);
+            let m = &*m;
+            let (comments, mut render) = module.iter(m);
 
             if let Some(first) = render.next() {
                 has_any = true;
                 out.push();
+                comments.write(out, "//");
 
                 // render as a group if there's more than one thing being
                 // imported.
@@ -490,10 +818,10 @@ impl Rust {
                             name,
                             alias: Some(alias),
                         } => {
-                            quote_in!(*out => use $m::$name as $alias;);
+                            quote_in!(*out => use $m::$(local(name)) as $(local(alias)););
                         }
                         RenderItem::Name { name, alias: None } => {
-                            quote_in!(*out => use $m::$name;);
+                            quote_in!(*out => use $m::$(local(name)););
                         }
                     }
                 }
@@ -507,39 +835,52 @@ impl Rust {
         return;
 
         /// An imported module.
-        #[derive(Debug, Default)]
+        #[derive(Debug)]
         struct Import<'a> {
             /// If we need the module (e.g. through an alias).
             self_import: bool,
             /// Aliases for the own module.
-            self_aliases: BTreeSet<&'a ItemStr>,
+            self_aliases: ImportSet<&'a ItemStr>,
             /// Set of imported names.
-            names: BTreeSet<(&'a ItemStr, Option<&'a ItemStr>)>,
+            names: ImportSet<(&'a ItemStr, Option<&'a ItemStr>)>,
+            /// Comments to render above the declaration.
+            comments: ImportComments<'a>,
         }
 
         impl<'a> Import<'a> {
-            fn iter(self, module: &'a str) -> ImportedIter<'a> {
-                ImportedIter {
+            fn new(order: ImportOrder) -> Self {
+                Self {
+                    self_import: false,
+                    self_aliases: ImportSet::new(order),
+                    names: ImportSet::new(order),
+                    comments: ImportComments::new(),
+                }
+            }
+
+            fn iter(self, module: &'a str) -> (ImportComments<'a>, ImportedIter<'a>) {
+                let iter = ImportedIter {
                     module,
                     self_import: self.self_import,
                     self_aliases: self.self_aliases.into_iter(),
                     names: self.names.into_iter(),
-                }
+                };
+
+                (self.comments, iter)
             }
         }
 
         struct ImportedIter<'a> {
             module: &'a str,
             self_import: bool,
-            self_aliases: btree_set::IntoIter<&'a ItemStr>,
-            names: btree_set::IntoIter<(&'a ItemStr, Option<&'a ItemStr>)>,
+            self_aliases: <ImportSet<&'a ItemStr> as IntoIterator>::IntoIter,
+            names: <ImportSet<(&'a ItemStr, Option<&'a ItemStr>)> as IntoIterator>::IntoIter,
         }
 
         impl<'a> Iterator for ImportedIter<'a> {
             type Item = RenderItem<'a>;
 
             fn next(&mut self) -> Option<Self::Item> {
-                if std::mem::take(&mut self.self_import) {
+                if core::mem::take(&mut self.self_import) {
                     // Only render self-import if it's not a top level module.
                     if self.module.split(SEP).count() > 1 {
                         return Some(RenderItem::SelfImport);
@@ -583,10 +924,10 @@ impl Rust {
                         name,
                         alias: Some(alias),
                     } => {
-                        quote_in!(*out => $name as $alias);
+                        quote_in!(*out => $(local(name)) as $(local(alias)));
                     }
                     Self::Name { name, alias: None } => {
-                        quote_in!(*out => $name);
+                        quote_in!(*out => $(local(name)));
                     }
                 }
             }
@@ -695,6 +1036,71 @@ impl Rust {
 /// );
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
+///
+/// # Example with conflicting names
+///
+/// If the same name is imported directly from different modules, imports
+/// after the first are automatically aliased.
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let a = rust::import("std::fmt", "Result");
+/// let b = rust::import("std::io", "Result");
+///
+/// let toks = quote!{
+///     $a
+///     $b
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use std::fmt::Result;",
+///         "use std::io::Result as Result2;",
+///         "",
+///         "Result",
+///         "Result2",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// # Example with relative paths
+///
+/// Modules can start with `crate`, `super`, or `self`. Since anything
+/// relative to `self` is already in scope it's referenced by its path without
+/// a `use` declaration, unless it's aliased.
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let a = rust::import("crate::foo", "Bar");
+/// let b = rust::import("super::foo", "Baz").qualified();
+/// let c = rust::import("self::error", "Error");
+/// let d = rust::import("self", "Config");
+///
+/// let toks = quote!{
+///     $a
+///     $b
+///     $c
+///     $d
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use crate::foo::Bar;",
+///         "use super::foo;",
+///         "",
+///         "Bar",
+///         "foo::Baz",
+///         "error::Error",
+///         "Config",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
 pub fn import<M, N>(module: M, name: N) -> Import
 where
     M: Into<ItemStr>,
@@ -707,5 +1113,1087 @@ where
         },
         name: name.into(),
         alias: None,
+        comment: None,
     }
 }
+
+/// A local name, like a variable, field, or function, which is escaped as a
+/// raw identifier if it collides with a keyword.
+///
+/// Created through the [local()] function.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Local {
+    name: ItemStr,
+}
+
+impl FormatInto<Rust> for Local {
+    fn format_into(self, tokens: &mut Tokens) {
+        match Rust::escape_ident(&self.name) {
+            Cow::Borrowed(_) => tokens.append(self.name),
+            Cow::Owned(name) => tokens.append(name),
+        }
+    }
+}
+
+/// Construct a local name, which is escaped as a [raw identifier] if it
+/// collides with a keyword.
+///
+/// [raw identifier]: https://doc.rust-lang.org/reference/identifiers.html#raw-identifiers
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let name = rust::local("match");
+/// let ty = rust::import("std::fmt", "Debug");
+///
+/// let toks = quote! {
+///     fn $name(value: &dyn $ty) {}
+///     fn $(rust::local("matches"))() {}
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use std::fmt::Debug;",
+///         "",
+///         "fn r#match(value: &dyn Debug) {}",
+///         "fn matches() {}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn local<N>(name: N) -> Local
+where
+    N: Into<ItemStr>,
+{
+    Local { name: name.into() }
+}
+
+/// The maximum width of a `where` clause before it is broken up over multiple
+/// lines by [Bounds].
+const BOUNDS_MAX_WIDTH: usize = 60;
+
+/// A builder for a `where` clause, like `where T: Clone + Debug`.
+///
+/// Each type added is rendered with its bounds joined by `+`, and types are
+/// separated by `,`. If the clause gets too long, it's broken up with one type
+/// per line the same way rustfmt would do it.
+///
+/// Since the clause decides what whitespace should precede the item following
+/// it, it should be interpolated directly before the opening brace like
+/// `$bounds{`. Nothing is rendered if no bounds have been added.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let debug = rust::import("std::fmt", "Debug");
+///
+/// let bounds = rust::Bounds::new()
+///     .with("T", ["Clone", "Send"])
+///     .with("U", [debug]);
+///
+/// let toks = quote! {
+///     impl<T, U> Foo for Bar<T, U> $bounds{
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use std::fmt::Debug;",
+///         "",
+///         "impl<T, U> Foo for Bar<T, U> where T: Clone + Send, U: Debug {",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// Long clauses are broken up over multiple lines:
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let mut bounds = rust::Bounds::new();
+/// bounds.push("Request", ["Clone", "Send", "Sync", "'static"]);
+/// bounds.push("Response", ["Default", "PartialEq", "Eq"]);
+///
+/// let toks = quote! {
+///     fn handle<Request, Response>() $bounds{
+///         todo!()
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "fn handle<Request, Response>()",
+///         "where",
+///         "    Request: Clone + Send + Sync + 'static,",
+///         "    Response: Default + PartialEq + Eq,",
+///         "{",
+///         "    todo!()",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Bounds {
+    items: Vec<(Tokens, Vec<Tokens>)>,
+}
+
+impl Bounds {
+    /// Construct an empty set of bounds.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Test if no bounds have been added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let bounds = rust::Bounds::new();
+    /// assert!(bounds.is_empty());
+    /// assert!(!bounds.clone().with("T", ["Clone"]).is_empty());
+    ///
+    /// let toks: rust::Tokens = quote!(impl Foo $bounds{});
+    /// assert_eq!("impl Foo {}", toks.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Add bounds for the given type.
+    pub fn push<T, B>(&mut self, ty: T, bounds: B)
+    where
+        T: FormatInto<Rust>,
+        B: IntoIterator,
+        B::Item: FormatInto<Rust>,
+    {
+        let mut t = Tokens::new();
+        t.append(ty);
+
+        let bounds = bounds
+            .into_iter()
+            .map(|bound| {
+                let mut t = Tokens::new();
+                t.append(bound);
+                t
+            })
+            .collect();
+
+        self.items.push((t, bounds));
+    }
+
+    /// Add bounds for the given type, returning the modified builder.
+    pub fn with<T, B>(mut self, ty: T, bounds: B) -> Self
+    where
+        T: FormatInto<Rust>,
+        B: IntoIterator,
+        B::Item: FormatInto<Rust>,
+    {
+        self.push(ty, bounds);
+        self
+    }
+
+    /// Estimate the width of the clause if it was rendered on a single line.
+    fn width(&self) -> usize {
+        fn len(tokens: &Tokens) -> usize {
+            tokens.to_string().map(|s| s.len()).unwrap_or_default()
+        }
+
+        let mut width = "where".len();
+
+        for (ty, bounds) in &self.items {
+            // Leading space or `, ` separator, followed by `: `.
+            width += 2 + len(ty) + 2;
+            width += bounds.iter().map(len).sum::<usize>();
+            width += bounds.len().saturating_sub(1) * " + ".len();
+        }
+
+        width
+    }
+}
+
+impl FormatInto<Rust> for Bounds {
+    fn format_into(self, tokens: &mut Tokens) {
+        use crate as genco;
+        use crate::quote_in;
+
+        if self.items.is_empty() {
+            return;
+        }
+
+        if self.width() <= BOUNDS_MAX_WIDTH {
+            quote_in! { *tokens =>
+                where $(for (ty, bounds) in self.items join (, ) =>
+                    $ty: $(for b in bounds join ( + ) => $b)
+                )$[' ']
+            }
+
+            return;
+        }
+
+        tokens.push();
+        quote_in!(*tokens => where);
+        tokens.indent();
+
+        for (ty, bounds) in self.items {
+            tokens.push();
+            quote_in!(*tokens => $ty: $(for b in bounds join ( + ) => $b),);
+        }
+
+        tokens.unindent();
+        tokens.push();
+    }
+}
+
+/// A lifetime, like `'a`.
+///
+/// Created through the [lifetime()] function.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lifetime {
+    name: ItemStr,
+}
+
+impl FormatInto<Rust> for Lifetime {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(ItemStr::Static("'"));
+        tokens.append(self.name);
+    }
+}
+
+impl FormatInto<Rust> for &Lifetime {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(self.clone());
+    }
+}
+
+/// Construct a lifetime, like `'a`.
+///
+/// The leading `'` is optional.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: rust::Tokens = quote!(&$(rust::lifetime("a")) str, &$(rust::lifetime("'static")) str);
+/// assert_eq!("&'a str, &'static str", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn lifetime<N>(name: N) -> Lifetime
+where
+    N: Into<ItemStr>,
+{
+    let name = name.into();
+
+    let name = match name.strip_prefix('\'') {
+        Some(rest) => ItemStr::from(rest),
+        None => name,
+    };
+
+    Lifetime { name }
+}
+
+/// A builder for a list of generic parameters, like `<'a, T: Clone>`.
+///
+/// Lifetimes are always rendered before type parameters. Formatting the
+/// builder renders the declaration of the parameters including their bounds,
+/// while [Generics::arguments] renders them as arguments without bounds, so
+/// that they can be used as `impl$(&generics) Foo$(generics.arguments())`.
+/// Nothing is rendered if no parameters have been added.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let debug = rust::import("std::fmt", "Debug");
+///
+/// let generics = rust::Generics::new()
+///     .with_lifetime("a")
+///     .with("T", [quote!(Clone), quote!($debug)])
+///     .with("U", [] as [&str; 0]);
+///
+/// let toks = quote! {
+///     impl$(&generics) Foo$(generics.arguments()) {
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use std::fmt::Debug;",
+///         "",
+///         "impl<'a, T: Clone + Debug, U> Foo<'a, T, U> {",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Generics {
+    lifetimes: Vec<Lifetime>,
+    params: Vec<(Tokens, Vec<Tokens>)>,
+}
+
+impl Generics {
+    /// Construct an empty list of generic parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Test if no parameters have been added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let generics = rust::Generics::new();
+    /// assert!(generics.is_empty());
+    /// assert!(!generics.clone().with_lifetime("a").is_empty());
+    ///
+    /// let toks: rust::Tokens = quote!(struct Foo$(&generics)(u32););
+    /// assert_eq!("struct Foo(u32);", toks.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.lifetimes.is_empty() && self.params.is_empty()
+    }
+
+    /// Add a lifetime parameter.
+    pub fn push_lifetime<N>(&mut self, name: N)
+    where
+        N: Into<ItemStr>,
+    {
+        self.lifetimes.push(lifetime(name));
+    }
+
+    /// Add a lifetime parameter, returning the modified builder.
+    pub fn with_lifetime<N>(mut self, name: N) -> Self
+    where
+        N: Into<ItemStr>,
+    {
+        self.push_lifetime(name);
+        self
+    }
+
+    /// Add a type parameter with the given bounds.
+    pub fn push<T, B>(&mut self, ty: T, bounds: B)
+    where
+        T: FormatInto<Rust>,
+        B: IntoIterator,
+        B::Item: FormatInto<Rust>,
+    {
+        let mut t = Tokens::new();
+        t.append(ty);
+
+        let bounds = bounds
+            .into_iter()
+            .map(|bound| {
+                let mut t = Tokens::new();
+                t.append(bound);
+                t
+            })
+            .collect();
+
+        self.params.push((t, bounds));
+    }
+
+    /// Add a type parameter with the given bounds, returning the modified
+    /// builder.
+    pub fn with<T, B>(mut self, ty: T, bounds: B) -> Self
+    where
+        T: FormatInto<Rust>,
+        B: IntoIterator,
+        B::Item: FormatInto<Rust>,
+    {
+        self.push(ty, bounds);
+        self
+    }
+
+    /// Render the parameters as arguments without their bounds, like
+    /// `<'a, T>`.
+    pub fn arguments(&self) -> GenericArguments<'_> {
+        GenericArguments { generics: self }
+    }
+}
+
+impl FormatInto<Rust> for Generics {
+    fn format_into(self, tokens: &mut Tokens) {
+        use crate as genco;
+        use crate::quote_in;
+
+        if self.is_empty() {
+            return;
+        }
+
+        let sep = !self.lifetimes.is_empty() && !self.params.is_empty();
+
+        quote_in! { *tokens =>
+            <$(for l in self.lifetimes join (, ) => $l)$(if sep => ,$[' '])$(for (ty, bounds) in self.params join (, ) =>
+                $ty$(if !bounds.is_empty() => : $(for b in bounds join ( + ) => $b))
+            )>
+        }
+    }
+}
+
+impl FormatInto<Rust> for &Generics {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(self.clone());
+    }
+}
+
+/// Generic parameters rendered as arguments, like `<'a, T>`.
+///
+/// Created through [Generics::arguments].
+#[derive(Debug, Clone, Copy)]
+pub struct GenericArguments<'a> {
+    generics: &'a Generics,
+}
+
+impl FormatInto<Rust> for GenericArguments<'_> {
+    fn format_into(self, tokens: &mut Tokens) {
+        use crate as genco;
+        use crate::quote_in;
+
+        if self.generics.is_empty() {
+            return;
+        }
+
+        let generics = self.generics;
+        let sep = !generics.lifetimes.is_empty() && !generics.params.is_empty();
+
+        quote_in! { *tokens =>
+            <$(for l in &generics.lifetimes join (, ) => $l)$(if sep => ,$[' '])$(for (ty, _) in &generics.params join (, ) => $ty)>
+        }
+    }
+}
+
+/// An attribute, like `#[derive(Debug)]`.
+///
+/// Attributes are always put on their own line.
+///
+/// Created through the [attr()], [derive()], and [cfg()] functions.
+#[derive(Debug, Clone)]
+pub struct Attribute {
+    content: Tokens,
+}
+
+impl FormatInto<Rust> for Attribute {
+    fn format_into(self, tokens: &mut Tokens) {
+        use crate as genco;
+        use crate::quote_in;
+
+        tokens.push();
+        quote_in!(*tokens => #[$(self.content)]);
+        tokens.push();
+    }
+}
+
+/// Construct an attribute with the given content, like `#[<content>]`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: rust::Tokens = quote! {
+///     $(rust::attr("inline"))
+///     fn foo() {}
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "#[inline]",
+///         "fn foo() {}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn attr<T>(content: T) -> Attribute
+where
+    T: FormatInto<Rust>,
+{
+    let mut t = Tokens::new();
+    t.append(content);
+    Attribute { content: t }
+}
+
+/// Construct a derive attribute, like `#[derive(Clone, Debug)]`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let serialize = rust::import("serde", "Serialize");
+///
+/// let toks: rust::Tokens = quote! {
+///     $(rust::derive(["Clone", "Debug"]))
+///     $(rust::derive([serialize]))
+///     struct Foo {
+///         value: u32,
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use serde::Serialize;",
+///         "",
+///         "#[derive(Clone, Debug)]",
+///         "#[derive(Serialize)]",
+///         "struct Foo {",
+///         "    value: u32,",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn derive<I>(traits: I) -> Attribute
+where
+    I: IntoIterator,
+    I::Item: FormatInto<Rust>,
+{
+    use crate as genco;
+    use crate::quote;
+
+    let traits = traits.into_iter();
+    attr(quote!(derive($(for t in traits join (, ) => $t))))
+}
+
+/// Construct a conditional compilation attribute, like `#[cfg(<predicate>)]`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: rust::Tokens = quote! {
+///     $(rust::cfg("feature = \"std\""))
+///     mod std_support;
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "#[cfg(feature = \"std\")]",
+///         "mod std_support;",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn cfg<T>(predicate: T) -> Attribute
+where
+    T: FormatInto<Rust>,
+{
+    use crate as genco;
+    use crate::quote;
+
+    attr(quote!(cfg($predicate)))
+}
+
+/// A byte-string literal, like `b"\x00abc"`.
+///
+/// Created through the [byte_string()] function.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteString {
+    bytes: Vec<u8>,
+}
+
+impl FormatInto<Rust> for ByteString {
+    fn format_into(self, tokens: &mut Tokens) {
+        let mut out = String::with_capacity(self.bytes.len() + 3);
+        out.push_str("b\"");
+
+        for b in self.bytes {
+            match b {
+                b'"' => out.push_str("\\\""),
+                b'\\' => out.push_str("\\\\"),
+                b'\n' => out.push_str("\\n"),
+                b'\r' => out.push_str("\\r"),
+                b'\t' => out.push_str("\\t"),
+                b' '..=b'~' => out.push(b as char),
+                b => {
+                    let _ = write!(out, "\\x{:02x}", b);
+                }
+            }
+        }
+
+        out.push('"');
+        tokens.append(ItemStr::from(out));
+    }
+}
+
+/// Construct a [byte-string literal], like `b"\x00abc"`.
+///
+/// Bytes outside of printable ASCII are escaped as `\xNN`, while `"` and `\`
+/// are escaped with a backslash. Newlines, carriage returns, and tabs use
+/// their readable `\n`, `\r`, and `\t` escapes.
+///
+/// [byte-string literal]: https://doc.rust-lang.org/reference/tokens.html#byte-string-literals
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let blob = rust::byte_string(&[0, 1, b'a', b'"', b'\\', b'\n', 0xff]);
+///
+/// let toks: rust::Tokens = quote!(const BLOB: &[u8] = $blob;);
+///
+/// assert_eq!(
+///     r#"const BLOB: &[u8] = b"\x00\x01a\"\\\n\xff";"#,
+///     toks.to_string()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn byte_string(bytes: &[u8]) -> ByteString {
+    ByteString {
+        bytes: bytes.to_vec(),
+    }
+}
+
+/// A `match` expression.
+///
+/// Created through the [match_expr()] function.
+#[derive(Debug, Clone)]
+pub struct Match {
+    scrutinee: Tokens,
+    arms: Vec<(Tokens, Tokens)>,
+}
+
+impl FormatInto<Rust> for Match {
+    fn format_into(self, tokens: &mut Tokens) {
+        use crate as genco;
+        use crate::quote_in;
+
+        quote_in! { *tokens =>
+            match $(self.scrutinee) {
+                $(for (pattern, body) in self.arms join ($['\r']) =>
+                    $pattern => {
+                        $body
+                    },
+                )
+            }
+        }
+    }
+}
+
+impl FormatInto<Rust> for &Match {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(self.clone());
+    }
+}
+
+/// Construct a `match` expression over `scrutinee` out of `(pattern, body)`
+/// arms.
+///
+/// Each arm is rendered on its own lines with its body in a block, followed
+/// by a trailing comma.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let ordering = rust::import("std::cmp", "Ordering");
+///
+/// let arms = [
+///     (quote!($(&ordering)::Less), quote!(-1)),
+///     (quote!($(&ordering)::Equal), quote!(0)),
+///     (quote!($(&ordering)::Greater), quote!(1)),
+/// ];
+///
+/// let toks: rust::Tokens = quote! {
+///     fn sign(value: $ordering) -> i32 {
+///         $(rust::match_expr("value", arms))
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use std::cmp::Ordering;",
+///         "",
+///         "fn sign(value: Ordering) -> i32 {",
+///         "    match value {",
+///         "        Ordering::Less => {",
+///         "            -1",
+///         "        },",
+///         "        Ordering::Equal => {",
+///         "            0",
+///         "        },",
+///         "        Ordering::Greater => {",
+///         "            1",
+///         "        },",
+///         "    }",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn match_expr<S, I, P, B>(scrutinee: S, arms: I) -> Match
+where
+    S: FormatInto<Rust>,
+    I: IntoIterator<Item = (P, B)>,
+    P: FormatInto<Rust>,
+    B: FormatInto<Rust>,
+{
+    let mut t = Tokens::new();
+    t.append(scrutinee);
+
+    Match {
+        scrutinee: t,
+        arms: arms
+            .into_iter()
+            .map(|(pattern, body)| {
+                let mut p = Tokens::new();
+                p.append(pattern);
+                let mut b = Tokens::new();
+                b.append(body);
+                (p, b)
+            })
+            .collect(),
+    }
+}
+
+/// An `impl` block, like `impl Trait for Type { .. }`.
+///
+/// Created through the [impl_block()] function.
+#[derive(Debug, Clone)]
+pub struct ImplBlock {
+    generics: Generics,
+    trait_: Option<Tokens>,
+    for_type: Tokens,
+    body: Tokens,
+}
+
+impl ImplBlock {
+    /// Set the generic parameters of the block, like the `<T: Clone>` in
+    /// `impl<T: Clone> Foo<T>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let generics = rust::Generics::new().with("T", [quote!(Clone)]);
+    /// let ty = quote!(Wrapper$(generics.arguments()));
+    ///
+    /// let toks: rust::Tokens = quote! {
+    ///     $(rust::impl_block(Some(quote!(Clone)), ty, quote!(fn clone(&self) -> Self { todo!() })).with_generics(generics))
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "impl<T: Clone> Clone for Wrapper<T> {",
+    ///         "    fn clone(&self) -> Self { todo!() }",
+    ///         "}",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_generics(self, generics: Generics) -> Self {
+        Self { generics, ..self }
+    }
+}
+
+impl FormatInto<Rust> for ImplBlock {
+    fn format_into(self, tokens: &mut Tokens) {
+        use crate as genco;
+        use crate::quote_in;
+
+        quote_in! { *tokens =>
+            impl$(self.generics) $(if let Some(trait_) = self.trait_ => $trait_ for$[' '])$(self.for_type) {
+                $(self.body)
+            }
+        }
+    }
+}
+
+impl FormatInto<Rust> for &ImplBlock {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(self.clone());
+    }
+}
+
+/// Construct an `impl` block for `for_type`, implementing `trait_` if one is
+/// specified, like `impl Trait for Type` or `impl Type`.
+///
+/// The body is indented inside of the block. Generic parameters are added
+/// through [ImplBlock::with_generics].
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let display = rust::import("std::fmt", "Display");
+/// let formatter = rust::import("std::fmt", "Formatter");
+///
+/// let inherent = rust::impl_block(None, "Point", quote! {
+///     fn origin() -> Self {
+///         Self { x: 0, y: 0 }
+///     }
+/// });
+///
+/// let trait_impl = rust::impl_block(Some(quote!($display)), "Point", quote! {
+///     fn fmt(&self, f: &mut $formatter<'_>) -> std::fmt::Result {
+///         write!(f, "({}, {})", self.x, self.y)
+///     }
+/// });
+///
+/// let toks: rust::Tokens = quote! {
+///     $inherent
+///
+///     $trait_impl
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use std::fmt::{Display, Formatter};",
+///         "",
+///         "impl Point {",
+///         "    fn origin() -> Self {",
+///         "        Self { x: 0, y: 0 }",
+///         "    }",
+///         "}",
+///         "",
+///         "impl Display for Point {",
+///         "    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {",
+///         "        write!(f, \"({}, {})\", self.x, self.y)",
+///         "    }",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn impl_block<F, B>(trait_: Option<Tokens>, for_type: F, body: B) -> ImplBlock
+where
+    F: FormatInto<Rust>,
+    B: FormatInto<Rust>,
+{
+    let mut t = Tokens::new();
+    t.append(for_type);
+    let mut b = Tokens::new();
+    b.append(body);
+
+    ImplBlock {
+        generics: Generics::new(),
+        trait_,
+        for_type: t,
+        body: b,
+    }
+}
+
+/// An `async fn`, like `async fn name() { .. }`.
+///
+/// Created through the [async_fn()] function.
+#[derive(Debug, Clone)]
+pub struct AsyncFn {
+    signature: Tokens,
+    body: Tokens,
+}
+
+impl FormatInto<Rust> for AsyncFn {
+    fn format_into(self, tokens: &mut Tokens) {
+        use crate as genco;
+        use crate::quote_in;
+
+        quote_in! { *tokens =>
+            async fn $(self.signature) {
+                $(self.body)
+            }
+        }
+    }
+}
+
+impl FormatInto<Rust> for &AsyncFn {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(self.clone());
+    }
+}
+
+/// Construct an `async fn` out of its `signature`, being everything that
+/// follows the `fn` keyword like `fetch(url: &str) -> String`, and its `body`.
+///
+/// The body is indented inside of the block.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: rust::Tokens = quote! {
+///     $(rust::async_fn(quote!(fetch(url: &str) -> String), quote! {
+///         let response = $(rust::await_(quote!(get(url))));
+///         $(rust::await_(quote!(response.text()))).unwrap()
+///     }))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "async fn fetch(url: &str) -> String {",
+///         "    let response = get(url).await;",
+///         "    response.text().await.unwrap()",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn async_fn<S, B>(signature: S, body: B) -> AsyncFn
+where
+    S: FormatInto<Rust>,
+    B: FormatInto<Rust>,
+{
+    let mut s = Tokens::new();
+    s.append(signature);
+    let mut b = Tokens::new();
+    b.append(body);
+
+    AsyncFn {
+        signature: s,
+        body: b,
+    }
+}
+
+/// An await expression, like `future.await`.
+///
+/// Created through the [await_()] function.
+#[derive(Debug, Clone)]
+pub struct Await {
+    expr: Tokens,
+}
+
+impl FormatInto<Rust> for Await {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(self.expr);
+        tokens.append(ItemStr::Static(".await"));
+    }
+}
+
+impl FormatInto<Rust> for &Await {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(self.clone());
+    }
+}
+
+/// Await the given expression, which in Rust is the postfix `expr.await`.
+///
+/// The expression is not parenthesized, so something like `a + b` needs to
+/// be wrapped by the caller.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: rust::Tokens = quote!(let value = $(rust::await_("future")););
+/// assert_eq!("let value = future.await;", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn await_<E>(expr: E) -> Await
+where
+    E: FormatInto<Rust>,
+{
+    let mut t = Tokens::new();
+    t.append(expr);
+    Await { expr: t }
+}
+
+/// Error raised by [format_pretty()].
+#[cfg(feature = "pretty")]
+#[derive(Debug)]
+pub enum PrettyError {
+    /// The tokens could not be formatted.
+    Format(fmt::Error),
+    /// The generated code could not be parsed as a Rust file.
+    Parse(syn::Error),
+}
+
+#[cfg(feature = "pretty")]
+impl core::fmt::Display for PrettyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Format(..) => write!(f, "failed to format tokens"),
+            Self::Parse(error) => {
+                let start = error.span().start();
+
+                write!(
+                    f,
+                    "generated code is not valid Rust: {} (at line {}, column {})",
+                    error, start.line, start.column
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "pretty")]
+impl std::error::Error for PrettyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Format(error) => Some(error),
+            Self::Parse(error) => Some(error),
+        }
+    }
+}
+
+/// Format the tokens as a file, and pass the result through [prettyplease]
+/// to get canonical formatting without having to invoke rustfmt.
+///
+/// Requires the `pretty` feature.
+///
+/// [prettyplease]: https://docs.rs/prettyplease
+///
+/// # Errors
+///
+/// Errors with [PrettyError::Parse] if the generated code isn't a valid Rust
+/// file.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let map = rust::import("std::collections", "HashMap");
+///
+/// let toks = quote! {
+///     #[derive(Debug)] struct Foo { values: $map<u32,
+///         String>, }
+/// };
+///
+/// assert_eq!(
+///     "use std::collections::HashMap;\n#[derive(Debug)]\nstruct Foo {\n    values: HashMap<u32, String>,\n}\n",
+///     rust::format_pretty(&toks)?
+/// );
+///
+/// let toks: rust::Tokens = quote!(struct 42;);
+/// let error = rust::format_pretty(&toks).unwrap_err();
+/// assert!(matches!(error, rust::PrettyError::Parse(..)));
+/// assert!(error.to_string().starts_with("generated code is not valid Rust"));
+/// # Ok::<_, rust::PrettyError>(())
+/// ```
+#[cfg(feature = "pretty")]
+pub fn format_pretty(tokens: &Tokens) -> Result<String, PrettyError> {
+    let source = tokens.to_file_string().map_err(PrettyError::Format)?;
+    let file = syn::parse_file(&source).map_err(PrettyError::Parse)?;
+    Ok(prettyplease::unparse(&file))
+}
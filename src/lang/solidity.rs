@@ -0,0 +1,551 @@
+//! Specialization for Solidity code generation.
+//!
+//! The `pragma solidity` version directive and the SPDX license identifier
+//! are specified through [Config], and are rendered at the top of the file
+//! above any imports.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//! use genco::fmt;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let ierc20 = solidity::import("./IERC20.sol", "IERC20");
+//!
+//! let toks: solidity::Tokens = quote! {
+//!     $(solidity::contract("Vault", quote! {
+//!         $(&ierc20) public token;
+//!
+//!         $(solidity::function("deposit", ["uint256 amount"], quote! {
+//!             token.transferFrom(msg.sender, address(this), amount);
+//!         }).with_modifier("external"))
+//!     }))
+//! };
+//!
+//! let mut w = fmt::VecWriter::new();
+//! let fmt = fmt::Config::from_lang::<Solidity>();
+//! let config = solidity::Config::default()
+//!     .with_license("MIT")
+//!     .with_pragma("^0.8.0");
+//! toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+//!
+//! assert_eq!(
+//!     vec![
+//!         "// SPDX-License-Identifier: MIT",
+//!         "pragma solidity ^0.8.0;",
+//!         "",
+//!         "import \"./IERC20.sol\";",
+//!         "",
+//!         "contract Vault {",
+//!         "    IERC20 public token;",
+//!         "",
+//!         "    function deposit(uint256 amount) external {",
+//!         "        token.transferFrom(msg.sender, address(this), amount);",
+//!         "    }",
+//!         "}",
+//!     ],
+//!     w.into_vec()
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in Solidity
+//!
+//! Regular string literals in Solidity may only contain printable ASCII, so
+//! anything else is escaped as the bytes of its UTF-8 encoding.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: solidity::Tokens = quote!("hello \n \"world\" ÿ");
+//! assert_eq!("\"hello \\n \\\"world\\\" \\xc3\\xbf\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate as genco;
+use crate::fmt;
+use crate::lang::import_order::{self, ImportMap, ImportOrder};
+use crate::quote_in;
+use crate::tokens::{quoted, FormatInto, ItemStr};
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+/// Tokens container specialization for Solidity.
+pub type Tokens = crate::Tokens<Solidity>;
+
+impl_lang! {
+    /// Language specialization for Solidity.
+    pub Solidity {
+        type Config = Config;
+        type Format = Format;
+        type Item = Import;
+
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn concat(out: &mut Tokens, parts: Vec<Tokens>) {
+            quote_in!(*out => string.concat($(for part in parts join (, ) => $part)));
+        }
+
+        fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            // From: https://docs.soliditylang.org/en/latest/types.html#string-literals-and-types
+            for c in input.chars() {
+                match c {
+                    '\n' => out.write_str("\\n")?,
+                    '\r' => out.write_str("\\r")?,
+                    '\t' => out.write_str("\\t")?,
+                    '\\' => out.write_str("\\\\")?,
+                    '"' => out.write_str("\\\"")?,
+                    c if c.is_ascii() && !c.is_control() => out.write_char(c)?,
+                    c => {
+                        let mut buf = [0u8; 4];
+
+                        for b in c.encode_utf8(&mut buf).bytes() {
+                            write!(out, "\\x{:02x}", b)?;
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+
+        fn format_file(
+            tokens: &Tokens,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut header = Tokens::new();
+
+            if let Some(license) = &config.license {
+                header.push();
+                quote_in!(header => $("// SPDX-License-Identifier:") $license);
+            }
+
+            if let Some(pragma) = &config.pragma {
+                header.push();
+                quote_in!(header => pragma solidity $pragma;);
+            }
+
+            if !header.is_empty() {
+                header.line();
+            }
+
+            Self::imports(&mut header, tokens, config);
+            let format = Format::default();
+            header.format(out, config, &format)?;
+            tokens.format(out, config, &format)?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+            out.write_str(&self.name)
+        }
+    }
+}
+
+/// Formatting state for Solidity.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for Solidity.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// The SPDX license identifier of the file.
+    license: Option<ItemStr>,
+    /// The compiler version constraint of the file.
+    pragma: Option<ItemStr>,
+    /// The order in which imports are rendered.
+    import_order: ImportOrder,
+}
+
+impl Config {
+    /// Set the SPDX license identifier, rendered as
+    /// `// SPDX-License-Identifier: <license>` on the first line of the file.
+    pub fn with_license<L>(self, license: L) -> Self
+    where
+        L: Into<ItemStr>,
+    {
+        Self {
+            license: Some(license.into()),
+            ..self
+        }
+    }
+
+    /// Set the compiler version constraint, rendered as
+    /// `pragma solidity <version>;` at the top of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let toks: solidity::Tokens = quote!(contract Empty {});
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// let fmt = fmt::Config::from_lang::<Solidity>();
+    /// let config = solidity::Config::default().with_pragma(">=0.8.0 <0.9.0");
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "pragma solidity >=0.8.0 <0.9.0;",
+    ///         "",
+    ///         "contract Empty {}",
+    ///     ],
+    ///     w.into_vec()
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_pragma<V>(self, pragma: V) -> Self
+    where
+        V: Into<ItemStr>,
+    {
+        Self {
+            pragma: Some(pragma.into()),
+            ..self
+        }
+    }
+
+    /// Set the order in which imports are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self {
+            import_order,
+            ..self
+        }
+    }
+}
+
+/// The import of a Solidity name through `import "./IERC20.sol";`.
+///
+/// Created through the [import()] function.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Import {
+    /// Path of the imported file, like `./IERC20.sol`.
+    path: ItemStr,
+    /// The name that was imported.
+    name: ItemStr,
+}
+
+impl Solidity {
+    fn imports(out: &mut Tokens, tokens: &Tokens, config: &Config) {
+        let mut paths = ImportMap::new(config.import_order);
+
+        for import in import_order::walk_imports(tokens) {
+            paths.get_or_insert_with(&import.path, || ());
+        }
+
+        if paths.is_empty() {
+            return;
+        }
+
+        for (path, ()) in paths {
+            out.push();
+            quote_in!(*out => import $(quoted(path)););
+        }
+
+        out.line();
+    }
+}
+
+/// A contract declaration, like `contract Token is ERC20 { .. }`.
+///
+/// Created through the [contract()] function.
+#[derive(Debug, Clone)]
+pub struct Contract {
+    /// The name of the contract.
+    name: ItemStr,
+    /// The contracts being inherited from.
+    bases: Vec<Tokens>,
+    /// The body of the contract.
+    body: Tokens,
+}
+
+impl Contract {
+    /// Inherit from the given contract, which is added to the `is` list of
+    /// the declaration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let erc20 = solidity::import("./ERC20.sol", "ERC20");
+    /// let ownable = solidity::import("./Ownable.sol", "Ownable");
+    ///
+    /// let contract = solidity::contract("Token", solidity::Tokens::new())
+    ///     .with_base(erc20)
+    ///     .with_base(ownable);
+    ///
+    /// let toks: solidity::Tokens = quote!($contract);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import \"./ERC20.sol\";",
+    ///         "import \"./Ownable.sol\";",
+    ///         "",
+    ///         "contract Token is ERC20, Ownable {}",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_base<B>(mut self, base: B) -> Self
+    where
+        B: FormatInto<Solidity>,
+    {
+        let mut tokens = Tokens::new();
+        tokens.append(base);
+        self.bases.push(tokens);
+        self
+    }
+}
+
+impl FormatInto<Solidity> for Contract {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(ItemStr::Static("contract"));
+        tokens.space();
+        tokens.append(self.name);
+
+        if !self.bases.is_empty() {
+            quote_in!(*tokens => $[' ']is $(for base in self.bases join (, ) => $base));
+        }
+
+        block(tokens, self.body);
+    }
+}
+
+/// A function declaration, like
+/// `function balanceOf(address owner) external view returns (uint256) { .. }`.
+///
+/// Created through the [function()] function.
+#[derive(Debug, Clone)]
+pub struct Function {
+    /// The name of the function.
+    name: ItemStr,
+    /// The parameters of the function.
+    params: Vec<Tokens>,
+    /// Visibility, mutability, and custom modifiers of the function.
+    modifiers: Vec<ItemStr>,
+    /// The return types of the function.
+    returns: Vec<Tokens>,
+    /// The body of the function.
+    body: Tokens,
+}
+
+impl Function {
+    /// Add a modifier to the function, like a visibility such as `external`,
+    /// a state mutability such as `view`, or a custom modifier.
+    ///
+    /// Modifiers are rendered in the order they are added.
+    pub fn with_modifier<M>(mut self, modifier: M) -> Self
+    where
+        M: Into<ItemStr>,
+    {
+        self.modifiers.push(modifier.into());
+        self
+    }
+
+    /// Add a return type to the function, which is added to its `returns`
+    /// list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let function = solidity::function("balanceOf", ["address owner"], quote!(return balances[owner];))
+    ///     .with_modifier("external")
+    ///     .with_modifier("view")
+    ///     .with_returns("uint256");
+    ///
+    /// let toks: solidity::Tokens = quote!($function);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "function balanceOf(address owner) external view returns (uint256) {",
+    ///         "    return balances[owner];",
+    ///         "}",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_returns<R>(mut self, ty: R) -> Self
+    where
+        R: FormatInto<Solidity>,
+    {
+        let mut tokens = Tokens::new();
+        tokens.append(ty);
+        self.returns.push(tokens);
+        self
+    }
+}
+
+impl FormatInto<Solidity> for Function {
+    fn format_into(self, tokens: &mut Tokens) {
+        quote_in! { *tokens =>
+            function $(self.name)($(for p in self.params join (, ) => $p))
+        }
+
+        for modifier in self.modifiers {
+            tokens.space();
+            tokens.append(modifier);
+        }
+
+        if !self.returns.is_empty() {
+            quote_in!(*tokens => $[' ']returns ($(for ty in self.returns join (, ) => $ty)));
+        }
+
+        block(tokens, self.body);
+    }
+}
+
+/// Write a braced block with an indented body, or `{}` if the body is empty.
+fn block(tokens: &mut Tokens, body: Tokens) {
+    tokens.space();
+    tokens.append(ItemStr::Static("{"));
+
+    if !body.is_empty() {
+        tokens.indent();
+        tokens.append(body);
+        tokens.unindent();
+    }
+
+    tokens.append(ItemStr::Static("}"));
+}
+
+/// Import a name from the given file, like `IERC20` from `./IERC20.sol`.
+///
+/// Files are imported through `import "<path>";` declarations, which are
+/// deduplicated by path.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let ierc20 = solidity::import("./IERC20.sol", "IERC20");
+/// let safe = solidity::import("./SafeERC20.sol", "SafeERC20");
+///
+/// let toks: solidity::Tokens = quote! {
+///     using $safe for $(&ierc20);
+///     $(&ierc20) token;
+///     $ierc20 other;
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "import \"./IERC20.sol\";",
+///         "import \"./SafeERC20.sol\";",
+///         "",
+///         "using SafeERC20 for IERC20;",
+///         "IERC20 token;",
+///         "IERC20 other;",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn import<P, N>(path: P, name: N) -> Import
+where
+    P: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    Import {
+        path: path.into(),
+        name: name.into(),
+    }
+}
+
+/// Construct a contract declaration, where the body is indented between
+/// braces.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: solidity::Tokens = quote! {
+///     $(solidity::contract("Counter", quote!(uint256 public count;)))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "contract Counter {",
+///         "    uint256 public count;",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn contract<N>(name: N, body: Tokens) -> Contract
+where
+    N: Into<ItemStr>,
+{
+    Contract {
+        name: name.into(),
+        bases: Vec::new(),
+        body,
+    }
+}
+
+/// Construct a function declaration with the given parameters, where the
+/// body is indented between braces.
+///
+/// Parameters can be anything which can be formatted, so types used in them
+/// are imported like `quote!($ierc20 token)`.
+///
+/// Use [Function::with_modifier] and [Function::with_returns] to add
+/// modifiers and return types.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: solidity::Tokens = quote! {
+///     $(solidity::function("increment", Vec::<&str>::new(), quote!(count += 1;)).with_modifier("public"))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "function increment() public {",
+///         "    count += 1;",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn function<N, P>(name: N, params: P, body: Tokens) -> Function
+where
+    N: Into<ItemStr>,
+    P: IntoIterator,
+    P::Item: FormatInto<Solidity>,
+{
+    Function {
+        name: name.into(),
+        params: params
+            .into_iter()
+            .map(|param| {
+                let mut tokens = Tokens::new();
+                tokens.append(param);
+                tokens
+            })
+            .collect(),
+        modifiers: Vec::new(),
+        returns: Vec::new(),
+        body,
+    }
+}
@@ -15,9 +15,9 @@
 //! # }
 
 use crate::fmt;
+use crate::lang::import_order::{self, ImportOrder, ImportSet};
 use crate::tokens::ItemStr;
-use std::collections::BTreeSet;
-use std::fmt::Write as _;
+use core::fmt::Write as _;
 
 /// Tokens container specialization for Rust.
 pub type Tokens = crate::Tokens<Swift>;
@@ -29,6 +29,10 @@ impl_lang! {
         type Format = Format;
         type Item = Import;
 
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
         fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
             // From: https://docs.swift.org/swift-book/LanguageGuide/StringsAndCharacters.html
 
@@ -41,6 +45,9 @@ impl_lang! {
                     '\r' => out.write_str("\\r")?,
                     '\'' => out.write_str("\\'")?,
                     '"' => out.write_str("\\\"")?,
+                    c if !c.is_ascii() && out.ascii_only() => {
+                        write!(out, "\\u{{{:x}}}", c as u32)?;
+                    }
                     c if !c.is_control() => out.write_char(c)?,
                     c => {
                         write!(out, "\\u{{{:x}}}", c as u32)?;
@@ -51,13 +58,21 @@ impl_lang! {
             Ok(())
         }
 
+        fn write_char_literal(out: &mut fmt::Formatter<'_>, c: char) -> fmt::Result {
+            // Note: characters use the same literal syntax as strings.
+            out.write_char('"')?;
+            Self::write_quoted(out, c.encode_utf8(&mut [0; 4]))?;
+            out.write_char('"')?;
+            Ok(())
+        }
+
         fn format_file(
             tokens: &Tokens,
             out: &mut fmt::Formatter<'_>,
             config: &Self::Config,
         ) -> fmt::Result {
             let mut imports = Tokens::new();
-            Self::imports(&mut imports, tokens);
+            Self::imports(&mut imports, tokens, config);
             let format = Format::default();
             imports.format(out, config, &format)?;
             tokens.format(out, config, &format)?;
@@ -78,12 +93,23 @@ pub struct Format {}
 
 /// Configuration for formatting Swift code.
 #[derive(Debug, Default)]
-pub struct Config {}
+pub struct Config {
+    /// The order in which imports are rendered.
+    import_order: ImportOrder,
+}
+
+impl Config {
+    /// Set the order in which imports are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self { import_order }
+    }
+}
 
 /// The import of a Swift type `import UIKit`.
 ///
 /// Created through the [import()] function.
 #[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Import {
     /// Module of the imported name.
     module: ItemStr,
@@ -92,13 +118,13 @@ pub struct Import {
 }
 
 impl Swift {
-    fn imports(out: &mut Tokens, tokens: &Tokens) {
+    fn imports(out: &mut Tokens, tokens: &Tokens, config: &Config) {
         use crate as genco;
         use crate::quote_in;
 
-        let mut modules = BTreeSet::new();
+        let mut modules = ImportSet::new(config.import_order);
 
-        for import in tokens.walk_imports() {
+        for import in import_order::walk_imports(tokens) {
             modules.insert(&import.module);
         }
 
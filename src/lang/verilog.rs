@@ -0,0 +1,469 @@
+//! Specialization for Verilog code generation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let ports = [
+//!     verilog::input("clk"),
+//!     verilog::output("q").with_type("reg").with_width(8),
+//! ];
+//!
+//! let toks: verilog::Tokens = quote! {
+//!     $(verilog::module("counter", ports, quote! {
+//!         always @(posedge clk) begin
+//!             q <= q + $(verilog::literal(8, 1));
+//!         end
+//!     }))
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "module counter (",
+//!         "    input clk,",
+//!         "    output reg [7:0] q",
+//!         ");",
+//!         "    always @(posedge clk) begin",
+//!         "        q <= q + 8'h01;",
+//!         "    end",
+//!         "endmodule",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in Verilog
+//!
+//! Strings are double-quoted, where control characters are escaped as octal
+//! escape sequences unless they have a dedicated escape like `\n`.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: verilog::Tokens = quote!($$display($(quoted("value: \"%d\"\n\x07"))););
+//! assert_eq!("$display(\"value: \\\"%d\\\"\\n\\007\");", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate as genco;
+use crate::fmt;
+use crate::lang::Lang;
+use crate::quote_in;
+use crate::tokens::{FormatInto, ItemStr};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+/// Tokens container specialization for Verilog.
+pub type Tokens = crate::Tokens<Verilog>;
+
+/// Language specialization for Verilog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Verilog(());
+
+impl Lang for Verilog {
+    type Config = Config;
+    type Format = Format;
+    type Item = ();
+
+    fn default_config() -> Self::Config {
+        Config::default()
+    }
+
+    fn concat(out: &mut Tokens, parts: Vec<Tokens>) {
+        quote_in!(*out => {$(for part in parts join (, ) => $part)});
+    }
+
+    fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+        // From: IEEE 1364-2005, section 3.6.3.
+        for c in input.chars() {
+            match c {
+                '\n' => out.write_str("\\n")?,
+                '\t' => out.write_str("\\t")?,
+                '\\' => out.write_str("\\\\")?,
+                '"' => out.write_str("\\\"")?,
+                c if c.is_ascii_control() => write!(out, "\\{:03o}", c as u32)?,
+                c => out.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Formatting state for Verilog.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for Verilog.
+#[derive(Debug, Default)]
+pub struct Config {}
+
+/// The base of a sized numeric literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Base {
+    /// Binary, like `4'b1010`.
+    Binary,
+    /// Octal, like `6'o52`.
+    Octal,
+    /// Decimal, like `8'd255`.
+    Decimal,
+    /// Hexadecimal, like `8'hFF`.
+    ///
+    /// This is the default.
+    Hex,
+}
+
+impl Default for Base {
+    fn default() -> Self {
+        Self::Hex
+    }
+}
+
+/// A sized numeric literal, like `8'hFF`.
+///
+/// Created through the [literal()] function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Literal {
+    /// The width of the literal in bits.
+    width: u32,
+    /// The value of the literal.
+    value: u64,
+    /// The base the literal is rendered in.
+    base: Base,
+}
+
+impl Literal {
+    /// Set the base the literal is rendered in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use verilog::Base;
+    ///
+    /// let toks: verilog::Tokens = quote! {
+    ///     $(verilog::literal(4, 2).with_base(Base::Binary))
+    ///     $(verilog::literal(6, 42).with_base(Base::Octal))
+    ///     $(verilog::literal(8, 42).with_base(Base::Decimal))
+    /// };
+    ///
+    /// assert_eq!(vec!["4'b0010", "6'o52", "8'd42"], toks.to_file_vec()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_base(self, base: Base) -> Self {
+        Self { base, ..self }
+    }
+}
+
+impl FormatInto<Verilog> for Literal {
+    fn format_into(self, tokens: &mut Tokens) {
+        let value = match self.width {
+            0..=63 => self.value & ((1u64 << self.width) - 1),
+            _ => self.value,
+        };
+
+        // NB: digits are padded to the width of the literal, except for
+        // decimal literals where the digits don't line up with the bits.
+        let (c, bits) = match self.base {
+            Base::Binary => ('b', 1),
+            Base::Octal => ('o', 3),
+            Base::Decimal => ('d', 0),
+            Base::Hex => ('h', 4),
+        };
+
+        let mut out = String::new();
+        let _ = write!(out, "{}'{}", self.width, c);
+
+        let digits = match bits {
+            0 => 0,
+            bits => ((self.width + bits - 1) / bits) as usize,
+        };
+
+        let _ = match self.base {
+            Base::Binary => write!(out, "{:0digits$b}", value, digits = digits),
+            Base::Octal => write!(out, "{:0digits$o}", value, digits = digits),
+            Base::Decimal => write!(out, "{}", value),
+            Base::Hex => write!(out, "{:0digits$X}", value, digits = digits),
+        };
+
+        tokens.append(ItemStr::from(out));
+    }
+}
+
+/// The direction of a port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Direction {
+    Input,
+    Output,
+    Inout,
+}
+
+impl Direction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Input => "input",
+            Self::Output => "output",
+            Self::Inout => "inout",
+        }
+    }
+}
+
+/// A port declaration of a module, like `input wire [7:0] data`.
+///
+/// Created through the [input()], [output()], and [inout()] functions.
+#[derive(Debug, Clone)]
+pub struct Port {
+    /// The direction of the port.
+    direction: Direction,
+    /// The net or variable type of the port, like `reg`.
+    ty: Option<ItemStr>,
+    /// The width of the port in bits.
+    width: Option<u32>,
+    /// The name of the port.
+    name: ItemStr,
+}
+
+impl Port {
+    /// Set the net or variable type of the port, like `wire` or `reg`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let toks: verilog::Tokens = quote!($(verilog::output("ready").with_type("reg")));
+    /// assert_eq!("output reg ready", toks.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_type<T>(self, ty: T) -> Self
+    where
+        T: Into<ItemStr>,
+    {
+        Self {
+            ty: Some(ty.into()),
+            ..self
+        }
+    }
+
+    /// Set the width of the port in bits, which is declared as a range like
+    /// `[7:0]`.
+    ///
+    /// A width of 1 declares a scalar port without a range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let toks: verilog::Tokens = quote! {
+    ///     $(verilog::input("data").with_width(8))
+    ///     $(verilog::input("enable").with_width(1))
+    /// };
+    ///
+    /// assert_eq!(vec!["input [7:0] data", "input enable"], toks.to_file_vec()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_width(self, width: u32) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+}
+
+impl FormatInto<Verilog> for Port {
+    fn format_into(self, tokens: &mut Tokens) {
+        tokens.append(ItemStr::Static(self.direction.as_str()));
+
+        if let Some(ty) = self.ty {
+            tokens.space();
+            tokens.append(ty);
+        }
+
+        if let Some(width) = self.width.filter(|w| *w > 1) {
+            tokens.space();
+            tokens.append(ItemStr::from(alloc::format!("[{}:0]", width - 1)));
+        }
+
+        tokens.space();
+        tokens.append(self.name);
+    }
+}
+
+/// A module declaration, like `module name (..); .. endmodule`.
+///
+/// Created through the [module()] function.
+#[derive(Debug, Clone)]
+pub struct Module {
+    /// The name of the module.
+    name: ItemStr,
+    /// The ports of the module.
+    ports: Vec<Port>,
+    /// The body of the module.
+    body: Tokens,
+}
+
+impl FormatInto<Verilog> for Module {
+    fn format_into(self, tokens: &mut Tokens) {
+        if self.ports.is_empty() {
+            quote_in!(*tokens => module $(self.name););
+        } else {
+            quote_in! { *tokens =>
+                module $(self.name) (
+                    $(for port in self.ports join (,$['\r']) => $port)
+                );
+            }
+        }
+
+        // NB: an empty body would otherwise collapse `endmodule` onto the
+        // same line as the declaration.
+        if self.body.is_empty() {
+            tokens.push();
+        } else {
+            tokens.indent();
+            tokens.append(self.body);
+            tokens.unindent();
+        }
+
+        tokens.append(ItemStr::Static("endmodule"));
+    }
+}
+
+/// Construct a sized numeric literal with the given width in bits, like
+/// `8'hFF`.
+///
+/// Literals are rendered in hexadecimal by default, where the digits are
+/// padded to the width of the literal. Like in Verilog, a value which doesn't
+/// fit in the width is truncated. Use [Literal::with_base] to pick another
+/// base.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: verilog::Tokens = quote! {
+///     $(verilog::literal(8, 255))
+///     $(verilog::literal(12, 10))
+///     $(verilog::literal(4, 0x1f))
+/// };
+///
+/// assert_eq!(vec!["8'hFF", "12'h00A", "4'hF"], toks.to_file_vec()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn literal(width: u32, value: u64) -> Literal {
+    Literal {
+        width,
+        value,
+        base: Base::default(),
+    }
+}
+
+/// Construct an `input` port declaration.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: verilog::Tokens = quote!($(verilog::input("clk")));
+/// assert_eq!("input clk", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn input<N>(name: N) -> Port
+where
+    N: Into<ItemStr>,
+{
+    port(Direction::Input, name.into())
+}
+
+/// Construct an `output` port declaration.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: verilog::Tokens = quote!($(verilog::output("q").with_width(4)));
+/// assert_eq!("output [3:0] q", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn output<N>(name: N) -> Port
+where
+    N: Into<ItemStr>,
+{
+    port(Direction::Output, name.into())
+}
+
+/// Construct an `inout` port declaration.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: verilog::Tokens = quote!($(verilog::inout("sda").with_type("wire")));
+/// assert_eq!("inout wire sda", toks.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn inout<N>(name: N) -> Port
+where
+    N: Into<ItemStr>,
+{
+    port(Direction::Inout, name.into())
+}
+
+fn port(direction: Direction, name: ItemStr) -> Port {
+    Port {
+        direction,
+        ty: None,
+        width: None,
+        name,
+    }
+}
+
+/// Construct a module declaration with the given ports, where the body is
+/// indented between the port list and `endmodule`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: verilog::Tokens = quote! {
+///     $(verilog::module("top", [], quote!(wire unused;)))
+///
+///     $(verilog::module("empty", [], verilog::Tokens::new()))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "module top;",
+///         "    wire unused;",
+///         "endmodule",
+///         "",
+///         "module empty;",
+///         "endmodule",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn module<N, P>(name: N, ports: P, body: Tokens) -> Module
+where
+    N: Into<ItemStr>,
+    P: IntoIterator<Item = Port>,
+{
+    Module {
+        name: name.into(),
+        ports: ports.into_iter().collect(),
+        body,
+    }
+}
@@ -0,0 +1,370 @@
+//! Specialization for Zig code generation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let array_list = zig::import("std", "ArrayList");
+//! let allocator = zig::import("std", "heap.page_allocator");
+//!
+//! let toks: zig::Tokens = quote! {
+//!     pub fn numbers() !$(&array_list)(u32) {
+//!         var list = $array_list(u32).init($allocator);
+//!         try list.append(42);
+//!         return list;
+//!     }
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "const std = @import(\"std\");",
+//!         "",
+//!         "pub fn numbers() !std.ArrayList(u32) {",
+//!         "    var list = std.ArrayList(u32).init(std.heap.page_allocator);",
+//!         "    try list.append(42);",
+//!         "    return list;",
+//!         "}",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in Zig
+//!
+//! Zig string literals are UTF-8, so only control characters, backslashes, and
+//! quotes are escaped.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: zig::Tokens = quote!("hello \n \"world\" \x1b ÿ");
+//! assert_eq!("\"hello \\n \\\"world\\\" \\x1b ÿ\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate as genco;
+use crate::fmt;
+use crate::lang::import_order::{self, ImportMap, ImportOrder};
+use crate::quote_in;
+use crate::tokens::{quoted, ItemStr};
+use alloc::collections::BTreeMap;
+use alloc::format;
+use core::fmt::Write as _;
+
+/// Tokens container specialization for Zig.
+pub type Tokens = crate::Tokens<Zig>;
+
+impl_lang! {
+    /// Language specialization for Zig.
+    pub Zig {
+        type Config = Config;
+        type Format = Format;
+        type Item = Import;
+
+        fn default_config() -> Self::Config {
+            Config::default()
+        }
+
+        fn ternary(out: &mut Tokens, cond: Tokens, then: Tokens, els: Tokens) {
+            use crate as genco;
+            use crate::quote_in;
+
+            quote_in!(*out => if ($cond) $then else $els);
+        }
+
+        fn concat_operator() -> &'static str {
+            "++"
+        }
+
+        fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            // From: https://ziglang.org/documentation/master/#Escape-Sequences
+            for c in input.chars() {
+                match c {
+                    '\n' => out.write_str("\\n")?,
+                    '\r' => out.write_str("\\r")?,
+                    '\t' => out.write_str("\\t")?,
+                    '\\' => out.write_str("\\\\")?,
+                    '"' => out.write_str("\\\"")?,
+                    c if (c as u32) < 0x20 || c == '\x7f' => {
+                        write!(out, "\\x{:02x}", c as u32)?;
+                    }
+                    c if !c.is_ascii() && out.ascii_only() => {
+                        write!(out, "\\u{{{:x}}}", c as u32)?;
+                    }
+                    c => out.write_char(c)?,
+                }
+            }
+
+            Ok(())
+        }
+
+        fn format_file(
+            tokens: &Tokens,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut imports = Tokens::new();
+            let mut format = Format::default();
+            Self::imports(&mut imports, tokens, config, &mut format);
+            imports.format(out, config, &format)?;
+            tokens.format(out, config, &format)?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, format: &Format) -> fmt::Result {
+            out.write_str(format.binding(self))?;
+            out.write_str(SEP)?;
+            out.write_str(&self.name)?;
+            Ok(())
+        }
+    }
+}
+
+/// Formatting state for Zig.
+#[derive(Debug, Default)]
+pub struct Format {
+    /// Names assigned to modules whose file stem would otherwise conflict with
+    /// the constant of another module, keyed by module path.
+    aliases: BTreeMap<ItemStr, ItemStr>,
+}
+
+impl Format {
+    /// The name of the constant the module of the given import is bound to.
+    fn binding<'a>(&'a self, import: &'a Import) -> &'a str {
+        if import.alias.is_none() {
+            if let Some(alias) = self.aliases.get(&import.path) {
+                return alias;
+            }
+        }
+
+        import.binding()
+    }
+}
+
+/// Configuration for Zig.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// The order in which imports are rendered.
+    import_order: ImportOrder,
+}
+
+impl Config {
+    /// Set the order in which imports are rendered.
+    pub fn with_import_order(self, import_order: ImportOrder) -> Self {
+        Self { import_order }
+    }
+}
+
+static SEP: &str = ".";
+
+/// The import of a Zig name through `const std = @import("std");`.
+///
+/// Created through the [import()] function.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Import {
+    /// Path of the imported module, like `std` or `foo.zig`.
+    path: ItemStr,
+    /// The name that was imported.
+    name: ItemStr,
+    /// Alias of the constant the module is bound to.
+    alias: Option<ItemStr>,
+}
+
+impl Import {
+    /// Bind the imported module to a constant with the given name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let toks: zig::Tokens = quote! {
+    ///     $(zig::import("utils/string_helpers.zig", "trim").with_alias("strings"))
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "const strings = @import(\"utils/string_helpers.zig\");",
+    ///         "",
+    ///         "strings.trim",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_alias<A>(self, alias: A) -> Self
+    where
+        A: Into<ItemStr>,
+    {
+        Self {
+            alias: Some(alias.into()),
+            ..self
+        }
+    }
+
+    /// The name of the constant the imported module is bound to.
+    ///
+    /// Unless an alias is specified, this is the file stem of the path.
+    fn binding(&self) -> &str {
+        if let Some(alias) = &self.alias {
+            return alias;
+        }
+
+        let path = self.path.rsplit('/').next().unwrap_or(&self.path);
+        path.strip_suffix(".zig").unwrap_or(path)
+    }
+}
+
+impl Zig {
+    /// Assign names to modules whose file stem conflicts with the constant of
+    /// another module, like `a/util.zig` and `b/util.zig`.
+    ///
+    /// Explicit aliases are reserved first, after which the first module
+    /// bound to a name keeps it and later ones are numbered like `util2`.
+    fn resolve_conflicts(tokens: &Tokens, format: &mut Format) {
+        let mut bound = BTreeMap::<ItemStr, &ItemStr>::new();
+
+        for import in import_order::walk_imports(tokens) {
+            if let Some(alias) = &import.alias {
+                bound.entry(alias.clone()).or_insert(&import.path);
+            }
+        }
+
+        for import in import_order::walk_imports(tokens) {
+            if import.alias.is_some() || format.aliases.contains_key(&import.path) {
+                continue;
+            }
+
+            let binding = ItemStr::from(import.binding());
+
+            match bound.get(&binding) {
+                None => {
+                    bound.insert(binding, &import.path);
+                    continue;
+                }
+                Some(path) if **path == import.path => continue,
+                Some(..) => {}
+            }
+
+            let mut n = 2;
+
+            let alias = loop {
+                let alias = ItemStr::from(format!("{}{n}", &*binding));
+
+                if !bound.contains_key(&alias) {
+                    break alias;
+                }
+
+                n += 1;
+            };
+
+            format.aliases.insert(import.path.clone(), alias.clone());
+            bound.insert(alias, &import.path);
+        }
+    }
+
+    fn imports(out: &mut Tokens, tokens: &Tokens, config: &Config, format: &mut Format) {
+        Self::resolve_conflicts(tokens, format);
+        let format = &*format;
+        let mut bindings = ImportMap::new(config.import_order);
+
+        for import in import_order::walk_imports(tokens) {
+            bindings.get_or_insert_with(format.binding(import), || &import.path);
+        }
+
+        if bindings.is_empty() {
+            return;
+        }
+
+        for (binding, path) in bindings {
+            out.push();
+            quote_in!(*out => const $binding = @import($(quoted(path)));)
+        }
+
+        out.line();
+    }
+}
+
+/// The import of a Zig name, like `std.ArrayList`.
+///
+/// The module is bound to a constant at the top of the file, which is named
+/// after the file stem of the path. Imports are deduplicated by the name of
+/// the constant. Local files can be imported by specifying a path ending in
+/// `.zig`.
+///
+/// If the file stems of different paths conflict, modules after the first one
+/// are bound to a numbered constant instead, unless they have an alias
+/// through [Import::with_alias].
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: zig::Tokens = quote! {
+///     $(zig::import("std", "ArrayList"))
+///     $(zig::import("std", "mem.eql"))
+///     $(zig::import("foo.zig", "Bar"))
+///     $(zig::import("src/util.zig", "helper"))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "const foo = @import(\"foo.zig\");",
+///         "const std = @import(\"std\");",
+///         "const util = @import(\"src/util.zig\");",
+///         "",
+///         "std.ArrayList",
+///         "std.mem.eql",
+///         "foo.Bar",
+///         "util.helper",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// Example with conflicting file stems:
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: zig::Tokens = quote! {
+///     $(zig::import("a/util.zig", "first"))
+///     $(zig::import("b/util.zig", "second"))
+///     $(zig::import("a/util.zig", "third"))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "const util = @import(\"a/util.zig\");",
+///         "const util2 = @import(\"b/util.zig\");",
+///         "",
+///         "util.first",
+///         "util2.second",
+///         "util.third",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn import<P, N>(path: P, name: N) -> Import
+where
+    P: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    Import {
+        path: path.into(),
+        name: name.into(),
+        alias: None,
+    }
+}
@@ -81,6 +81,42 @@
 //!   <small>[Example][python-example]</small><br>
 //!   **Requires a `nightly` compiler**
 //!
+//! * [📄 <b>HTML</b>][html]
+//!
+//! * [👑 <b>Nim</b>][nim]
+//!
+//! * [⚡ <b>Zig</b>][zig]
+//!
+//! * [💧 <b>Elixir</b>][elixir]
+//!
+//! * [📊 <b>R</b>][r]
+//!
+//! * [🏗️ <b>HCL</b>][hcl]
+//!
+//! * [➕ <b>C++</b>][cpp]
+//!
+//! * [📝 <b>Markdown</b>][markdown]
+//!
+//! * [🐫 <b>OCaml</b>][ocaml]
+//!
+//! * [🐪 <b>Perl</b>][perl]
+//!
+//! * [⭐ <b>Groovy</b>][groovy]
+//!
+//! * [🐚 <b>Bash</b>][bash]
+//!
+//! * [🧊 <b>CUE</b>][cue]
+//!
+//! * [⚙️ <b>Assembly</b>][asm]
+//!
+//! * [🐳 <b>Dockerfile</b>][dockerfile]
+//!
+//! * [🔌 <b>Verilog</b>][verilog]
+//!
+//! * [🎮 <b>GDScript</b>][gdscript]
+//!
+//! * [⛓️ <b>Solidity</b>][solidity]
+//!
 //! <small>Is your favorite language missing? <b>[Open an issue!]</b></small>
 //!
 //! You can run one of the examples by:
@@ -125,14 +161,24 @@
 //!
 //! <br>
 //!
+//! [asm]: https://docs.rs/genco/latest/genco/lang/asm/index.html
+//! [bash]: https://docs.rs/genco/latest/genco/lang/bash/index.html
 //! [c-example]: https://github.com/udoprog/genco/blob/master/examples/c.rs
 //! [c]: https://docs.rs/genco/latest/genco/lang/c/index.html
 //! [c#-example]: https://github.com/udoprog/genco/blob/master/examples/csharp.rs
 //! [c#]: https://docs.rs/genco/latest/genco/lang/csharp/index.html
+//! [cpp]: https://docs.rs/genco/latest/genco/lang/cpp/index.html
+//! [cue]: https://docs.rs/genco/latest/genco/lang/cue/index.html
 //! [dart-example]: https://github.com/udoprog/genco/blob/master/examples/dart.rs
 //! [dart]: https://docs.rs/genco/latest/genco/lang/dart/index.html
+//! [dockerfile]: https://docs.rs/genco/latest/genco/lang/dockerfile/index.html
+//! [elixir]: https://docs.rs/genco/latest/genco/lang/elixir/index.html
+//! [gdscript]: https://docs.rs/genco/latest/genco/lang/gdscript/index.html
 //! [go-example]: https://github.com/udoprog/genco/blob/master/examples/go.rs
 //! [go]: https://docs.rs/genco/latest/genco/lang/go/index.html
+//! [groovy]: https://docs.rs/genco/latest/genco/lang/groovy/index.html
+//! [hcl]: https://docs.rs/genco/latest/genco/lang/hcl/index.html
+//! [html]: https://docs.rs/genco/latest/genco/lang/html/index.html
 //! [impl_lang!]: https://docs.rs/genco/latest/genco/macro.impl_lang.html
 //! [import statements]: https://docs.rs/genco/latest/genco/macro.quote.html#imports
 //! [indentation is meaningful]: https://docs.python.org/3/faq/design.html#why-does-python-use-indentation-for-grouping-of-statements
@@ -141,22 +187,36 @@
 //! [java]: https://docs.rs/genco/latest/genco/lang/java/index.html
 //! [js-example]: https://github.com/udoprog/genco/blob/master/examples/js.rs
 //! [js]: https://docs.rs/genco/latest/genco/lang/js/index.html
+//! [markdown]: https://docs.rs/genco/latest/genco/lang/markdown/index.html
+//! [nim]: https://docs.rs/genco/latest/genco/lang/nim/index.html
+//! [ocaml]: https://docs.rs/genco/latest/genco/lang/ocaml/index.html
 //! [Open an issue!]: https://github.com/udoprog/genco/issues/new
+//! [perl]: https://docs.rs/genco/latest/genco/lang/perl/index.html
 //! [python-example]: https://github.com/udoprog/genco/blob/master/examples/python.rs
 //! [python]: https://docs.rs/genco/latest/genco/lang/python/index.html
 //! [quote strings]: https://docs.rs/genco/latest/genco/macro.quote.html#string-quoting
 //! [quote_in!]: https://docs.rs/genco/latest/genco/macro.quote_in.html
 //! [quote!]: https://docs.rs/genco/latest/genco/macro.quote.html
 //! [quoted()]: https://docs.rs/genco/latest/genco/tokens/fn.quoted.html
+//! [r]: https://docs.rs/genco/latest/genco/lang/r/index.html
 //! [rust-example]: https://github.com/udoprog/genco/blob/master/examples/rust.rs
 //! [rust]: https://docs.rs/genco/latest/genco/lang/rust/index.html
+//! [solidity]: https://docs.rs/genco/latest/genco/lang/solidity/index.html
 //! [solve namespace conflicts]: https://docs.rs/genco/latest/genco/lang/csharp/fn.import.html
 //! [token streams]: https://docs.rs/genco/latest/genco/tokens/struct.Tokens.html
+//! [verilog]: https://docs.rs/genco/latest/genco/lang/verilog/index.html
 //! [whitespace detection]: https://docs.rs/genco/latest/genco/macro.quote.html#whitespace-detection
+//! [zig]: https://docs.rs/genco/latest/genco/lang/zig/index.html
 
 #![deny(missing_docs)]
 #![deny(rustdoc::broken_intra_doc_links)]
 #![allow(clippy::needless_doctest_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "alloc"))]
+compile_error!("genco requires the `alloc` feature to be enabled");
+
+extern crate alloc;
 
 /// Whitespace sensitive quasi-quoting.
 ///
@@ -257,6 +317,33 @@
 /// }
 /// ```
 ///
+/// As a shorthand for fallible fragments, `$(try <expr>)` applies `?` to the
+/// expression and interpolates the `Ok` value:
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// fn field(name: &str) -> Result<rust::Tokens, String> {
+///     if name.is_empty() {
+///         return Err(String::from("empty field name"));
+///     }
+///
+///     Ok(quote!($name: u32,))
+/// }
+///
+/// fn fields(names: &[&str]) -> Result<rust::Tokens, String> {
+///     Ok(quote! {
+///         struct Fields {
+///             $(for name in names => $(try field(name)))
+///         }
+///     })
+/// }
+///
+/// assert_eq!("struct Fields {\n    a: u32,\n}", fields(&["a"])?.to_string().unwrap());
+/// assert_eq!(Err(String::from("empty field name")), fields(&["a", ""]));
+/// # Ok::<_, String>(())
+/// ```
+///
 /// [FormatInto]: crate::tokens::FormatInto
 /// [main genco documentation]: https://docs.rs/genco
 ///
@@ -285,6 +372,15 @@
 ///   following tokens have an empty line separating them. This corresponds to
 ///   the [Tokens::line] function.
 ///
+/// * `$[softpush]` — Inserts a soft push operation. Like a push it makes sure
+///   that any following tokens are on their own dedicated line, but it also
+///   collapses any preceeding line operations so that it never results in an
+///   empty line. This corresponds to the [Tokens::soft_push] function.
+///
+/// * `$[flush]` — Discards any pending whitespace, so that whatever follows is
+///   appended directly after the last token. This corresponds to the
+///   [Tokens::flush] function.
+///
 /// ```
 /// use genco::prelude::*;
 ///
@@ -293,6 +389,48 @@
 /// let tokens: Tokens<()> = quote!(foo$['\r']bar$['\n']baz$[' ']biz);
 ///
 /// assert_eq!("foo\nbar\n\nbaz biz", tokens.to_string()?);
+///
+/// let tokens: Tokens<()> = quote!(foo$['\n']$[softpush]bar$[softpush]$[softpush]baz);
+///
+/// assert_eq!("foo\nbar\nbaz", tokens.to_string()?);
+///
+/// let tokens: Tokens<()> = quote!(foo $(ref t => {})$[flush]bar);
+///
+/// assert_eq!("foobar", tokens.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// <br>
+///
+/// # Concatenation
+///
+/// `$(concat <quoted>)` fuses everything in `<quoted>` together without any
+/// whitespace between them, regardless of how they are spaced out in the
+/// macro. Both literal tokens and interpolations participate.
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let field = "Name";
+///
+/// let tokens: js::Tokens = quote!(user.$(concat get $field)());
+///
+/// assert_eq!("user.getName()", tokens.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// Note that content which together with `concat` parses as a Rust
+/// expression is evaluated as one. So `$(concat (a))`, `$(concat [x])` and
+/// `$(concat -1)` are a function call, an index, and a subtraction involving
+/// a variable named `concat`. To concatenate tokens like these, start with an
+/// interpolation instead:
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let tokens: js::Tokens = quote!($(concat $("-") 1) $(concat $("(") a $(")")));
+///
+/// assert_eq!("-1 (a)", tokens.to_string()?);
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
 ///
@@ -439,9 +577,12 @@
 /// [quote!] provides some limited mechanisms for control flow inside of the
 /// macro for convenience. The supported mechanisms are:
 ///
-/// * [Loops](#loops) - `$(for <bindings> in <expr> [join (<quoted>)] => <quoted>)`.
-/// * [Conditionals](#conditionals) - `$(if <pattern> => <quoted>)`.
+/// * [Loops](#loops) - `$(for <bindings> in <expr> [sorted] [join (<quoted>)] => <quoted>)`.
+/// * [Spreading](#spreading) - `$(spread <expr> with <separator>)`.
+/// * [Repetitions](#repetitions) - `$(repeat <count> [join (<quoted>)] => <quoted>)`.
+/// * [Conditionals](#conditionals) - `$(if <pattern> => <quoted> [else => <quoted>])`.
 /// * [Match Statements](#match-statements) - `$(match <expr> { [<pattern> => <quoted>,]* })`.
+/// * [Once Blocks](#once-blocks) - `$(once <key> => <quoted>)`.
 ///
 /// <br>
 ///
@@ -470,6 +611,78 @@
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
 ///
+/// `<bindings>` can be any irrefutable Rust pattern, including ones using
+/// `ref`, `ref mut`, and `mut`. To iterate without consuming a collection,
+/// loop over a reference to it.
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let names = vec![String::from("foo"), String::from("bar")];
+///
+/// let tokens: Tokens<()> = quote! {
+///     $(for name in &names join (, ) => $name)
+///     $(for (i, ref name) in names.iter().enumerate() join (, ) => $i=$(*name))
+/// };
+///
+/// assert_eq!(vec!["foo, bar", "0=foo, 1=bar"], tokens.to_file_vec()?);
+/// assert_eq!(2, names.len());
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// Like in a regular `for` loop, a refutable pattern is an error:
+///
+/// ```,compile_fail
+/// use genco::prelude::*;
+///
+/// let values = vec![Some(1), None];
+///
+/// let tokens: Tokens<()> = quote! {
+///     $(for Some(n) in values => $n)
+/// };
+/// ```
+///
+/// <br>
+///
+/// # Sorted Loops
+///
+/// Iterating over something like a `HashMap` or a `HashSet` produces items in
+/// an unspecified order, which would make the generated code differ between
+/// runs. Adding `sorted` after `<expr>` collects and sorts the items before
+/// they are iterated over, so that the output is deterministic.
+///
+/// If `<bindings>` is a pair like `(k, v)` the items are sorted by their key,
+/// which must implement [Ord]. Otherwise the items themselves are sorted.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use genco::prelude::*;
+///
+/// let mut fields = HashMap::new();
+/// fields.insert("name", "String");
+/// fields.insert("age", "u32");
+/// fields.insert("email", "String");
+///
+/// let tokens: rust::Tokens = quote! {
+///     struct User {
+///         $(for (name, ty) in &fields sorted => $(*name): $(*ty),$['\r'])
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "struct User {",
+///         "    age: u32,",
+///         "    email: String,",
+///         "    name: String,",
+///         "}",
+///     ],
+///     tokens.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
 /// <br>
 ///
 /// # Joining Loops
@@ -498,6 +711,60 @@
 ///
 /// <br>
 ///
+/// # Spreading
+///
+/// If you already have an iterator of fragments, like a `Vec<Tokens>` of
+/// arguments, `$(spread <expr> with <separator>)` appends each of them
+/// separated by `<separator>`. The separator is anything which can be
+/// converted into an [ItemStr], and is added as a literal.
+///
+/// [ItemStr]: crate::tokens::ItemStr
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let args: Vec<js::Tokens> = vec![quote!(a), quote!(b), quote!(c)];
+///
+/// let tokens: js::Tokens = quote!(run($(spread args with ", ")));
+///
+/// assert_eq!("run(a, b, c)", tokens.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// <br>
+///
+/// # Repetitions
+///
+/// To repeat a pattern a fixed number of times without an iterator, you can
+/// use `$(repeat <count> => <quoted>)`, where `<count>` is a `usize`. Like
+/// [loops](#loops) it also supports braces instead of the arrow and an
+/// optional `join (<quoted>)`.
+///
+/// The current repetition, starting at zero, is available to `<quoted>` as
+/// `index`.
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let columns = 3;
+///
+/// let tokens: Tokens<()> = quote! {
+///     INSERT INTO t VALUES ($(repeat columns join (, ) => ?));
+///     $(repeat columns join (, ) => $$$(index + 1))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "INSERT INTO t VALUES (?, ?, ?);",
+///         "$1, $2, $3",
+///     ],
+///     tokens.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// <br>
+///
 /// # Conditionals
 ///
 /// You can specify a conditional with `$(if <pattern> => <then>)` where
@@ -529,6 +796,24 @@
 ///
 /// <br>
 ///
+/// The compact arrow form can also be used for both branches, like `$(if
+/// <pattern> => <then> else => <else>)`. Everything up until the `else =>` is
+/// part of `<then>`.
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// fn visibility(public: bool) -> Tokens<()> {
+///     quote!($(if public => pub fn else => fn) run())
+/// }
+///
+/// assert_eq!("pub fn run()", visibility(true).to_string()?);
+/// assert_eq!("fn run()", visibility(false).to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// <br>
+///
 /// The `<else>` branch is optional, conditionals which do not have an else
 /// branch and evaluated to `false` won't produce any tokens:
 ///
@@ -629,6 +914,57 @@
 ///
 /// <br>
 ///
+/// # Once Blocks
+///
+/// `$(once <key> => <quoted>)` or `$(once <key> { <quoted> })` emits
+/// `<quoted>` only the first time a block with the same `<key>` is seen while
+/// formatting. This is useful for helper definitions which are needed by many
+/// fragments, but should only be defined once in the output.
+///
+/// `<key>` is anything which can be converted into an [ItemStr], and the set
+/// of seen keys is kept for the duration of a single formatting session, like
+/// one call to [to_file_string].
+///
+/// [ItemStr]: crate::tokens::ItemStr
+/// [to_file_string]: crate::Tokens::to_file_string
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// fn abs(value: &str) -> js::Tokens {
+///     quote! {
+///         $(once "abs" {
+///             function abs(v) {
+///                 return v < 0 ? -v : v;
+///             }
+///         })
+///
+///         console.log(abs($value));
+///     }
+/// }
+///
+/// let tokens: js::Tokens = quote! {
+///     $(abs("a"))
+///     $(abs("b"))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "function abs(v) {",
+///         "    return v < 0 ? -v : v;",
+///         "}",
+///         "",
+///         "console.log(abs(a));",
+///         "",
+///         "console.log(abs(b));",
+///     ],
+///     tokens.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// <br>
+///
 /// # Scopes
 ///
 /// You can use `$(ref <binding> { <expr> })` to gain access to the current
@@ -658,6 +994,149 @@
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
 ///
+/// The binding is a mutable reborrow of the current token stream, so it can be
+/// passed on to [quote_in!] either directly or as `*binding`. Scopes can be
+/// nested, and since the binding doesn't take ownership of anything, captured
+/// state remains accessible at every level. Use `$(ref _ => <expr>)` to run
+/// code without binding the token stream at all.
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let fields = [("a", "u32"), ("b", "String")];
+/// let mut count = 0;
+///
+/// let tokens: rust::Tokens = quote! {
+///     struct Foo {
+///         $(ref toks => for (name, ty) in fields {
+///             quote_in! { *toks =>
+///                 $name: $(ref inner {
+///                     count += 1;
+///                     quote_in!(inner => $ty);
+///                 }),
+///             }
+///
+///             toks.push();
+///         })
+///     }
+///     $(ref _ => count += 1)
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "struct Foo {",
+///         "    a: u32,",
+///         "    b: String,",
+///         "}",
+///     ],
+///     tokens.to_file_vec()?
+/// );
+///
+/// assert_eq!(count, 3);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// <br>
+///
+/// # Raw Output
+///
+/// You can use `$(raw <expr>)` to embed a string verbatim, where `<expr>` is
+/// anything implementing `AsRef<str>`. Whitespace inside of the string is
+/// preserved and lines after the first are not indented. This is useful for
+/// embedding pre-formatted snippets. See [tokens::raw()] for details.
+///
+/// Content which is a valid expression on its own, other than a binary
+/// operation like `raw &sql`, is evaluated as one. So `$(raw(sql))` calls a
+/// function named `raw` rather than embedding `sql`, use `$(raw sql)` instead.
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let sql = "SELECT id,\n       name\n  FROM users";
+///
+/// let tokens: Tokens = quote! {
+///     query {
+///         $(raw sql)
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "query {",
+///         "    SELECT id,",
+///         "       name",
+///         "  FROM users",
+///         "}",
+///     ],
+///     tokens.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// If the lines should instead follow the current indentation while keeping
+/// their own leading whitespace on top of it, use `$(verbatim <expr>)`. See
+/// [tokens::verbatim()] for details. Like with `raw`, `$(verbatim(sql))` calls
+/// a function named `verbatim`.
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let sql = "SELECT id,\n       name\n  FROM users";
+///
+/// let tokens: Tokens = quote! {
+///     query {
+///         $(verbatim sql)
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "query {",
+///         "    SELECT id,",
+///         "           name",
+///         "      FROM users",
+///         "}",
+///     ],
+///     tokens.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// <br>
+///
+/// # Debugging
+///
+/// `$(debug <expr>)` is evaluated the same as `$(<expr>)`, but when the
+/// `GENCO_DEBUG` environment variable is set the fragment it produces is
+/// printed to stderr together with the source expression and its location,
+/// like `[src/main.rs:10] body = "return 42;"`. Like with `raw`,
+/// `$(debug(body))` calls a function named `debug`.
+///
+/// Logging requires the `std` feature and only happens in builds with debug
+/// assertions enabled, so it is a no-op in release builds.
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let body: rust::Tokens = quote!(return 42;);
+///
+/// let tokens: rust::Tokens = quote! {
+///     fn answer() -> u32 {
+///         $(debug body)
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "fn answer() -> u32 {",
+///         "    return 42;",
+///         "}",
+///     ],
+///     tokens.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
 /// <br>
 ///
 /// ## Whitespace Detection
@@ -869,9 +1348,9 @@ pub use genco_macros::quote_fn;
 /// This macro takes a destination stream followed by an `=>` and the tokens to
 /// extend that stream with.
 ///
-/// Note that the `<target>` arguments must be borrowable. So a mutable
-/// reference like `&mut rust::Tokens` will have to be dereferenced when used
-/// with this macro.
+/// The `<target>` can either be a mutably borrowable place, like a local
+/// variable or a struct field, or an expression which evaluates to a
+/// `&mut Tokens<L>`, like a mutable reference or a method call.
 ///
 /// ```
 /// # use genco::prelude::*;
@@ -883,11 +1362,26 @@ pub use genco_macros::quote_fn;
 /// # }
 ///
 /// fn generate_into(tokens: &mut rust::Tokens) {
-///     quote_in! { *tokens =>
+///     quote_in! { tokens =>
 ///         hello...
 ///         world!
 ///     };
 /// }
+///
+/// struct Generator {
+///     buf: rust::Tokens,
+/// }
+///
+/// impl Generator {
+///     fn buf(&mut self) -> &mut rust::Tokens {
+///         &mut self.buf
+///     }
+///
+///     fn generate(&mut self) {
+///         quote_in!(self.buf => hello);
+///         quote_in!(self.buf() => world);
+///     }
+/// }
 /// ```
 ///
 /// # Example
@@ -931,9 +1425,14 @@ pub use genco_macros::quote_in;
 
 #[macro_use]
 mod macros;
+#[cfg(feature = "std")]
+pub mod file_set;
 pub mod fmt;
 pub mod lang;
 pub mod prelude;
 pub mod tokens;
 
+#[cfg(feature = "std")]
+pub use self::file_set::FileSet;
+
 pub use self::tokens::Tokens;
@@ -22,6 +22,10 @@
 ///         type Item = Any;
 ///         type Format = Format;
 ///
+///         fn default_config() -> Self::Config {
+///             Config::default()
+///         }
+///
 ///         fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
 ///             genco::lang::c_family_write_quoted(out, input)
 ///         }
@@ -161,25 +165,25 @@ macro_rules! impl_lang {
         $(
             impl $crate::tokens::FormatInto<$lang> for $ty {
                 fn format_into(self, tokens: &mut $crate::Tokens<$lang>) {
-                    tokens.append($crate::tokens::__lang_item::<$lang>(Box::new(self.into())));
+                    tokens.append($crate::tokens::__lang_item::<$lang>(self.into()));
                 }
             }
 
             impl<'a> $crate::tokens::FormatInto<$lang> for &'a $ty {
                 fn format_into(self, tokens: &mut $crate::Tokens<$lang>) {
-                    tokens.append($crate::tokens::__lang_item::<$lang>(Box::new(self.clone().into())));
+                    tokens.append($crate::tokens::__lang_item::<$lang>(self.clone().into()));
                 }
             }
 
             impl $crate::tokens::Register<$lang> for $ty {
                 fn register(self, tokens: &mut $crate::Tokens<$lang>) {
-                    tokens.append($crate::tokens::__lang_item_register::<$lang>(Box::new(self.into())));
+                    tokens.append($crate::tokens::__lang_item_register::<$lang>(self.into()));
                 }
             }
 
             impl<'a> $crate::tokens::Register<$lang> for &'a $ty {
                 fn register(self, tokens: &mut $crate::Tokens<$lang>) {
-                    tokens.append($crate::tokens::__lang_item_register::<$lang>(Box::new(self.clone().into())));
+                    tokens.append($crate::tokens::__lang_item_register::<$lang>(self.clone().into()));
                 }
             }
 
@@ -189,3 +193,104 @@ macro_rules! impl_lang {
         )*
     }
 }
+
+/// Assert that a token stream formats as a file into the expected output.
+///
+/// The expected output is a string, where a single leading line break is
+/// ignored so that it can start on the line after the opening quote. On
+/// mismatch this panics with the first line which differs, followed by a
+/// line-by-line diff where `-` marks expected and `+` marks actual lines.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let map = rust::import("std::collections", "HashMap");
+///
+/// let tokens: rust::Tokens = quote! {
+///     fn build() -> $(&map)<u32, u32> {
+///         $map::new()
+///     }
+/// };
+///
+/// genco::assert_tokens!(tokens, "
+/// use std::collections::HashMap;
+///
+/// fn build() -> HashMap<u32, u32> {
+///     HashMap::new()
+/// }
+/// ");
+/// ```
+///
+/// A mismatch reports the first differing line:
+///
+/// ```should_panic
+/// use genco::prelude::*;
+///
+/// let tokens: Tokens = quote! {
+///     fn foo() {
+///         bar();
+///     }
+/// };
+///
+/// // panics with: tokens differ from expected output at line 2
+/// genco::assert_tokens!(tokens, "
+/// fn foo() {
+///     baz();
+/// }
+/// ");
+/// ```
+#[macro_export]
+macro_rules! assert_tokens {
+    ($tokens:expr, $expected:expr $(,)?) => {
+        $crate::tokens::__assert_tokens(&$tokens, $expected)
+    };
+}
+
+/// Implement serialization for the type-erased `Any` item of a language
+/// declared through [impl_lang!], which serializes like an enum named `Any`
+/// with one variant for each kind of item.
+///
+/// This can't be derived by [impl_lang!] itself, since it would require the
+/// items of every language declared through it to be serializable.
+#[cfg(feature = "serde")]
+macro_rules! impl_serde_any {
+    ($($ty:ident),* $(,)?) => {
+        impl serde::Serialize for Any {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                #[derive(serde::Serialize)]
+                #[serde(rename = "Any")]
+                enum AnyRef<'a> {
+                    $($ty(&'a $ty),)*
+                }
+
+                let any = match self {
+                    $(Self::$ty(item) => AnyRef::$ty(item),)*
+                };
+
+                any.serialize(serializer)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for Any {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                #[derive(serde::Deserialize)]
+                #[serde(rename = "Any")]
+                enum AnyOwned {
+                    $($ty($ty),)*
+                }
+
+                Ok(match AnyOwned::deserialize(deserializer)? {
+                    $(AnyOwned::$ty(item) => Self::$ty(item),)*
+                })
+            }
+        }
+    };
+}
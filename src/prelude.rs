@@ -1,5 +1,5 @@
 //! Prelude containing typical things to import when using the library.
 
 pub use crate::lang::*;
-pub use crate::tokens::{display, quoted, register, FormatInto};
+pub use crate::tokens::{display, quoted, quoted_char, register, FormatInto};
 pub use crate::{quote, quote_fn, quote_in, Tokens};
@@ -0,0 +1,69 @@
+use crate::lang::Lang;
+use crate::tokens::FormatInto;
+use crate::Tokens;
+
+/// Concatenate the given parts using the string concatenation operator of the
+/// target language.
+///
+/// The concatenation is rendered by [Lang::concat], which by default separates
+/// the parts with [Lang::concat_operator]. This defaults to `+`.
+///
+/// C has no such operator, so the parts are placed next to each other like
+/// `"a" "b"`, which the compiler joins into one string. This is only valid if
+/// every part is a string literal, or a macro which expands to one.
+///
+/// [Lang::concat]: crate::lang::Lang::concat
+/// [Lang::concat_operator]: crate::lang::Lang::concat_operator
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::concat;
+///
+/// let js: js::Tokens = quote!(const greeting = $(concat([quote!("Hello, "), quote!(name)])););
+/// assert_eq!("const greeting = \"Hello, \" + name;", js.to_string()?);
+///
+/// let elixir: elixir::Tokens = quote!(greeting = $(concat([quote!("Hello, "), quote!(name)])));
+/// assert_eq!("greeting = \"Hello, \" <> name", elixir.to_string()?);
+///
+/// let r: r::Tokens = quote!(greeting <- $(concat([quote!("Hello, "), quote!(name)])));
+/// assert_eq!("greeting <- paste0(\"Hello, \", name)", r.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn concat<I>(parts: I) -> Concat<I>
+where
+    I: IntoIterator,
+{
+    Concat { parts }
+}
+
+/// Parts concatenated using the string concatenation operator of the target
+/// language.
+///
+/// This is constructed with the [concat()] function.
+#[derive(Clone, Copy)]
+pub struct Concat<I> {
+    parts: I,
+}
+
+impl<I, L> FormatInto<L> for Concat<I>
+where
+    L: Lang,
+    I: IntoIterator,
+    I::Item: FormatInto<L>,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        let parts = self
+            .parts
+            .into_iter()
+            .map(|part| {
+                let mut t = Tokens::new();
+                t.append(part);
+                t
+            })
+            .collect();
+
+        L::concat(tokens, parts);
+    }
+}
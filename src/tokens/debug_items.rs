@@ -0,0 +1,63 @@
+use core::fmt;
+
+use crate::lang::Lang;
+use crate::tokens::Item;
+
+/// A readable view of the items in a token stream.
+///
+/// Created through [Tokens::debug_items].
+///
+/// [Tokens::debug_items]: crate::Tokens::debug_items
+pub struct DebugItems<'a, L>
+where
+    L: Lang,
+{
+    items: &'a [Item<L>],
+}
+
+impl<'a, L> DebugItems<'a, L>
+where
+    L: Lang,
+{
+    pub(crate) fn new(items: &'a [Item<L>]) -> Self {
+        Self { items }
+    }
+}
+
+impl<L> fmt::Debug for DebugItems<'_, L>
+where
+    L: Lang,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut it = self.items.iter().peekable();
+
+        while let Some(item) = it.next() {
+            match item {
+                Item::Literal(literal) => write!(f, "{:?}", &**literal)?,
+                Item::Raw(raw) => write!(f, "<raw {:?}>", &**raw)?,
+                Item::Lang(_, item) => write!(f, "<lang {:?}>", item)?,
+                Item::Register(_, item) => write!(f, "<register {:?}>", item)?,
+                Item::Push => f.write_str("<push>")?,
+                Item::Line => f.write_str("<line>")?,
+                Item::Space => f.write_str("<space>")?,
+                Item::Indentation(n) if *n > 0 => write!(f, "<indent {}>", n)?,
+                Item::Indentation(n) => write!(f, "<unindent {}>", -n)?,
+                Item::OpenQuote(false) => f.write_str("<open-quote>")?,
+                Item::OpenQuote(true) => f.write_str("<open-quote eval>")?,
+                Item::CloseQuote => f.write_str("<close-quote>")?,
+                Item::OpenEval => f.write_str("<open-eval>")?,
+                Item::CloseEval => f.write_str("<close-eval>")?,
+                Item::Source(file, line) => write!(f, "<source {}:{}>", &**file, line)?,
+                Item::OpenOnce(key) => write!(f, "<open-once {:?}>", &**key)?,
+                Item::CloseOnce => f.write_str("<close-once>")?,
+                Item::Char(c) => write!(f, "<char {:?}>", c)?,
+            }
+
+            if it.peek().is_some() {
+                f.write_str(" ")?;
+            }
+        }
+
+        Ok(())
+    }
+}
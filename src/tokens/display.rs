@@ -1,7 +1,8 @@
 use crate::lang::Lang;
 use crate::tokens::{FormatInto, Item};
 use crate::Tokens;
-use std::fmt;
+use alloc::string::ToString;
+use core::fmt;
 
 /// Function to build a string literal.
 ///
@@ -0,0 +1,219 @@
+use crate::lang::Lang;
+use crate::tokens::{FormatInto, ItemStr};
+use crate::Tokens;
+
+/// The associativity of a binary operator.
+///
+/// This decides which operand needs to be parenthesized when it uses an
+/// operator of the same precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Assoc {
+    /// Left-associative, like `+` where `a + b + c` is `(a + b) + c`.
+    Left,
+    /// Right-associative, like `=` where `a = b = c` is `a = (b = c)`.
+    Right,
+    /// Non-associative, like comparisons in Rust where `a == b == c` is not
+    /// permitted.
+    None,
+}
+
+/// An expression which keeps track of the precedence of its outermost
+/// operator, so that it can be parenthesized as needed when used as an
+/// operand.
+///
+/// Expressions are constructed with [Expr::new] for operands which never need
+/// parenthesis, like identifiers or function calls, and combined with
+/// [bin_op()] or [Expr::binary].
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::{bin_op, Expr};
+///
+/// let sum = bin_op(quote!(b), "+", quote!(c));
+/// let product: Expr<Rust> = bin_op(quote!(a), "*", sum);
+///
+/// assert_eq!("a * (b + c)", quote!($product).to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Expr<L>
+where
+    L: Lang,
+{
+    tokens: Tokens<L>,
+    precedence: u8,
+}
+
+impl<L> Expr<L>
+where
+    L: Lang,
+{
+    /// Construct an expression out of the given tokens, which are treated as
+    /// binding tighter than any operator.
+    pub fn new<T>(tokens: T) -> Self
+    where
+        T: FormatInto<L>,
+    {
+        let mut t = Tokens::new();
+        t.append(tokens);
+
+        Self {
+            tokens: t,
+            precedence: u8::MAX,
+        }
+    }
+
+    /// Combine two expressions using an operator with an explicit
+    /// `precedence` and `assoc`iativity.
+    ///
+    /// Higher precedences bind tighter, and operands are parenthesized if they
+    /// bind looser than the operator. A precedence of `u8::MAX` is reserved
+    /// for expressions constructed with [Expr::new].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::tokens::{Assoc, Expr};
+    ///
+    /// let pipe = |lhs, rhs| Expr::<Rust>::binary(lhs, "|>", 1, Assoc::Left, rhs);
+    ///
+    /// let left = pipe(pipe(Expr::new("a"), Expr::new("b")), Expr::new("c"));
+    /// let right = pipe(Expr::new("a"), pipe(Expr::new("b"), Expr::new("c")));
+    ///
+    /// assert_eq!("a |> b |> c", quote!($left).to_string()?);
+    /// assert_eq!("a |> (b |> c)", quote!($right).to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn binary<A, B, O>(lhs: A, op: O, precedence: u8, assoc: Assoc, rhs: B) -> Self
+    where
+        A: Into<Expr<L>>,
+        B: Into<Expr<L>>,
+        O: Into<ItemStr>,
+    {
+        let lhs = lhs.into();
+        let rhs = rhs.into();
+
+        let lhs_parens =
+            lhs.precedence < precedence || (lhs.precedence == precedence && assoc != Assoc::Left);
+        let rhs_parens =
+            rhs.precedence < precedence || (rhs.precedence == precedence && assoc != Assoc::Right);
+
+        let mut tokens = Tokens::new();
+        tokens.append_wrapped(lhs.tokens, lhs_parens);
+        tokens.space();
+        tokens.append(op.into());
+        tokens.space();
+        tokens.append_wrapped(rhs.tokens, rhs_parens);
+
+        Self { tokens, precedence }
+    }
+
+    /// Get the precedence of the outermost operator in the expression.
+    pub fn precedence(&self) -> u8 {
+        self.precedence
+    }
+}
+
+impl<L> From<Tokens<L>> for Expr<L>
+where
+    L: Lang,
+{
+    fn from(tokens: Tokens<L>) -> Self {
+        Self::new(tokens)
+    }
+}
+
+impl<L> From<&'static str> for Expr<L>
+where
+    L: Lang,
+{
+    fn from(value: &'static str) -> Self {
+        Self::new(ItemStr::Static(value))
+    }
+}
+
+impl<L> FormatInto<L> for Expr<L>
+where
+    L: Lang,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        tokens.append(self.tokens);
+    }
+}
+
+impl<L> FormatInto<L> for &Expr<L>
+where
+    L: Lang,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        tokens.append_ref(&self.tokens);
+    }
+}
+
+/// Combine two expressions with the binary operator `op`, parenthesizing the
+/// operands as needed according to the operator precedence of the target
+/// language.
+///
+/// Precedence is provided by [Lang::operator_precedence]. Operands of
+/// operators which are not known to the language are parenthesized unless
+/// they were constructed with [Expr::new].
+///
+/// [Lang::operator_precedence]: crate::lang::Lang::operator_precedence
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::{bin_op, Expr};
+///
+/// let sum = bin_op(bin_op(quote!(a), "+", quote!(b)), "+", quote!(c));
+/// let product = bin_op(quote!(a), "*", bin_op(quote!(b), "+", quote!(c)));
+/// let difference = bin_op(quote!(a), "-", bin_op(quote!(b), "-", quote!(c)));
+///
+/// let toks: js::Tokens = quote! {
+///     $sum;
+///     $product;
+///     $difference;
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "a + b + c;",
+///         "a * (b + c);",
+///         "a - (b - c);",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn bin_op<L, A, B>(lhs: A, op: &'static str, rhs: B) -> Expr<L>
+where
+    L: Lang,
+    A: Into<Expr<L>>,
+    B: Into<Expr<L>>,
+{
+    let op = ItemStr::Static(op);
+
+    if let Some((precedence, assoc)) = L::operator_precedence(&op) {
+        return Expr::binary(lhs, op, precedence, assoc, rhs);
+    }
+
+    Expr::binary(grouped(lhs.into()), op, 0, Assoc::None, grouped(rhs.into()))
+}
+
+/// Parenthesize the expression unless it is already an operand.
+fn grouped<L>(expr: Expr<L>) -> Expr<L>
+where
+    L: Lang,
+{
+    if expr.precedence == u8::MAX {
+        return expr;
+    }
+
+    let mut tokens = Tokens::new();
+    tokens.append_wrapped(expr.tokens, true);
+    Expr::new(tokens)
+}
@@ -1,7 +1,10 @@
 use crate::lang::Lang;
 use crate::tokens::{Item, ItemStr, Tokens};
-use std::fmt::Arguments;
-use std::rc::Rc;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt::Arguments;
 
 /// Trait for types that can be formatted in-place into a token stream.
 ///
@@ -51,7 +54,7 @@ where
     L: Lang,
 {
     fn format_into(self, tokens: &mut Self) {
-        tokens.extend(self);
+        tokens.append_tokens(self);
     }
 }
 
@@ -70,12 +73,45 @@ where
 /// assert_eq!("foo bar baz", result.to_string()?);
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
-impl<'a, L> FormatInto<L> for &'a Tokens<L>
+impl<L> FormatInto<L> for &Tokens<L>
 where
     L: Lang,
 {
     fn format_into(self, tokens: &mut Tokens<L>) {
-        tokens.extend(self.iter().cloned());
+        tokens.append_ref(self);
+    }
+}
+
+/// Formatting a reference to a reference is the same as formatting the inner
+/// reference.
+///
+/// This allows borrowed values to be interpolated with `$(&value)` or be
+/// iterated over by reference without having to be dereferenced first.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let foo: Tokens = quote!(foo);
+/// let bar: Tokens = quote!(bar);
+///
+/// let parts: Vec<&Tokens> = vec![&foo, &bar];
+/// let first = parts[0];
+///
+/// let result = quote!($(&first) $(for p in &parts join ( ) => $p));
+///
+/// assert_eq!("foo foo bar", result.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+impl<'a, L, T> FormatInto<L> for &&'a T
+where
+    L: Lang,
+    T: ?Sized,
+    &'a T: FormatInto<L>,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        (*self).format_into(tokens);
     }
 }
 
@@ -108,6 +144,30 @@ where
     }
 }
 
+/// Formatting a borrowed vector is the same as formatting it as a slice.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let vec = vec![quote!(foo), quote!($[' ']bar)];
+///
+/// let result: Tokens = quote!($(&vec) baz $(&vec));
+///
+/// assert_eq!("foo bar baz foo bar", result.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+impl<L, T> FormatInto<L> for &Vec<T>
+where
+    L: Lang,
+    T: Clone + FormatInto<L>,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        self.as_slice().format_into(tokens);
+    }
+}
+
 /// Formatting a slice of token streams is like formatting each, one after
 /// another.
 ///
@@ -126,7 +186,7 @@ where
 /// assert_eq!("foo bar baz", result.to_string()?);
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
-impl<'a, L, T> FormatInto<L> for &'a [T]
+impl<L, T> FormatInto<L> for &[T]
 where
     L: Lang,
     T: Clone + FormatInto<L>,
@@ -153,7 +213,7 @@ where
 /// assert_eq!("foo bar baz", result.to_string()?);
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
-impl<'a, L> FormatInto<L> for &'a str
+impl<L> FormatInto<L> for &str
 where
     L: Lang,
 {
@@ -177,7 +237,7 @@ where
 /// assert_eq!("foo bar baz", result.to_string()?);
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
-impl<'a, L> FormatInto<L> for &'a String
+impl<L> FormatInto<L> for &String
 where
     L: Lang,
 {
@@ -253,7 +313,7 @@ where
 /// assert_eq!("foo bar baz", result.to_string()?);
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
-impl<'a, L> FormatInto<L> for &'a Rc<String>
+impl<L> FormatInto<L> for &Rc<String>
 where
     L: Lang,
 {
@@ -285,6 +345,113 @@ where
     }
 }
 
+/// A character is formatted as a literal, as-is.
+///
+/// This is distinct from [quoted_char()], which formats the character as a
+/// quoted character literal in the target language.
+///
+/// [quoted_char()]: crate::tokens::quoted_char()
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let c = 'x';
+/// let result: rust::Tokens = quote!($c $(&c) $(quoted_char(c)));
+///
+/// assert_eq!("x x 'x'", result.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+impl<L> FormatInto<L> for char
+where
+    L: Lang,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        let mut buf = [0u8; 4];
+        tokens.item(Item::Literal(ItemStr::from(&*self.encode_utf8(&mut buf))));
+    }
+}
+
+/// A borrowed character is formatted the same as a character.
+impl<L> FormatInto<L> for &char
+where
+    L: Lang,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        (*self).format_into(tokens);
+    }
+}
+
+/// A path is formatted as a literal, using `/` as the separator regardless of
+/// the host platform.
+///
+/// The components of the path are joined with `/`, so only the separators of
+/// the host platform are converted. A `\` is a separator on Windows, but it's
+/// a regular character in file names on other platforms where it's kept
+/// as-is. Paths which are not valid UTF-8 are converted lossily.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use std::path::{Path, PathBuf};
+///
+/// let a = Path::new("src/lib.rs");
+/// let b = PathBuf::from("src").join("lang").join("rust.rs");
+///
+/// let result: Tokens = quote!($a $(&b) $b);
+///
+/// assert_eq!("src/lib.rs src/lang/rust.rs src/lang/rust.rs", result.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+#[cfg(feature = "std")]
+impl<L> FormatInto<L> for &std::path::Path
+where
+    L: Lang,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        use std::path::Component;
+
+        let mut path = String::new();
+
+        for component in self.components() {
+            if !path.is_empty() && !path.ends_with('/') {
+                path.push('/');
+            }
+
+            match component {
+                Component::RootDir => path.push('/'),
+                component => path.push_str(&component.as_os_str().to_string_lossy()),
+            }
+        }
+
+        tokens.item(Item::Literal(ItemStr::from(path)));
+    }
+}
+
+/// A borrowed path buffer is formatted the same as a path.
+#[cfg(feature = "std")]
+impl<L> FormatInto<L> for &std::path::PathBuf
+where
+    L: Lang,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        self.as_path().format_into(tokens);
+    }
+}
+
+/// A path buffer is formatted the same as a path.
+#[cfg(feature = "std")]
+impl<L> FormatInto<L> for std::path::PathBuf
+where
+    L: Lang,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        self.as_path().format_into(tokens);
+    }
+}
+
 /// Optional items are formatted if they are present.
 ///
 /// # Examples
@@ -314,6 +481,34 @@ where
     }
 }
 
+/// Formatting a borrowed optional value formats the borrowed value if it is
+/// present.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let foo: Option<Tokens> = Some(quote!(foo));
+/// let bar: Option<Tokens> = None;
+///
+/// let result: Tokens = quote!($(&foo) $(&bar) baz $(&foo));
+///
+/// assert_eq!("foo baz foo", result.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+impl<'a, L, T> FormatInto<L> for &'a Option<T>
+where
+    L: Lang,
+    &'a T: FormatInto<L>,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        if let Some(inner) = self {
+            inner.format_into(tokens);
+        }
+    }
+}
+
 macro_rules! impl_display {
     ($($ty:ty),*) => {
         $(
@@ -328,6 +523,17 @@ macro_rules! impl_display {
                     tokens.append(self.to_string());
                 }
             }
+
+            /// Implementation for a borrowed primitive type, which is
+            /// formatted the same as the primitive type.
+            impl<L> FormatInto<L> for &$ty
+            where
+                L: Lang,
+            {
+                fn format_into(self, tokens: &mut Tokens<L>) {
+                    tokens.append(self.to_string());
+                }
+            }
         )*
     };
 }
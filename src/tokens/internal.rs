@@ -1,17 +1,23 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp;
+use core::fmt::Write as _;
+
 use crate::lang::Lang;
-use crate::tokens::{from_fn, FormatInto};
+use crate::tokens::{from_fn, FormatInto, Item, ItemStr, Tokens};
 
 /// Add a language item directly.
 ///
 /// This must only be used by the [impl_lang!] macro.
 ///
 /// [impl_lang!]: crate::impl_lang!
-pub fn __lang_item<L>(item: Box<L::Item>) -> impl FormatInto<L>
+pub fn __lang_item<L>(item: L::Item) -> impl FormatInto<L>
 where
     L: Lang,
 {
     from_fn(|t| {
-        t.lang_item(item);
+        t.lang_item(Box::new(item));
     })
 }
 
@@ -20,11 +26,271 @@ where
 /// This must only be used by the [impl_lang!] macro.
 ///
 /// [impl_lang!]: crate::impl_lang!
-pub fn __lang_item_register<L>(item: Box<L::Item>) -> impl FormatInto<L>
+pub fn __lang_item_register<L>(item: L::Item) -> impl FormatInto<L>
 where
     L: Lang,
 {
     from_fn(|t| {
-        t.lang_item_register(item);
+        t.lang_item_register(Box::new(item));
+    })
+}
+
+/// Evaluate `value` into the token stream, logging the produced fragment to
+/// stderr if the `GENCO_DEBUG` environment variable is set.
+///
+/// Logging only happens with the `std` feature in builds with debug
+/// assertions enabled, otherwise this is the same as appending `value`.
+///
+/// This must only be used by the `$(debug <expr>)` syntax of [quote!].
+///
+/// [quote!]: crate::quote!
+pub fn __debug<L, T>(
+    source: &'static str,
+    file: &'static str,
+    line: u32,
+    value: T,
+) -> impl FormatInto<L>
+where
+    L: Lang,
+    T: FormatInto<L>,
+{
+    from_fn(move |t| {
+        if !debug_enabled() {
+            t.append(value);
+            return;
+        }
+
+        let mut fragment = Tokens::new();
+        fragment.append(value);
+
+        match fragment.to_string() {
+            Ok(output) => debug_log(file, line, source, &output),
+            Err(e) => debug_log(file, line, source, &alloc::format!("<error: {}>", e)),
+        }
+
+        t.append(fragment);
     })
 }
+
+/// Mark the items which follow as coming from the given file and line, if
+/// sources are being recorded through `tokens::record_sources`.
+///
+/// This must only be used by [quote!].
+///
+/// [quote!]: crate::quote!
+pub fn __source<L>(file: &'static str, line: u32) -> impl FormatInto<L>
+where
+    L: Lang,
+{
+    from_fn(move |t| {
+        if recording() {
+            t.item(Item::Source(ItemStr::Static(file), line));
+        }
+    })
+}
+
+/// Open a block which is only formatted the first time `key` is seen.
+///
+/// This must only be used by the `$(once <key> => <quoted>)` syntax of
+/// [quote!].
+///
+/// [quote!]: crate::quote!
+pub fn __open_once<L, K>(key: K) -> impl FormatInto<L>
+where
+    L: Lang,
+    K: Into<ItemStr>,
+{
+    from_fn(move |t| {
+        t.item(Item::OpenOnce(key.into()));
+    })
+}
+
+/// Close a block opened with [__open_once].
+///
+/// This must only be used by the `$(once <key> => <quoted>)` syntax of
+/// [quote!].
+///
+/// [quote!]: crate::quote!
+pub fn __close_once<L>() -> impl FormatInto<L>
+where
+    L: Lang,
+{
+    from_fn(|t| {
+        t.item(Item::CloseOnce);
+    })
+}
+
+/// Append `tokens` with all whitespace between them removed, so that they
+/// are fused together.
+///
+/// This must only be used by the `$(concat <quoted>)` syntax of [quote!].
+///
+/// [quote!]: crate::quote!
+pub fn __concat<L>(tokens: Tokens<L>) -> impl FormatInto<L>
+where
+    L: Lang,
+{
+    from_fn(move |t| {
+        t.extend(tokens.into_iter().filter(|item| {
+            !matches!(
+                item,
+                Item::Space | Item::Push | Item::Line | Item::Indentation(..)
+            )
+        }));
+    })
+}
+
+/// Mark where the first iteration of a loop starts, so that the number of
+/// items it produces can be passed to [__loop_reserve].
+///
+/// This must only be used by the `$(for <bindings> in <expr>)` syntax of
+/// [quote!].
+///
+/// [quote!]: crate::quote!
+pub fn __loop_start<L>(tokens: &Tokens<L>) -> usize
+where
+    L: Lang,
+{
+    tokens.len()
+}
+
+/// Reserve room for the remaining iterations of a loop, assuming that each of
+/// them produces as many items as the first one which started at `start`.
+///
+/// Nothing is reserved unless the iterator has an upper bound, so that
+/// unbounded iterators don't reserve based on a saturated lower bound.
+///
+/// This must only be used by the `$(for <bindings> in <expr>)` syntax of
+/// [quote!].
+///
+/// [quote!]: crate::quote!
+pub fn __loop_reserve<L>(tokens: &mut Tokens<L>, start: usize, hint: (usize, Option<usize>))
+where
+    L: Lang,
+{
+    if let (remaining, Some(_)) = hint {
+        let per_iteration = tokens.len().saturating_sub(start);
+        tokens.reserve(per_iteration.saturating_mul(remaining));
+    }
+}
+
+/// Collect the items of an iterator sorted by their natural order.
+///
+/// This must only be used by the `$(for <bindings> in <expr> sorted)` syntax
+/// of [quote!].
+///
+/// [quote!]: crate::quote!
+pub fn __sorted<I>(iter: I) -> alloc::vec::IntoIter<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Ord,
+{
+    let mut items = iter.into_iter().collect::<Vec<_>>();
+    items.sort();
+    items.into_iter()
+}
+
+/// Collect the pairs of an iterator sorted by their key, like the entries of a
+/// `HashMap`.
+///
+/// This must only be used by the `$(for (<key>, <value>) in <expr> sorted)`
+/// syntax of [quote!].
+///
+/// [quote!]: crate::quote!
+pub fn __sorted_by_key<I, K, V>(iter: I) -> alloc::vec::IntoIter<(K, V)>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Ord,
+{
+    let mut items = iter.into_iter().collect::<Vec<_>>();
+    items.sort_by(|a, b| a.0.cmp(&b.0));
+    items.into_iter()
+}
+
+#[cfg(feature = "sourcemap")]
+fn recording() -> bool {
+    crate::tokens::sources::recording()
+}
+
+#[cfg(not(feature = "sourcemap"))]
+fn recording() -> bool {
+    false
+}
+
+#[cfg(feature = "std")]
+fn debug_enabled() -> bool {
+    cfg!(debug_assertions) && std::env::var_os("GENCO_DEBUG").is_some()
+}
+
+#[cfg(not(feature = "std"))]
+fn debug_enabled() -> bool {
+    false
+}
+
+#[cfg(feature = "std")]
+fn debug_log(file: &str, line: u32, source: &str, output: &str) {
+    std::eprintln!("[{}:{}] {} = {:?}", file, line, source, output);
+}
+
+#[cfg(not(feature = "std"))]
+fn debug_log(_: &str, _: u32, _: &str, _: &str) {}
+
+/// Compare the file rendering of a token stream against the expected lines,
+/// panicking with a line-by-line diff if they differ.
+///
+/// This must only be used by the [assert_tokens!] macro.
+///
+/// [assert_tokens!]: crate::assert_tokens!
+#[track_caller]
+pub fn __assert_tokens<L>(tokens: &Tokens<L>, expected: &str)
+where
+    L: Lang,
+{
+    let actual = match tokens.to_file_vec() {
+        Ok(actual) => actual,
+        Err(e) => panic!("failed to format tokens: {}", e),
+    };
+
+    // NB: a leading line break is ignored so that the expected output can
+    // start on the line after the opening quote.
+    let expected = expected.strip_prefix('\n').unwrap_or(expected);
+    let expected = expected.lines().collect::<Vec<_>>();
+
+    let len = cmp::max(actual.len(), expected.len());
+
+    let first =
+        match (0..len).find(|&n| actual.get(n).map(String::as_str) != expected.get(n).copied()) {
+            Some(first) => first,
+            None => return,
+        };
+
+    let mut diff = String::new();
+
+    for n in 0..len {
+        match (expected.get(n), actual.get(n)) {
+            (Some(e), Some(a)) if *e == a => {
+                let _ = writeln!(diff, "  {:>3} | {}", n + 1, e);
+            }
+            (e, a) => {
+                if let Some(e) = e {
+                    let _ = writeln!(diff, "- {:>3} | {}", n + 1, e);
+                }
+
+                if let Some(a) = a {
+                    let _ = writeln!(diff, "+ {:>3} | {}", n + 1, a);
+                }
+            }
+        }
+    }
+
+    panic!(
+        "tokens differ from expected output at line {}\n expected: {:?}\n   actual: {:?}\n\n{}",
+        first + 1,
+        expected.get(first).copied().unwrap_or("<end of output>"),
+        actual
+            .get(first)
+            .map(String::as_str)
+            .unwrap_or("<end of output>"),
+        diff
+    );
+}
@@ -2,6 +2,7 @@
 
 use crate::lang::Lang;
 use crate::tokens::{FormatInto, ItemStr, Tokens};
+use alloc::boxed::Box;
 
 /// A single item in a stream of tokens.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -12,6 +13,11 @@ where
     /// A literal item.
     /// Is added as a raw string to the stream of tokens.
     Literal(ItemStr),
+    /// A raw string which is written verbatim.
+    ///
+    /// Line breaks inside of it are preserved, and any lines after the first
+    /// are not subject to indentation.
+    Raw(ItemStr),
     /// A language-specific item.
     Lang(usize, Box<L::Item>),
     /// A language-specific item that is not rendered.
@@ -44,6 +50,35 @@ where
     OpenEval,
     /// Close evaluation.
     CloseEval,
+    /// The file and line of the [quote!] invocation which produced the items
+    /// that follow.
+    ///
+    /// This is only added when the `sourcemap` feature is enabled and sources
+    /// are being recorded through `tokens::record_sources`, and doesn't affect
+    /// formatting. It is used to build the source map of
+    /// `Tokens::to_file_with_sourcemap`.
+    ///
+    /// [quote!]: crate::quote!
+    Source(ItemStr, u32),
+    /// Open a block which is only formatted the first time the given key is
+    /// seen while formatting.
+    ///
+    /// This is produced by the `$(once <key> => ..)` syntax of [quote!].
+    ///
+    /// [quote!]: crate::quote!
+    OpenOnce(ItemStr),
+    /// Close a block opened with [Item::OpenOnce].
+    CloseOnce,
+    /// A character literal.
+    ///
+    /// The character is escaped at format time with the language-specific
+    /// [Lang::write_char_literal], so that it respects the configuration of
+    /// the formatter.
+    ///
+    /// This is produced by [quoted_char].
+    ///
+    /// [quoted_char]: crate::tokens::quoted_char
+    Char(char),
 }
 
 /// Formatting an item is the same as simply adding that item to the token
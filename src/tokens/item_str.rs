@@ -2,10 +2,13 @@
 
 use crate::lang::Lang;
 use crate::tokens::{FormatInto, Item, Tokens};
-use std::borrow::Cow;
-use std::fmt;
-use std::ops::Deref;
-use std::rc::Rc;
+use alloc::borrow::Cow;
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::String;
+use core::fmt;
+use core::ops::Deref;
 
 /// A managed string that permits immutable borrowing.
 #[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
@@ -26,7 +29,7 @@ where
     }
 }
 
-impl<'a, L> FormatInto<L> for &'a ItemStr
+impl<L> FormatInto<L> for &ItemStr
 where
     L: Lang,
 {
@@ -56,28 +56,110 @@
 //! # }
 //! ```
 
+mod concat;
+mod debug_items;
 mod display;
+mod expr;
 mod format_into;
 mod from_fn;
 mod internal;
 mod item;
 mod item_str;
+#[cfg(feature = "std")]
+mod parse;
 mod quoted;
+mod raw;
 mod register;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "sourcemap")]
+mod sources;
 mod static_literal;
+mod ternary;
 mod tokens;
 
+pub use self::concat::{concat, Concat};
+pub use self::debug_items::DebugItems;
 pub use self::display::{display, Display};
+pub use self::expr::{bin_op, Assoc, Expr};
 pub use self::format_into::FormatInto;
 pub use self::from_fn::{from_fn, FromFn};
 pub use self::item::Item;
 pub use self::item_str::ItemStr;
-pub use self::quoted::{quoted, QuotedFn};
+#[cfg(feature = "std")]
+pub use self::parse::ParseError;
+pub use self::quoted::{quoted, quoted_char, QuotedChar, QuotedFn};
+pub use self::raw::{raw, verbatim, Raw, Verbatim};
 pub use self::register::{register, Register, RegisterFn};
+#[cfg(feature = "sourcemap")]
+pub use self::sources::record_sources;
 pub use self::static_literal::static_literal;
+pub use self::ternary::{ternary, Ternary};
+#[cfg(feature = "std")]
+pub(crate) use self::tokens::write_atomic;
+
 pub use self::tokens::Tokens;
+/// Derive a [FormatInto] implementation for a struct.
+///
+/// Fields are formatted into the token stream in the order in which they are
+/// declared, which requires each of them to implement [FormatInto] for the
+/// target language.
+///
+/// By default fields are separated by a space. This can be configured with the
+/// `#[genco(sep = ...)]` attribute on the struct, which accepts either a string
+/// literal or one of `space`, `push`, or `line`. Individual fields can be left
+/// out with `#[genco(skip)]`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// #[derive(FormatInto)]
+/// #[genco(sep = ": ")]
+/// struct Field {
+///     name: &'static str,
+///     ty: rust::Import,
+///     #[genco(skip)]
+///     #[allow(unused)]
+///     doc: String,
+/// }
+///
+/// let field = Field {
+///     name: "map",
+///     ty: rust::import("std::collections", "HashMap"),
+///     doc: String::from("A map."),
+/// };
+///
+/// let tokens: rust::Tokens = quote!(struct Foo { $field });
+///
+/// assert_eq!(
+///     vec![
+///         "use std::collections::HashMap;",
+///         "",
+///         "struct Foo { map: HashMap }",
+///     ],
+///     tokens.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub use genco_macros::FormatInto;
 
+#[doc(hidden)]
+pub use self::internal::__assert_tokens;
+#[doc(hidden)]
+pub use self::internal::__concat;
+#[doc(hidden)]
+pub use self::internal::__debug;
 #[doc(hidden)]
 pub use self::internal::__lang_item;
 #[doc(hidden)]
 pub use self::internal::__lang_item_register;
+#[doc(hidden)]
+pub use self::internal::__source;
+#[doc(hidden)]
+pub use self::internal::{__close_once, __open_once};
+#[doc(hidden)]
+pub use self::internal::{__loop_reserve, __loop_start};
+#[doc(hidden)]
+pub use self::internal::{__sorted, __sorted_by_key};
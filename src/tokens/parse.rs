@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::error;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::lang::Lang;
+use crate::tokens::ItemStr;
+use crate::Tokens;
+
+/// Error raised when parsing a template through [Tokens::parse].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A `$name` was used which has no binding.
+    MissingBinding {
+        /// The name which is missing a binding.
+        name: String,
+        /// The line the name was used on, starting at 1.
+        line: usize,
+    },
+    /// A `$` was not followed by a name or another `$`.
+    ExpectedName {
+        /// The line of the `$`, starting at 1.
+        line: usize,
+        /// The column of the `$`, starting at 1.
+        column: usize,
+    },
+    /// A line is indented less than the first line, or doesn't match the
+    /// indentation of any enclosing line.
+    Indentation {
+        /// The offending line, starting at 1.
+        line: usize,
+    },
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingBinding { name, line } => {
+                write!(f, "no binding for `${}` (at line {})", name, line)
+            }
+            Self::ExpectedName { line, column } => {
+                write!(
+                    f,
+                    "expected a name or `$` after `$` (at line {}, column {})",
+                    line, column
+                )
+            }
+            Self::Indentation { line } => {
+                write!(f, "unexpected indentation (at line {})", line)
+            }
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+pub(crate) fn parse<L>(
+    template: &str,
+    bindings: &HashMap<String, Tokens<L>>,
+) -> Result<Tokens<L>, ParseError>
+where
+    L: Lang,
+{
+    let mut tokens = Tokens::new();
+    // Indentation levels of the enclosing lines, where the first is the
+    // indentation of the first line.
+    let mut levels = Vec::<usize>::new();
+    let mut blank = false;
+
+    for (n, line) in template.lines().enumerate() {
+        let n = n + 1;
+        let content = line.trim_start();
+
+        if content.trim_end().is_empty() {
+            blank = true;
+            continue;
+        }
+
+        let column = line[..line.len() - content.len()].chars().count();
+
+        match levels.last().copied() {
+            None => {
+                levels.push(column);
+            }
+            Some(current) if column > current => {
+                if blank {
+                    tokens.line();
+                }
+
+                levels.push(column);
+                tokens.indent();
+            }
+            Some(_) => {
+                while levels.len() > 1 && levels.last().map_or(false, |&level| level > column) {
+                    levels.pop();
+                    tokens.unindent();
+                }
+
+                if levels.last() != Some(&column) {
+                    return Err(ParseError::Indentation { line: n });
+                }
+
+                if blank {
+                    tokens.line();
+                } else {
+                    tokens.push();
+                }
+            }
+        }
+
+        blank = false;
+        parse_line(&mut tokens, content.trim_end(), column, n, bindings)?;
+    }
+
+    for _ in levels.iter().skip(1) {
+        tokens.unindent();
+    }
+
+    Ok(tokens)
+}
+
+fn parse_line<L>(
+    tokens: &mut Tokens<L>,
+    content: &str,
+    column: usize,
+    line: usize,
+    bindings: &HashMap<String, Tokens<L>>,
+) -> Result<(), ParseError>
+where
+    L: Lang,
+{
+    let mut literal = String::new();
+    let mut it = content.char_indices().peekable();
+
+    while let Some((n, c)) = it.next() {
+        match c {
+            '$' => match it.peek() {
+                Some(&(_, '$')) => {
+                    it.next();
+                    literal.push('$');
+                }
+                Some(&(start, c)) if c.is_alphabetic() || c == '_' => {
+                    let mut end = start;
+
+                    while let Some(&(e, c)) = it.peek() {
+                        if !c.is_alphanumeric() && c != '_' {
+                            break;
+                        }
+
+                        end = e + c.len_utf8();
+                        it.next();
+                    }
+
+                    let name = &content[start..end];
+
+                    let value = match bindings.get(name) {
+                        Some(value) => value,
+                        None => {
+                            return Err(ParseError::MissingBinding {
+                                name: name.into(),
+                                line,
+                            })
+                        }
+                    };
+
+                    flush(tokens, &mut literal);
+                    tokens.append(value);
+                }
+                _ => {
+                    return Err(ParseError::ExpectedName {
+                        line,
+                        column: column + content[..n].chars().count() + 1,
+                    });
+                }
+            },
+            c if c.is_whitespace() => {
+                while it.peek().map_or(false, |&(_, c)| c.is_whitespace()) {
+                    it.next();
+                }
+
+                flush(tokens, &mut literal);
+                tokens.space();
+            }
+            c => {
+                literal.push(c);
+            }
+        }
+    }
+
+    flush(tokens, &mut literal);
+    Ok(())
+}
+
+fn flush<L>(tokens: &mut Tokens<L>, literal: &mut String)
+where
+    L: Lang,
+{
+    if !literal.is_empty() {
+        tokens.append(ItemStr::from(core::mem::take(literal)));
+    }
+}
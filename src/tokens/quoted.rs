@@ -74,3 +74,45 @@ where
         t.item(Item::CloseQuote);
     }
 }
+
+/// Function to provide a character literal, in the language of choice.
+///
+/// The character is escaped the same way as in [quoted()], with the
+/// additional escaping needed for a character literal as determined by
+/// [Lang::write_char_literal].
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let tokens: rust::Tokens = quote!(['a', $(quoted_char('\n')), $(quoted_char('\''))]);
+/// assert_eq!("['a', '\\n', '\\'']", tokens.to_string()?);
+///
+/// let tokens: go::Tokens = quote!($(quoted_char('"')));
+/// assert_eq!("'\"'", tokens.to_string()?);
+///
+/// let tokens: elixir::Tokens = quote!($(quoted_char('a')));
+/// assert_eq!("?a", tokens.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn quoted_char(c: char) -> QuotedChar {
+    QuotedChar { c }
+}
+
+/// Struct containing a character literal.
+///
+/// This is constructed with the [quoted_char()] function.
+#[derive(Clone, Copy, Debug)]
+pub struct QuotedChar {
+    c: char,
+}
+
+impl<L> FormatInto<L> for QuotedChar
+where
+    L: Lang,
+{
+    fn format_into(self, t: &mut Tokens<L>) {
+        t.item(Item::Char(self.c));
+    }
+}
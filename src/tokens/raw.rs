@@ -0,0 +1,154 @@
+use crate::lang::Lang;
+use crate::tokens::{FormatInto, Item, ItemStr};
+
+/// A formatter for raw, pre-formatted text.
+///
+/// Created from the [raw()] function.
+#[derive(Debug, Clone, Copy)]
+pub struct Raw<T> {
+    inner: T,
+}
+
+impl<T, L> FormatInto<L> for Raw<T>
+where
+    L: Lang,
+    T: AsRef<str>,
+{
+    fn format_into(self, tokens: &mut crate::Tokens<L>) {
+        tokens.item(Item::Raw(ItemStr::from(self.inner.as_ref())));
+    }
+}
+
+/// Append the given string verbatim, bypassing genco's whitespace and
+/// indentation handling.
+///
+/// Line breaks inside of the string are preserved, and lines following them
+/// are written as-is without being indented. Only the first line is
+/// positioned according to the surrounding tokens. This is useful for
+/// embedding pre-formatted snippets of code.
+///
+/// This can also be used through the `$(raw <expr>)` syntax in [quote!].
+///
+/// [quote!]: crate::quote
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let sql = "SELECT *\n  FROM users\n WHERE id = ?";
+///
+/// let tokens: Tokens = quote! {
+///     fn query() {
+///         let q = $(raw sql);
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "fn query() {",
+///         "    let q = SELECT *",
+///         "  FROM users",
+///         " WHERE id = ?;",
+///         "}",
+///     ],
+///     tokens.to_file_vec()?
+/// );
+///
+/// let mut tokens = Tokens::<()>::new();
+/// tokens.append(genco::tokens::raw("a  b"));
+/// assert_eq!("a  b", tokens.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn raw<T>(inner: T) -> Raw<T>
+where
+    T: AsRef<str>,
+{
+    Raw { inner }
+}
+
+/// A formatter for text whose lines keep their relative indentation.
+///
+/// Created from the [verbatim()] function.
+#[derive(Debug, Clone, Copy)]
+pub struct Verbatim<T> {
+    inner: T,
+}
+
+impl<T, L> FormatInto<L> for Verbatim<T>
+where
+    L: Lang,
+    T: AsRef<str>,
+{
+    fn format_into(self, tokens: &mut crate::Tokens<L>) {
+        let mut first = true;
+        let mut blank = false;
+
+        for line in self.inner.as_ref().split('\n') {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            if line.trim().is_empty() {
+                blank = !first;
+                continue;
+            }
+
+            if !first {
+                if blank {
+                    tokens.line();
+                } else {
+                    tokens.push();
+                }
+            }
+
+            tokens.item(Item::Literal(ItemStr::from(line)));
+            first = false;
+            blank = false;
+        }
+    }
+}
+
+/// Append the given multi-line string, preserving the leading whitespace of
+/// each line relative to the current indentation.
+///
+/// Unlike [raw()], every line is indented to the current level, and the
+/// whitespace already present at the start of a line is kept on top of it.
+/// Nothing is reflowed, which is useful for embedding things like ASCII-art
+/// banners or scripts in indented code. Leading and trailing empty lines are
+/// dropped, and consecutive empty lines are collapsed into one.
+///
+/// This can also be used through the `$(verbatim <expr>)` syntax in [quote!].
+///
+/// [quote!]: crate::quote
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let snippet = "match x {\n    0 => \"zero\",\n    _ => \"many\",\n}";
+///
+/// let tokens: rust::Tokens = quote! {
+///     fn describe(x: u32) -> &'static str {
+///         $(verbatim snippet)
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "fn describe(x: u32) -> &'static str {",
+///         "    match x {",
+///         "        0 => \"zero\",",
+///         "        _ => \"many\",",
+///         "    }",
+///         "}",
+///     ],
+///     tokens.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn verbatim<T>(inner: T) -> Verbatim<T>
+where
+    T: AsRef<str>,
+{
+    Verbatim { inner }
+}
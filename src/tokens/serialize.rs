@@ -0,0 +1,185 @@
+//! Serialization of token streams, enabled through the `serde` feature.
+//!
+//! Items are serialized as externally tagged variants, so a token stream in
+//! JSON looks like `[{"Literal":"foo"},"Space",{"Lang":...}]`. Language items
+//! are serialized without their position in the stream, which is recalculated
+//! when the stream is deserialized.
+
+use crate::lang::Lang;
+use crate::tokens::{Item, ItemStr, Tokens};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize)]
+#[serde(rename = "Item")]
+enum ItemRef<'a, T> {
+    Literal(&'a ItemStr),
+    Raw(&'a ItemStr),
+    Lang(&'a T),
+    Register(&'a T),
+    Push,
+    Line,
+    Space,
+    Indentation(i16),
+    OpenQuote(bool),
+    CloseQuote,
+    OpenEval,
+    CloseEval,
+    Source(&'a ItemStr, u32),
+    OpenOnce(&'a ItemStr),
+    CloseOnce,
+    Char(char),
+}
+
+impl<'a, L> From<&'a Item<L>> for ItemRef<'a, L::Item>
+where
+    L: Lang,
+{
+    fn from(item: &'a Item<L>) -> Self {
+        match item {
+            Item::Literal(s) => ItemRef::Literal(s),
+            Item::Raw(s) => ItemRef::Raw(s),
+            Item::Lang(_, item) => ItemRef::Lang(item),
+            Item::Register(_, item) => ItemRef::Register(item),
+            Item::Push => ItemRef::Push,
+            Item::Line => ItemRef::Line,
+            Item::Space => ItemRef::Space,
+            Item::Indentation(n) => ItemRef::Indentation(*n),
+            Item::OpenQuote(has_eval) => ItemRef::OpenQuote(*has_eval),
+            Item::CloseQuote => ItemRef::CloseQuote,
+            Item::OpenEval => ItemRef::OpenEval,
+            Item::CloseEval => ItemRef::CloseEval,
+            Item::Source(file, line) => ItemRef::Source(file, *line),
+            Item::OpenOnce(key) => ItemRef::OpenOnce(key),
+            Item::CloseOnce => ItemRef::CloseOnce,
+            Item::Char(c) => ItemRef::Char(*c),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Item")]
+enum ItemOwned<T> {
+    Literal(ItemStr),
+    Raw(ItemStr),
+    Lang(T),
+    Register(T),
+    Push,
+    Line,
+    Space,
+    Indentation(i16),
+    OpenQuote(bool),
+    CloseQuote,
+    OpenEval,
+    CloseEval,
+    Source(ItemStr, u32),
+    OpenOnce(ItemStr),
+    CloseOnce,
+    Char(char),
+}
+
+impl<L> From<ItemOwned<L::Item>> for Item<L>
+where
+    L: Lang,
+{
+    fn from(item: ItemOwned<L::Item>) -> Self {
+        // NB: the position of language items is recorded once the item is
+        // added to a token stream.
+        match item {
+            ItemOwned::Literal(s) => Item::Literal(s),
+            ItemOwned::Raw(s) => Item::Raw(s),
+            ItemOwned::Lang(item) => Item::Lang(0, Box::new(item)),
+            ItemOwned::Register(item) => Item::Register(0, Box::new(item)),
+            ItemOwned::Push => Item::Push,
+            ItemOwned::Line => Item::Line,
+            ItemOwned::Space => Item::Space,
+            ItemOwned::Indentation(n) => Item::Indentation(n),
+            ItemOwned::OpenQuote(has_eval) => Item::OpenQuote(has_eval),
+            ItemOwned::CloseQuote => Item::CloseQuote,
+            ItemOwned::OpenEval => Item::OpenEval,
+            ItemOwned::CloseEval => Item::CloseEval,
+            ItemOwned::Source(file, line) => Item::Source(file, line),
+            ItemOwned::OpenOnce(key) => Item::OpenOnce(key),
+            ItemOwned::CloseOnce => Item::CloseOnce,
+            ItemOwned::Char(c) => Item::Char(c),
+        }
+    }
+}
+
+impl Serialize for ItemStr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ItemStr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(ItemStr::Box(Box::<str>::deserialize(deserializer)?))
+    }
+}
+
+impl<L> Serialize for Item<L>
+where
+    L: Lang,
+    L::Item: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ItemRef::from(self).serialize(serializer)
+    }
+}
+
+impl<'de, L> Deserialize<'de> for Item<L>
+where
+    L: Lang,
+    L::Item: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(ItemOwned::deserialize(deserializer)?.into())
+    }
+}
+
+impl<L> Serialize for Tokens<L>
+where
+    L: Lang,
+    L::Item: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.iter().map(ItemRef::from))
+    }
+}
+
+impl<'de, L> Deserialize<'de> for Tokens<L>
+where
+    L: Lang,
+    L::Item: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let items = Vec::<ItemOwned<L::Item>>::deserialize(deserializer)?;
+        let mut tokens = Tokens::with_capacity(items.len());
+
+        for item in items {
+            tokens.push_item(item.into());
+        }
+
+        Ok(tokens)
+    }
+}
@@ -0,0 +1,59 @@
+use core::cell::Cell;
+
+std::thread_local! {
+    static RECORDING: Cell<bool> = Cell::new(false);
+}
+
+/// Record where each line of the token streams built by `f` was written, so
+/// that a source map can be produced through
+/// [Tokens::to_file_with_sourcemap].
+///
+/// Recording only applies to the current thread and to token streams built
+/// through [quote!] and [quote_in!] while `f` is running.
+///
+/// This requires the `sourcemap` feature, without which [quote!] doesn't mark
+/// where its lines are written.
+///
+/// [Tokens::to_file_with_sourcemap]: crate::Tokens::to_file_with_sourcemap
+/// [quote!]: crate::quote!
+/// [quote_in!]: crate::quote_in!
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::record_sources;
+///
+/// let tokens: rust::Tokens = record_sources(|| quote! {
+///     fn main() {
+///         println!("Hello World");
+///     }
+/// });
+///
+/// let (code, map) = tokens.to_file_with_sourcemap()?;
+///
+/// assert_eq!("fn main() {\n    println!(\"Hello World\");\n}\n", code);
+/// assert!(map.starts_with("{\"1\":\""));
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn record_sources<F, O>(f: F) -> O
+where
+    F: FnOnce() -> O,
+{
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let old = self.0;
+            RECORDING.with(|r| r.set(old));
+        }
+    }
+
+    let _restore = Restore(RECORDING.with(|r| r.replace(true)));
+    f()
+}
+
+/// Test if sources are being recorded on the current thread.
+pub(crate) fn recording() -> bool {
+    RECORDING.with(Cell::get)
+}
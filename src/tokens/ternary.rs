@@ -0,0 +1,60 @@
+use crate::lang::Lang;
+use crate::tokens::FormatInto;
+use crate::Tokens;
+
+/// Construct a conditional expression which evaluates to `then` if `cond`
+/// holds, and `els` otherwise, using the idiomatic form of the target
+/// language.
+///
+/// The expression is rendered by [Lang::ternary], which defaults to the
+/// C-style `cond ? then : els`.
+///
+/// [Lang::ternary]: crate::lang::Lang::ternary
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::ternary;
+///
+/// let js: js::Tokens = quote!(const sign = $(ternary(quote!(n < 0), "-1", "1")););
+/// assert_eq!("const sign = n < 0 ? -1 : 1;", js.to_string()?);
+///
+/// let py: python::Tokens = quote!(sign = $(ternary(quote!(n < 0), "-1", "1")));
+/// assert_eq!("sign = -1 if n < 0 else 1", py.to_string()?);
+///
+/// let rust: rust::Tokens = quote!(let sign = $(ternary(quote!(n < 0), "-1", "1")););
+/// assert_eq!("let sign = if n < 0 { -1 } else { 1 };", rust.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn ternary<C, T, E>(cond: C, then: T, els: E) -> Ternary<C, T, E> {
+    Ternary { cond, then, els }
+}
+
+/// A conditional expression rendered according to the target language.
+///
+/// This is constructed with the [ternary()] function.
+#[derive(Clone, Copy)]
+pub struct Ternary<C, T, E> {
+    cond: C,
+    then: T,
+    els: E,
+}
+
+impl<C, T, E, L> FormatInto<L> for Ternary<C, T, E>
+where
+    L: Lang,
+    C: FormatInto<L>,
+    T: FormatInto<L>,
+    E: FormatInto<L>,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        let mut cond = Tokens::new();
+        cond.append(self.cond);
+        let mut then = Tokens::new();
+        then.append(self.then);
+        let mut els = Tokens::new();
+        els.append(self.els);
+        L::ternary(tokens, cond, then, els);
+    }
+}
@@ -12,12 +12,16 @@
 
 use crate::fmt;
 use crate::lang::{Lang, LangSupportsEval};
-use crate::tokens::{FormatInto, Item, Register};
-use std::cmp;
-use std::iter::FromIterator;
-use std::mem;
-use std::slice;
-use std::vec;
+use crate::tokens::{DebugItems, FormatInto, Item, ItemStr, Register};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp;
+use core::iter::FromIterator;
+use core::mem;
+use core::slice;
 
 /// A stream of tokens.
 ///
@@ -79,6 +83,8 @@ where
     /// This makes up a singly-linked list over all language items that you can
     /// follow.
     last_lang_item: usize,
+    /// The sum of all indentation changes made to the stream.
+    indent: i16,
 }
 
 impl<L> Tokens<L>
@@ -100,11 +106,20 @@ where
         Tokens {
             items: Vec::new(),
             last_lang_item: 0,
+            indent: 0,
         }
     }
 
     /// Create a new empty stream of tokens with the specified capacity.
     ///
+    /// The capacity is counted in items, not bytes. Every run of literal
+    /// text, interpolated value, and whitespace change like a space or a line
+    /// break is one item, so a generated line is typically made up of a
+    /// handful of them. For large generated files a hint of about four items
+    /// per expected output line avoids most of the buffer growth, and the
+    /// number of items in a comparable stream which has already been built
+    /// like `tokens.iter().count()` is a precise hint.
+    ///
     /// # Examples
     ///
     /// ```
@@ -113,14 +128,133 @@ where
     /// let tokens = Tokens::<()>::with_capacity(10);
     ///
     /// assert!(tokens.is_empty());
+    /// assert!(tokens.capacity() >= 10);
     /// ```
     pub fn with_capacity(cap: usize) -> Self {
         Tokens {
             items: Vec::with_capacity(cap),
             last_lang_item: 0,
+            indent: 0,
         }
     }
 
+    /// The number of items the stream can hold without growing its buffer.
+    ///
+    /// See [Tokens::with_capacity] for how items are counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let tokens = Tokens::<()>::with_capacity(10);
+    /// assert!(tokens.capacity() >= 10);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.items.capacity()
+    }
+
+    /// Reserve capacity for at least `additional` more items.
+    ///
+    /// See [Tokens::with_capacity] for how items are counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens: Tokens<()> = quote!(foo);
+    /// tokens.reserve(10);
+    /// assert!(tokens.capacity() >= 11);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        self.items.reserve(additional);
+    }
+
+    /// Parse a template at runtime, following the same whitespace rules as
+    /// [quote!][crate::quote].
+    ///
+    /// This is intended for templates which are loaded from files, and
+    /// therefore can't be expanded at compile time. A `$name` is substituted
+    /// with the tokens bound to `name` in `bindings`, and `$$` is used for a
+    /// literal `$`. Indentation, spacing, and empty lines are processed like
+    /// they are by the macro, so the first line determines the base
+    /// indentation of the template and runs of whitespace within a line are
+    /// collapsed into a single space.
+    ///
+    /// Requires the `std` feature.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [ParseError][crate::tokens::ParseError] if a name has no
+    /// binding, or if the indentation of a line doesn't match any enclosing
+    /// line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use genco::prelude::*;
+    ///
+    /// let mut bindings = HashMap::new();
+    /// bindings.insert(String::from("name"), quote!(world));
+    ///
+    /// let template = "
+    ///     fn main() {
+    ///         println!(\"Hello $name, it costs $$5\");
+    ///     }
+    /// ";
+    ///
+    /// let tokens = rust::Tokens::parse(template, &bindings)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "fn main() {",
+    ///         "    println!(\"Hello world, it costs $5\");",
+    ///         "}",
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn parse(
+        template: &str,
+        bindings: &std::collections::HashMap<String, Tokens<L>>,
+    ) -> Result<Self, crate::tokens::ParseError> {
+        crate::tokens::parse::parse(template, bindings)
+    }
+
+    /// Construct a readable view of the items in the token stream, which is
+    /// useful when debugging how whitespace is handled.
+    ///
+    /// Literals are shown quoted, and everything else is shown in angle
+    /// brackets like `<push>` or `<indent 1>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: Tokens = quote!(a$['\r']b c);
+    /// assert_eq!("\"a\" <push> \"b\" <space> \"c\"", format!("{:?}", tokens.debug_items()));
+    ///
+    /// let tokens: Tokens = quote! {
+    ///     if a {
+    ///         b
+    ///     }
+    /// };
+    ///
+    /// assert_eq!(
+    ///     "\"if\" <space> \"a\" <space> \"{\" <indent 1> \"b\" <unindent 1> \"}\"",
+    ///     format!("{:?}", tokens.debug_items())
+    /// );
+    /// ```
+    pub fn debug_items(&self) -> DebugItems<'_, L> {
+        DebugItems::new(&self.items)
+    }
+
     /// Construct an iterator over the token stream.
     ///
     /// # Examples
@@ -139,39 +273,600 @@ where
     /// assert_eq!(Some(&Item::Literal(ItemStr::Static("baz"))), it.next());
     /// assert_eq!(None, it.next());
     /// ```
-    pub fn iter(&self) -> Iter<'_, L> {
-        Iter {
-            iter: self.items.iter(),
+    pub fn iter(&self) -> Iter<'_, L> {
+        Iter {
+            iter: self.items.iter(),
+        }
+    }
+
+    /// Append the given tokens.
+    ///
+    /// This append function takes anything implementing [FormatInto] making the
+    /// argument's behavior customizable. Most primitive types have built-in
+    /// implementations of [FormatInto] treating them as raw tokens.
+    ///
+    /// Most notabley, things implementing [FormatInto] can be used as arguments
+    /// for [interpolation] in the [quote!] macro.
+    ///
+    /// [quote!]: macro.quote.html
+    /// [interpolation]: macro.quote.html#interpolation
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    /// tokens.append(4u32);
+    ///
+    /// assert_eq!(quote!($(4u32)), tokens);
+    /// ```
+    pub fn append<T>(&mut self, tokens: T)
+    where
+        T: FormatInto<L>,
+    {
+        tokens.format_into(self)
+    }
+
+    /// Append the given tokens, separated by a space from whatever precedes
+    /// them.
+    ///
+    /// This matches how the [quote!] macro spaces separated tokens. The space
+    /// is only added if the stream is non-empty and doesn't already end in
+    /// whitespace, like a [`space`], [`push`], or [`line`].
+    ///
+    /// [quote!]: macro.quote.html
+    /// [`space`]: Self::space
+    /// [`push`]: Self::push
+    /// [`line`]: Self::line
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    /// tokens.append_spaced("let").append_spaced("a").append_spaced("=");
+    /// tokens.push();
+    /// tokens.append_spaced(42);
+    ///
+    /// assert_eq!(vec!["let a =", "42"], tokens.to_file_vec()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn append_spaced<T>(&mut self, tokens: T) -> &mut Self
+    where
+        T: FormatInto<L>,
+    {
+        match self.items.last() {
+            None
+            | Some(Item::Space)
+            | Some(Item::Push)
+            | Some(Item::Line)
+            | Some(Item::Indentation(..)) => {}
+            Some(_) => self.space(),
+        }
+
+        tokens.format_into(self);
+        self
+    }
+
+    /// Append the given token stream, moving its items into this one.
+    ///
+    /// No items are cloned, and if this stream is empty the allocation of the
+    /// appended stream is reused. This is what interpolating an owned token
+    /// stream with `$tokens` does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut body = Tokens::<()>::new();
+    ///
+    /// for name in ["a", "b"] {
+    ///     let part: Tokens = quote!($name;);
+    ///     body.append_tokens(part);
+    /// }
+    ///
+    /// assert_eq!("a;b;", body.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn append_tokens(&mut self, tokens: Tokens<L>) {
+        if self.items.is_empty() && self.indent == 0 {
+            *self = tokens;
+            return;
+        }
+
+        self.extend(tokens);
+    }
+
+    /// Append a copy of the given token stream.
+    ///
+    /// Every item in the stream is cloned, use [Tokens::append_tokens] if the
+    /// stream is no longer needed. This is what interpolating a borrowed token
+    /// stream with `$(&tokens)` does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let part: Tokens = quote!(a;);
+    ///
+    /// let mut body = Tokens::<()>::new();
+    /// body.append_ref(&part);
+    /// body.append_ref(&part);
+    ///
+    /// assert_eq!("a;a;", body.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn append_ref(&mut self, tokens: &Tokens<L>) {
+        self.extend(tokens.iter().cloned());
+    }
+
+    /// Append the given token stream, inserting a push between it and the
+    /// current stream unless either side already supplies spacing.
+    ///
+    /// Spacing is supplied if the current stream ends with, or the appended
+    /// stream starts with, a space, push, line, or indentation. This is
+    /// useful when splicing pre-built fragments which don't carry their own
+    /// trailing whitespace, like fragments built with [quote!].
+    ///
+    /// [quote!]: crate::quote
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut body = Tokens::<()>::new();
+    /// body.append_separated(quote!(a;));
+    /// body.append_separated(quote!(b;));
+    /// body.append_separated(quote!($[' ']c;));
+    ///
+    /// assert_eq!(vec!["a;", "b; c;"], body.to_file_vec()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn append_separated(&mut self, tokens: Tokens<L>) {
+        if tokens.is_empty() {
+            return;
+        }
+
+        let spaced = matches!(
+            self.items.last(),
+            None | Some(Item::Space | Item::Push | Item::Line | Item::Indentation(_))
+        ) || matches!(
+            tokens.items.first(),
+            Some(Item::Space | Item::Push | Item::Line | Item::Indentation(_))
+        );
+
+        if !spaced {
+            self.push();
+        }
+
+        self.append_tokens(tokens);
+    }
+
+    /// Add the trailing whitespace of the given token stream, being a space,
+    /// push, or line, to the end of this stream.
+    ///
+    /// This carries over the spacing context of a fragment whose content is
+    /// appended in some other way, like after it's been transformed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut fragment = Tokens::<()>::new();
+    /// fragment.append("a");
+    /// fragment.line();
+    ///
+    /// let mut tokens: Tokens<()> = quote!(b);
+    /// tokens.push_spacing_from(&fragment);
+    /// tokens.append("c");
+    ///
+    /// assert_eq!(vec!["b", "", "c"], tokens.to_file_vec()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn push_spacing_from(&mut self, other: &Tokens<L>) {
+        match other.items.last() {
+            Some(Item::Space) => self.space(),
+            Some(Item::Push) => self.push(),
+            Some(Item::Line) => self.line(),
+            _ => (),
+        }
+    }
+
+    /// Append the given item, wrapping it in parenthesis if `needs_parens` is
+    /// set.
+    ///
+    /// This is useful when generating expressions, where a sub-expression
+    /// needs to be parenthesized if it binds less tightly than the operator
+    /// it is an operand of. See [Expr][crate::tokens::Expr] for a helper which
+    /// keeps track of this automatically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let sum: Tokens = quote!(b + c);
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    /// tokens.append("a * ");
+    /// tokens.append_wrapped(&sum, true);
+    /// tokens.append(" - ");
+    /// tokens.append_wrapped(&sum, false);
+    ///
+    /// assert_eq!("a * (b + c) - b + c", tokens.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn append_wrapped<T>(&mut self, tokens: T, needs_parens: bool)
+    where
+        T: FormatInto<L>,
+    {
+        if needs_parens {
+            self.append(ItemStr::Static("("));
+            tokens.format_into(self);
+            self.append(ItemStr::Static(")"));
+        } else {
+            tokens.format_into(self);
+        }
+    }
+
+    /// Comment out the token stream, so that every line it formats into is
+    /// prefixed with the comment marker of the language, like `// `.
+    ///
+    /// The token stream is formatted using the default configuration before
+    /// being commented out, so fragments spanning multiple lines are handled
+    /// line by line. Any language items like imports are rendered as text and
+    /// no longer cause imports to be added. Lines are commented out through
+    /// [Lang::line_comment].
+    ///
+    /// # Errors
+    ///
+    /// Errors if the token stream fails to format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    ///
+    /// let mut example: rust::Tokens = quote! {
+    ///     let mut m = $map::new();
+    ///
+    ///     if m.is_empty() {
+    ///         m.insert(1, 2);
+    ///     }
+    /// };
+    ///
+    /// example.comment_out()?;
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     fn main() {
+    ///         $example
+    ///     }
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "fn main() {",
+    ///         "    // let mut m = HashMap::new();",
+    ///         "    //",
+    ///         "    // if m.is_empty() {",
+    ///         "    //     m.insert(1, 2);",
+    ///         "    // }",
+    ///         "}",
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn comment_out(&mut self) -> fmt::Result {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        let mut w = fmt::VecWriter::new();
+        let fmt = fmt::Config::from_lang::<L>().with_max_blank_lines(usize::MAX);
+        self.format(
+            &mut w.as_formatter(&fmt),
+            &L::default_config(),
+            &L::Format::default(),
+        )?;
+
+        let mut out = Tokens::new();
+
+        // NB: literals can contain line breaks, which the writer keeps in the
+        // same entry.
+        for entry in w.into_vec() {
+            for line in entry.split('\n') {
+                out.push();
+                L::line_comment(&mut out, line);
+            }
+        }
+
+        out.push();
+        *self = out;
+        Ok(())
+    }
+
+    /// Embed a token stream of another language as pre-formatted text.
+    ///
+    /// The other token stream is formatted as a file using its own language,
+    /// so that any imports it uses are resolved according to `config`. The
+    /// resulting lines are appended as literals which keep their relative
+    /// indentation and follow the current indentation, like
+    /// [verbatim()][crate::tokens::verbatim()].
+    ///
+    /// # Errors
+    ///
+    /// Errors if the embedded token stream fails to format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let greet = js::import("./greet.js", "greet");
+    ///
+    /// let script: js::Tokens = quote! {
+    ///     window.onload = () => {
+    ///         $greet("world");
+    ///     };
+    /// };
+    ///
+    /// let mut body = html::Tokens::new();
+    /// body.embed(&script, &js::Config::default())?;
+    ///
+    /// let html: html::Tokens = quote! {
+    ///     <body>
+    ///         <script type="module">
+    ///             $body
+    ///         </script>
+    ///     </body>
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "<body>",
+    ///         "    <script type=\"module\">",
+    ///         "        import {greet} from \"./greet.js\";",
+    ///         "",
+    ///         "        window.onload = () => {",
+    ///         "            greet(\"world\");",
+    ///         "        };",
+    ///         "    </script>",
+    ///         "</body>",
+    ///     ],
+    ///     html.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn embed<M>(&mut self, other: &Tokens<M>, config: &M::Config) -> fmt::Result
+    where
+        M: Lang,
+    {
+        let mut out = String::new();
+        let fmt = fmt::Config::from_lang::<M>();
+        let mut w = fmt::FmtWriter::new(&mut out);
+        other.format_file(&mut w.as_formatter(&fmt), config)?;
+        self.append(crate::tokens::verbatim(out));
+        Ok(())
+    }
+
+    /// Append the given string as a literal.
+    ///
+    /// This is the canonical way of appending string content, and accepts
+    /// anything which can be converted into an [ItemStr] such as `&str`,
+    /// `String`, or `Cow<str>` without requiring any manual conversion.
+    ///
+    /// Borrowed strings are copied into the token stream. To append a
+    /// `&'static str` without allocating, use [append_static] or pass it in as
+    /// an [ItemStr::Static].
+    ///
+    /// [append_static]: Self::append_static
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::tokens::{Item, ItemStr};
+    ///
+    /// let name = String::from("world");
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    /// tokens.append_str("hello");
+    /// tokens.space();
+    /// tokens.append_str(&name[..]);
+    /// tokens.space();
+    /// tokens.append_str(name);
+    /// tokens.space();
+    /// tokens.append_str(ItemStr::Static("!"));
+    ///
+    /// assert_eq!("hello world world !", tokens.to_string()?);
+    /// assert_eq!(Some(&Item::Literal(ItemStr::Static("!"))), tokens.iter().last());
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn append_str<S>(&mut self, s: S)
+    where
+        S: Into<ItemStr>,
+    {
+        self.item(Item::Literal(s.into()));
+    }
+
+    /// Append the given static string as a literal without copying it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::tokens::{Item, ItemStr};
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    /// tokens.append_static("hello");
+    ///
+    /// assert_eq!("hello", tokens.to_string()?);
+    /// assert_eq!(Some(&Item::Literal(ItemStr::Static("hello"))), tokens.iter().last());
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn append_static(&mut self, s: &'static str) {
+        self.item(Item::Literal(ItemStr::Static(s)));
+    }
+
+    /// Replace all occurrences of `from` in literal items with `to`.
+    ///
+    /// Only literal text is affected, so imports and whitespace are left
+    /// untouched. Note that a match must be contained within a single literal
+    /// item, which is the case for any identifier-like placeholder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens: rust::Tokens = quote! {
+    ///     fn __NAME__() -> &'static str {
+    ///         "__NAME__"
+    ///     }
+    /// };
+    ///
+    /// tokens.replace_literal("__NAME__", "hello");
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "fn hello() -> &'static str {",
+    ///         "    \"hello\"",
+    ///         "}",
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn replace_literal<T>(&mut self, from: &str, to: T)
+    where
+        T: Into<ItemStr>,
+    {
+        if from.is_empty() {
+            return;
+        }
+
+        let to = to.into();
+
+        for item in &mut self.items {
+            if let Item::Literal(literal) = item {
+                if **literal == *from {
+                    *literal = to.clone();
+                } else if literal.contains(from) {
+                    *literal = literal.replace(from, &to).into();
+                }
+            }
         }
     }
 
-    /// Append the given tokens.
-    ///
-    /// This append function takes anything implementing [FormatInto] making the
-    /// argument's behavior customizable. Most primitive types have built-in
-    /// implementations of [FormatInto] treating them as raw tokens.
+    /// Re-indent embedded, externally formatted code to use the given
+    /// indentation width.
     ///
-    /// Most notabley, things implementing [FormatInto] can be used as arguments
-    /// for [interpolation] in the [quote!] macro.
+    /// This detects the indentation unit used by the lines of [raw] items in
+    /// the stream as the most common increase in indentation from one line to
+    /// the next, and rewrites their leading spaces so that each level of
+    /// indentation is `width` spaces wide. Since the first line of a raw item
+    /// is positioned by the surrounding token stream, only the lines following
+    /// it are affected. Lines indented with tabs are left untouched.
     ///
-    /// [quote!]: macro.quote.html
-    /// [interpolation]: macro.quote.html#interpolation
+    /// [raw]: Item::Raw
     ///
     /// # Examples
     ///
     /// ```
     /// use genco::prelude::*;
     ///
-    /// let mut tokens = Tokens::<()>::new();
-    /// tokens.append(4u32);
+    /// let body = "function main() {\n  if (ready) {\n    run();\n  }\n}";
     ///
-    /// assert_eq!(quote!($(4u32)), tokens);
+    /// let mut tokens: js::Tokens = quote! {
+    ///     $(raw body)
+    /// };
+    ///
+    /// tokens.normalize_indentation(4);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "function main() {",
+    ///         "    if (ready) {",
+    ///         "        run();",
+    ///         "    }",
+    ///         "}",
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
     /// ```
-    pub fn append<T>(&mut self, tokens: T)
-    where
-        T: FormatInto<L>,
-    {
-        tokens.format_into(self)
+    pub fn normalize_indentation(&mut self, width: usize) {
+        // Count how often each increase in indentation between consecutive
+        // lines occurs.
+        let mut steps = BTreeMap::<usize, usize>::new();
+
+        for item in &self.items {
+            if let Item::Raw(raw) = item {
+                let mut prev = 0;
+
+                for line in raw.split('\n').skip(1) {
+                    let n = leading_spaces(line);
+
+                    if n == line.len() || line[n..].starts_with('\t') {
+                        continue;
+                    }
+
+                    if n > prev {
+                        *steps.entry(n - prev).or_default() += 1;
+                    }
+
+                    prev = n;
+                }
+            }
+        }
+
+        // NB: the most common step is used, so that odd continuation lines
+        // like the ` * ` in a doc comment don't determine the unit.
+        let unit = steps
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+            .map_or(0, |(unit, _)| *unit);
+
+        if unit == 0 || unit == width {
+            return;
+        }
+
+        for item in &mut self.items {
+            if let Item::Raw(raw) = item {
+                if !raw.contains('\n') {
+                    continue;
+                }
+
+                let mut out = String::with_capacity(raw.len());
+                let mut lines = raw.split('\n');
+
+                if let Some(first) = lines.next() {
+                    out.push_str(first);
+                }
+
+                for line in lines {
+                    let n = leading_spaces(line);
+                    out.push('\n');
+
+                    for _ in 0..(n / unit) * width + n % unit {
+                        out.push(' ');
+                    }
+
+                    out.push_str(&line[n..]);
+                }
+
+                *raw = ItemStr::from(out);
+            }
+        }
     }
 
     /// Extend with another stream of tokens.
@@ -357,6 +1052,86 @@ where
         self.items.push(Item::Push);
     }
 
+    /// Add a single soft push operation.
+    ///
+    /// Like [push][Self::push], this ensures that any following tokens are
+    /// added to their own line. Unlike it, any trailing line operations are
+    /// collapsed into the push, so a soft push never leaves an empty line
+    /// behind.
+    ///
+    /// This corresponds to the `$[softpush]` escape sequence in [quote!].
+    ///
+    /// [quote!]: crate::quote
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    ///
+    /// tokens.append("hello");
+    /// tokens.line();
+    /// tokens.soft_push();
+    /// tokens.soft_push();
+    /// tokens.append("world");
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "hello",
+    ///         "world"
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn soft_push(&mut self) {
+        let item = loop {
+            match self.items.pop() {
+                Some(Item::Space | Item::Push | Item::Line) => continue,
+                item => break item,
+            }
+        };
+
+        self.items.extend(item);
+        self.items.push(Item::Push);
+    }
+
+    /// Discard any pending whitespace at the end of the token stream, so that
+    /// whatever is appended next follows the last token directly.
+    ///
+    /// This drops trailing spaces, pushes, and lines, which is useful when a
+    /// scope in [quote!] or [quote_in!] ends up empty and leaves the
+    /// whitespace leading up to it behind.
+    ///
+    /// This corresponds to the `$[flush]` escape sequence in [quote!], and is
+    /// also available as [trim_end][Self::trim_end].
+    ///
+    /// [quote!]: crate::quote
+    /// [quote_in!]: crate::quote_in
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    ///
+    /// tokens.append("foo");
+    /// tokens.space();
+    /// tokens.push();
+    /// tokens.flush();
+    /// tokens.append("bar");
+    ///
+    /// assert_eq!("foobar", tokens.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn flush(&mut self) {
+        while let Some(Item::Space | Item::Push | Item::Line) = self.items.last() {
+            self.items.pop();
+        }
+    }
+
     /// Add a single line operation.
     ///
     /// A line ensures that any following tokens have one line of separation
@@ -400,6 +1175,171 @@ where
         self.items.push(Item::Line);
     }
 
+    /// Remove any leading whitespace items, like spaces, pushes, and lines,
+    /// from the token stream.
+    ///
+    /// Literals and indentation are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens: Tokens<()> = quote!($[' ']foo bar);
+    /// assert_eq!(" foo bar", tokens.to_string()?);
+    ///
+    /// tokens.trim_start();
+    /// assert_eq!("foo bar", tokens.to_string()?);
+    /// assert_eq!(tokens, quote!(foo bar));
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn trim_start(&mut self) {
+        // NB: sources are skipped over since they precede the whitespace
+        // emitted at the start of a quote.
+        let n = self
+            .items
+            .iter()
+            .take_while(|item| {
+                matches!(
+                    item,
+                    Item::Space | Item::Push | Item::Line | Item::Source(..)
+                )
+            })
+            .count();
+
+        if !self.items[..n]
+            .iter()
+            .any(|item| !matches!(item, Item::Source(..)))
+        {
+            return;
+        }
+
+        // NB: the positions of language items need to be recalculated since
+        // the items are shifted.
+        let items = mem::take(&mut self.items);
+        self.last_lang_item = 0;
+
+        for (index, item) in items.into_iter().enumerate() {
+            match item {
+                Item::Space | Item::Push | Item::Line if index < n => (),
+                Item::Lang(_, item) => self.lang_item(item),
+                Item::Register(_, item) => self.lang_item_register(item),
+                item => self.items.push(item),
+            }
+        }
+    }
+
+    /// Remove any trailing whitespace items, like spaces, pushes, and lines,
+    /// from the token stream.
+    ///
+    /// This is an alias of [flush][Self::flush], named to pair with
+    /// [trim_start][Self::trim_start]. Literals and indentation are left
+    /// untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens: Tokens<()> = quote!(foo bar);
+    /// tokens.line();
+    /// tokens.space();
+    ///
+    /// tokens.trim_end();
+    /// assert_eq!(tokens, quote!(foo bar));
+    ///
+    /// tokens.append(";");
+    /// assert_eq!("foo bar;", tokens.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    #[inline]
+    pub fn trim_end(&mut self) {
+        self.flush();
+    }
+
+    /// Test if this token stream is equal to `other`, ignoring any
+    /// differences in whitespace.
+    ///
+    /// Spaces, pushes, lines, and indentation are ignored, and adjacent
+    /// literals are compared as if they were joined. This means that
+    /// `foo(a, b)` and `foo( a, b )` are equal, but so are `foo bar` and
+    /// `foobar`. Language items like imports are compared by value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    ///
+    /// let a: rust::Tokens = quote!(let m = $(&map)::new(););
+    /// let b: rust::Tokens = quote! {
+    ///     let m =
+    ///         $(&map)::new();
+    /// };
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.eq_ignoring_whitespace(&b));
+    ///
+    /// let c: rust::Tokens = quote!(let m = $map::default(););
+    /// assert!(!a.eq_ignoring_whitespace(&c));
+    /// ```
+    pub fn eq_ignoring_whitespace(&self, other: &Tokens<L>) -> bool {
+        return significant(&self.items) == significant(&other.items);
+
+        enum Significant<'a, L>
+        where
+            L: Lang,
+        {
+            Text(String),
+            Item(&'a Item<L>),
+        }
+
+        impl<L> PartialEq for Significant<'_, L>
+        where
+            L: Lang,
+        {
+            fn eq(&self, other: &Self) -> bool {
+                match (self, other) {
+                    (Self::Text(a), Self::Text(b)) => a == b,
+                    // NB: the recorded positions of language items depend on
+                    // the whitespace around them.
+                    (Self::Item(Item::Lang(_, a)), Self::Item(Item::Lang(_, b))) => a == b,
+                    (Self::Item(Item::Register(_, a)), Self::Item(Item::Register(_, b))) => a == b,
+                    (Self::Item(a), Self::Item(b)) => a == b,
+                    _ => false,
+                }
+            }
+        }
+
+        fn significant<L>(items: &[Item<L>]) -> Vec<Significant<'_, L>>
+        where
+            L: Lang,
+        {
+            let mut out = Vec::new();
+
+            for item in items {
+                match item {
+                    Item::Space
+                    | Item::Push
+                    | Item::Line
+                    | Item::Indentation(..)
+                    | Item::Source(..) => (),
+                    Item::Literal(literal) => {
+                        if let Some(Significant::Text(text)) = out.last_mut() {
+                            text.push_str(literal);
+                        } else {
+                            out.push(Significant::Text(String::from(&**literal)));
+                        }
+                    }
+                    item => out.push(Significant::Item(item)),
+                }
+            }
+
+            out
+        }
+    }
+
     /// Increase the indentation of the token stream.
     ///
     /// An indentation is a language-specific operation which adds whitespace to
@@ -475,18 +1415,155 @@ where
     ///
     /// assert_eq!(
     ///     vec![
-    ///         "    hello",
-    ///         "world",
-    ///         "😀",
-    ///         "😁",
-    ///         "    😂",
+    ///         "    hello",
+    ///         "world",
+    ///         "😀",
+    ///         "😁",
+    ///         "    😂",
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn unindent(&mut self) {
+        self.indentation(-1);
+    }
+
+    /// Decrease the indentation of the token stream, ensuring that it's
+    /// balanced against a preceeding [`indent`].
+    ///
+    /// This behaves like [`unindent`], except that it keeps track of the
+    /// indentation added to the stream and refuses to go below zero.
+    ///
+    /// [`indent`]: Self::indent
+    /// [`unindent`]: Self::unindent
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream has no indentation left to decrease.
+    ///
+    /// ```should_panic
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    /// tokens.dedent();
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    ///
+    /// tokens.append("if true:");
+    /// tokens.indent();
+    /// tokens.append("hello()");
+    /// tokens.dedent();
+    /// tokens.append("world()");
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "if true:",
+    ///         "    hello()",
+    ///         "world()",
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn dedent(&mut self) {
+        assert!(
+            self.indent > 0,
+            "dedent without a matching indent in the token stream"
+        );
+
+        self.indentation(-1);
+    }
+
+    /// Wrap the given body in the `open` and `close` delimiters.
+    ///
+    /// Delimiters like `(` and `[` wrap the body inline. If `open` ends with
+    /// `{` it's treated as a brace-style block: it's separated from any
+    /// preceding tokens with a space, and a non-empty body is indented on its
+    /// own lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens: rust::Tokens = quote!(fn foo);
+    /// tokens.surround("(", quote!(a: u32), ")");
+    /// tokens.surround("{", quote!(println!("{}", a);), "}");
+    ///
+    /// let mut empty: rust::Tokens = quote!(struct Empty);
+    /// empty.surround("{", Tokens::new(), "}");
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "fn foo(a: u32) {",
+    ///         "    println!(\"{}\", a);",
+    ///         "}",
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    ///
+    /// assert_eq!("struct Empty {}", empty.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn surround<O, C>(&mut self, open: O, body: Tokens<L>, close: C)
+    where
+        O: Into<ItemStr>,
+        C: Into<ItemStr>,
+    {
+        let open = open.into();
+
+        if !open.ends_with('{') {
+            self.append_str(open);
+            self.append_tokens(body);
+            self.append_str(close);
+            return;
+        }
+
+        if !self.is_empty() {
+            self.space();
+        }
+
+        self.append_str(open);
+
+        if !body.is_empty() {
+            self.indent();
+            self.append_tokens(body);
+            self.unindent();
+        }
+
+        self.append_str(close);
+    }
+
+    /// Wrap the given body in braces, as a shorthand for
+    /// [`surround("{", body, "}")`][Tokens::surround].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens: js::Tokens = quote!(if (ready));
+    /// tokens.block(quote!(start();));
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "if (ready) {",
+    ///         "    start();",
+    ///         "}",
     ///     ],
     ///     tokens.to_file_vec()?
     /// );
     /// # Ok::<_, genco::fmt::Error>(())
     /// ```
-    pub fn unindent(&mut self) {
-        self.indentation(-1);
+    pub fn block(&mut self, body: Tokens<L>) {
+        self.surround("{", body, "}");
     }
 
     /// Formatting function for token streams that gives full control over the
@@ -593,12 +1670,38 @@ where
         self.last_lang_item = self.items.len();
     }
 
+    /// The number of items in the stream.
+    pub(crate) fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Push an item as-is, only recording the position of language items and
+    /// the indentation it contributes.
+    #[cfg(feature = "serde")]
+    pub(crate) fn push_item(&mut self, item: Item<L>) {
+        match item {
+            Item::Lang(_, item) => self.lang_item(item),
+            Item::Register(_, item) => self.lang_item_register(item),
+            item => {
+                if let Item::Indentation(n) = item {
+                    self.indent += n;
+                }
+
+                self.items.push(item);
+            }
+        }
+    }
+
     /// File formatting function for token streams that gives full control over the
     /// formatting environment.
     ///
     /// File formatting will render preambles like namespace declarations and
     /// imports.
     ///
+    /// Since the output is written as it's being formatted, this doesn't apply
+    /// [Lang::post_format]. Use [to_file_string][Self::to_file_string] or
+    /// [fmt_file_to][Self::fmt_file_to] if it should be.
+    ///
     /// Available formatters:
     ///
     /// * [fmt::VecWriter] - To write result into a vector.
@@ -637,8 +1740,54 @@ where
         Ok(())
     }
 
+    /// File formatting function like [format_file][Self::format_file], which
+    /// uses the configuration provided by [Lang::default_config] if `config`
+    /// is `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    /// let tokens: rust::Tokens = quote!(let m = $map::new(););
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>();
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// tokens.format_file_with(&mut w.as_formatter(&fmt), None)?;
+    /// assert_eq!(tokens.to_file_vec()?, w.into_vec());
+    ///
+    /// let config = rust::Config::default().with_default_import(rust::ImportMode::Qualified);
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// tokens.format_file_with(&mut w.as_formatter(&fmt), Some(&config))?;
+    /// assert_eq!(
+    ///     vec![
+    ///         "use std::collections;",
+    ///         "",
+    ///         "let m = collections::HashMap::new();",
+    ///     ],
+    ///     w.into_vec()
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn format_file_with(
+        &self,
+        out: &mut fmt::Formatter<'_>,
+        config: Option<&L::Config>,
+    ) -> fmt::Result {
+        match config {
+            Some(config) => self.format_file(out, config),
+            None => self.format_file(out, &L::default_config()),
+        }
+    }
+
     /// Internal function to modify the indentation of the token stream.
     fn indentation(&mut self, mut n: i16) {
+        self.indent += n;
+
         let item = loop {
             // flush all whitespace preceeding the indentation change.
             match self.items.pop() {
@@ -667,6 +1816,21 @@ where
     }
 }
 
+impl<L> Tokens<L>
+where
+    L: Lang,
+{
+    /// Helper function used by [quote_in!] to mutably access a token stream
+    /// through either a place expression or a mutable reference.
+    ///
+    /// [quote_in!]: crate::quote_in
+    #[doc(hidden)]
+    #[inline]
+    pub fn __as_mut(&mut self) -> &mut Self {
+        self
+    }
+}
+
 impl<L> Tokens<L>
 where
     L: LangSupportsEval,
@@ -680,13 +1844,12 @@ where
 impl<L> Tokens<L>
 where
     L: Lang,
-    L::Config: Default,
 {
     /// Format the token stream as a file for the given target language to a
     /// string using the default configuration.
     ///
-    /// This is a shorthand to using [FmtWriter][fmt::FmtWriter] directly in
-    /// combination with [format][Self::format_file].
+    /// This is a shorthand to using [fmt_file_to][Self::fmt_file_to] with a
+    /// string.
     ///
     /// This function will render imports.
     ///
@@ -710,19 +1873,87 @@ where
     /// # Ok::<_, genco::fmt::Error>(())
     /// ```
     pub fn to_file_string(&self) -> fmt::Result<String> {
-        let mut w = fmt::FmtWriter::new(String::new());
+        let mut string = String::new();
+        let fmt = fmt::Config::from_lang::<L>();
+        self.fmt_file_to(&mut string, &fmt)?;
+        Ok(string)
+    }
+
+    /// Format the token stream as a file like [to_file_string], while also
+    /// producing a source map of where each line was written.
+    ///
+    /// The source map is a JSON object from output lines, starting at 1, to
+    /// the `file:line` of the [quote!] invocation which produced them, like
+    /// `{"1":"src/main.rs:10","2":"src/main.rs:11"}`. Sources are only
+    /// available for token streams which were built inside of
+    /// [record_sources], otherwise the map is empty. Output lines are those
+    /// from before [Lang::post_format] is applied.
+    ///
+    /// This requires the `sourcemap` feature.
+    ///
+    /// [to_file_string]: Self::to_file_string
+    /// [quote!]: crate::quote!
+    /// [record_sources]: crate::tokens::record_sources
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::tokens::record_sources;
+    ///
+    /// let (tokens, line): (js::Tokens, _) = record_sources(|| {
+    ///     (quote!(console.log("Hello");), line!())
+    /// });
+    ///
+    /// let (code, map) = tokens.to_file_with_sourcemap()?;
+    ///
+    /// assert_eq!("console.log(\"Hello\");\n", code);
+    /// assert_eq!(format!("{{\"1\":\"{}:{}\"}}", file!(), line), map);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    #[cfg(feature = "sourcemap")]
+    pub fn to_file_with_sourcemap(&self) -> fmt::Result<(String, String)> {
+        use core::fmt::Write as _;
+
+        let mut string = String::new();
         let fmt = fmt::Config::from_lang::<L>();
+        let mut w = fmt::FmtWriter::new(&mut string);
         let mut formatter = w.as_formatter(&fmt);
-        let config = L::Config::default();
+        formatter.collect_sources();
+        let config = L::default_config();
         self.format_file(&mut formatter, &config)?;
-        Ok(w.into_inner())
+        let sources = formatter.take_sources();
+        let string = L::post_format(string, &config);
+
+        let mut map = String::from("{");
+
+        for (n, (output, file, line)) in sources.into_iter().enumerate() {
+            if n > 0 {
+                map.push(',');
+            }
+
+            write!(map, "\"{}\":\"", output)?;
+
+            for c in file.chars() {
+                match c {
+                    '"' => map.push_str("\\\""),
+                    '\\' => map.push_str("\\\\"),
+                    c if (c as u32) < 0x20 => write!(map, "\\u{:04x}", c as u32)?,
+                    c => map.push(c),
+                }
+            }
+
+            write!(map, ":{}\"", line)?;
+        }
+
+        map.push('}');
+        Ok((string, map))
     }
 
     /// Format only the current token stream as a string using the default
     /// configuration.
     ///
-    /// This is a shorthand to using [FmtWriter][fmt::FmtWriter] directly in
-    /// combination with [format][Self::format].
+    /// This is a shorthand to using [fmt_to][Self::fmt_to] with a string.
     ///
     /// This function _will not_ render imports.
     ///
@@ -745,13 +1976,216 @@ where
     /// # Ok::<_, genco::fmt::Error>(())
     /// ```
     pub fn to_string(&self) -> fmt::Result<String> {
-        let mut w = fmt::FmtWriter::new(String::new());
+        let mut string = String::new();
         let fmt = fmt::Config::from_lang::<L>();
-        let mut formatter = w.as_formatter(&fmt);
-        let config = L::Config::default();
+        self.fmt_to(&mut string, &fmt)?;
+        Ok(string)
+    }
+
+    /// Format the token stream as a file into the given
+    /// [fmt::Write][std::fmt::Write] sink using the default language
+    /// configuration.
+    ///
+    /// The file is formatted into an intermediate string first, so that
+    /// [Lang::post_format] can be applied to it before it's written to `out`.
+    /// Use [format_file][Self::format_file] to write the output as it's being
+    /// formatted instead.
+    ///
+    /// This function will render imports.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     let mut m = $map::new();
+    /// };
+    ///
+    /// let mut out = String::from("// generated\n");
+    /// let fmt = fmt::Config::from_lang::<Rust>();
+    /// tokens.fmt_file_to(&mut out, &fmt)?;
+    ///
+    /// assert_eq!(
+    ///     "// generated\nuse std::collections::HashMap;\n\nlet mut m = HashMap::new();\n",
+    ///     out
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn fmt_file_to<W>(&self, out: &mut W, fmt: &fmt::Config) -> fmt::Result
+    where
+        W: core::fmt::Write,
+    {
+        let config = L::default_config();
+        out.write_str(&self.format_file_string(fmt, &config)?)
+    }
+
+    /// Format the token stream as a file and apply [Lang::post_format] to the
+    /// output.
+    ///
+    /// All functions which produce a file as a string are built on this, so
+    /// that they agree on its contents.
+    pub(crate) fn format_file_string(
+        &self,
+        fmt: &fmt::Config,
+        config: &L::Config,
+    ) -> fmt::Result<String> {
+        let mut string = String::new();
+
+        {
+            let mut w = fmt::FmtWriter::new(&mut string);
+            self.format_file(&mut w.as_formatter(fmt), config)?;
+        }
+
+        Ok(L::post_format(string, config))
+    }
+
+    /// Format the token stream as a file using the given language
+    /// configuration, and write it to the file at `path`.
+    ///
+    /// The file is written atomically by first writing to a temporary file in
+    /// the same directory, which is then renamed to `path`. So a crash while
+    /// writing never leaves a partially written file behind.
+    ///
+    /// This function will render imports.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     let mut m = $map::new();
+    /// };
+    ///
+    /// let path = std::env::temp_dir().join("genco-write-into-file-doc.rs");
+    /// tokens.write_into_file(&path, &rust::Config::default())?;
+    ///
+    /// assert_eq!(
+    ///     "use std::collections::HashMap;\n\nlet mut m = HashMap::new();\n",
+    ///     std::fs::read_to_string(&path)?
+    /// );
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_into_file<P>(&self, path: P, config: &L::Config) -> std::io::Result<()>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let path = path.as_ref();
+        let contents = self.to_file_contents(path, config)?;
+        write_atomic(path, &contents)
+    }
+
+    /// Format the token stream as a file like
+    /// [write_into_file][Self::write_into_file], but only write it if the
+    /// contents of the file at `path` differ from the output.
+    ///
+    /// Returns `true` if the file was written. Leaving unchanged files alone
+    /// means their modification time is preserved, so build tools which track
+    /// it don't rebuild anything that depends on them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: rust::Tokens = quote!(pub const VERSION: u32 = 1;);
+    ///
+    /// let path = std::env::temp_dir().join("genco-write-if-changed-doc.rs");
+    /// # let _ = std::fs::remove_file(&path);
+    /// let config = rust::Config::default();
+    ///
+    /// assert!(tokens.write_if_changed(&path, &config)?);
+    /// assert!(!tokens.write_if_changed(&path, &config)?);
+    ///
+    /// let tokens: rust::Tokens = quote!(pub const VERSION: u32 = 2;);
+    /// assert!(tokens.write_if_changed(&path, &config)?);
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_if_changed<P>(&self, path: P, config: &L::Config) -> std::io::Result<bool>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let path = path.as_ref();
+        let contents = self.to_file_contents(path, config)?;
+
+        match std::fs::read(path) {
+            Ok(existing) if existing == contents.as_bytes() => return Ok(false),
+            Ok(..) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        write_atomic(path, &contents)?;
+        Ok(true)
+    }
+
+    /// Format the token stream as the contents of the file at `path`.
+    #[cfg(feature = "std")]
+    pub(crate) fn to_file_contents(
+        &self,
+        path: &std::path::Path,
+        config: &L::Config,
+    ) -> std::io::Result<String> {
+        use std::io;
+
+        let fmt = fmt::Config::from_lang::<L>();
+
+        self.format_file_string(&fmt, config).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                format!("failed to format `{}`", path.display()),
+            )
+        })
+    }
+
+    /// Format only the current token stream into the given
+    /// [fmt::Write][std::fmt::Write] sink using the default language
+    /// configuration.
+    ///
+    /// This is what [to_string][Self::to_string] is built on, and avoids
+    /// allocating an intermediate string.
+    ///
+    /// This function _will not_ render imports.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     let mut m = $map::new();
+    ///     m.insert(1u32, 2u32);
+    /// };
+    ///
+    /// let mut out = String::new();
+    /// let fmt = fmt::Config::from_lang::<Rust>();
+    /// tokens.fmt_to(&mut out, &fmt)?;
+    ///
+    /// assert_eq!(tokens.to_string()?, out);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn fmt_to<W>(&self, out: &mut W, fmt: &fmt::Config) -> fmt::Result
+    where
+        W: core::fmt::Write,
+    {
+        let mut w = fmt::FmtWriter::new(out);
+        let mut formatter = w.as_formatter(fmt);
+        let config = L::default_config();
         let format = L::Format::default();
-        self.format(&mut formatter, &config, &format)?;
-        Ok(w.into_inner())
+        self.format(&mut formatter, &config, &format)
     }
 
     /// Format tokens into a vector, where each entry equals a line in the
@@ -815,11 +2249,83 @@ where
         let mut w = fmt::VecWriter::new();
         let fmt = fmt::Config::from_lang::<L>();
         let mut formatter = w.as_formatter(&fmt);
-        let config = L::Config::default();
+        let config = L::default_config();
         self.format_file(&mut formatter, &config)?;
         Ok(w.into_vec())
     }
 
+    /// Count the number of lines the token stream produces as a file, without
+    /// keeping any of the formatted output.
+    ///
+    /// This renders imports the same way as [to_file_vec][Self::to_file_vec]
+    /// and returns the same number of lines, which is useful for things like
+    /// progress reporting. Like [to_file_vec][Self::to_file_vec], this doesn't
+    /// apply [Lang::post_format].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     fn new() -> $(&map)<u32, u32> {
+    ///         $map::new()
+    ///     }
+    /// };
+    ///
+    /// let config = rust::Config::default();
+    ///
+    /// assert_eq!(5, tokens.count_lines(&config)?);
+    /// assert_eq!(tokens.to_file_vec()?.len(), tokens.count_lines(&config)?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn count_lines(&self, config: &L::Config) -> fmt::Result<usize> {
+        let mut w = fmt::LineCounter::new();
+        let fmt = fmt::Config::from_lang::<L>();
+        self.format_file(&mut w.as_formatter(&fmt), config)?;
+        Ok(w.lines())
+    }
+
+    /// Iterate over the lines the token stream produces as a file.
+    ///
+    /// This renders imports the same way as [to_file_vec][Self::to_file_vec]
+    /// and yields the same lines, without formatting them into a vector or a
+    /// single string. Lines are collected as the formatter completes them, but
+    /// since [Lang::format_file] formats the file in one call, this happens
+    /// before the first line is returned.
+    ///
+    /// Like [to_file_vec][Self::to_file_vec], this doesn't apply
+    /// [Lang::post_format].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     let mut m = $map::new();
+    ///     m.insert(1u32, 2u32);
+    /// };
+    ///
+    /// let config = rust::Config::default();
+    /// let mut lines = tokens.lines(&config)?;
+    ///
+    /// assert_eq!(Some("use std::collections::HashMap;"), lines.next().as_deref());
+    /// assert_eq!(Some(""), lines.next().as_deref());
+    /// assert_eq!(tokens.to_file_vec()?[2..], lines.collect::<Vec<_>>()[..]);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn lines(&self, config: &L::Config) -> fmt::Result<fmt::Lines> {
+        let mut lines = fmt::Lines::new();
+        let fmt = fmt::Config::from_lang::<L>();
+        self.format_file(&mut lines.as_formatter(&fmt), config)?;
+        Ok(lines.finish())
+    }
+
     /// Helper function to format tokens into a vector, where each entry equals
     /// a line using the default configuration.
     ///
@@ -853,7 +2359,7 @@ where
         let mut w = fmt::VecWriter::new();
         let fmt = fmt::Config::from_lang::<L>();
         let mut formatter = w.as_formatter(&fmt);
-        let config = L::Config::default();
+        let config = L::default_config();
         let format = L::Format::default();
         self.format(&mut formatter, &config, &format)?;
         Ok(w.into_vec())
@@ -990,6 +2496,24 @@ where
     }
 }
 
+impl<L> Extend<Item<L>> for Tokens<L>
+where
+    L: Lang,
+{
+    fn extend<I: IntoIterator<Item = Item<L>>>(&mut self, iter: I) {
+        Tokens::extend(self, iter);
+    }
+}
+
+impl<'a, L> Extend<&'a Item<L>> for Tokens<L>
+where
+    L: Lang,
+{
+    fn extend<I: IntoIterator<Item = &'a Item<L>>>(&mut self, iter: I) {
+        Tokens::extend(self, iter.into_iter().cloned());
+    }
+}
+
 impl<'a, L> FromIterator<&'a Item<L>> for Tokens<L>
 where
     L: Lang,
@@ -1054,7 +2578,52 @@ where
     }
 }
 
+/// Count the number of leading spaces in the given line.
+fn leading_spaces(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Write `contents` to `path` by writing it to a temporary file in the same
+/// directory, which is then renamed to `path`.
+///
+/// The name of the temporary file includes the process id and a counter which
+/// is unique for every call, so concurrent writes never share it.
+#[cfg(feature = "std")]
+pub(crate) fn write_atomic(path: &std::path::Path, contents: &str) -> std::io::Result<()> {
+    use std::io::{self, Write as _};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path does not name a file"))?;
+
+    let mut tmp_name = std::ffi::OsString::from(".");
+    tmp_name.push(file_name);
+    tmp_name.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let tmp = path.with_file_name(tmp_name);
+
+    let result = (|| {
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, path)
+    })();
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+
+    result
+}
+
 #[cfg(test)]
+#[allow(dead_code)]
 mod tests {
     use crate as genco;
     use crate::fmt;
@@ -1069,11 +2638,13 @@ mod tests {
             type Config = ();
             type Format = ();
             type Item = Import;
+
+            fn default_config() -> Self::Config {}
         }
 
         Import {
             fn format(&self, out: &mut fmt::Formatter<'_>, _: &(), _: &()) -> fmt::Result {
-                use std::fmt::Write as _;
+                use core::fmt::Write as _;
                 write!(out, "{}", self.0)
             }
         }
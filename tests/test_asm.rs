@@ -0,0 +1,48 @@
+use genco::prelude::*;
+
+#[test]
+fn test_label_and_instructions() -> genco::fmt::Result {
+    let tokens: asm::Tokens = quote! {
+        $(asm::label("main"))
+            $(asm::instr("mov", ["eax", "1"]))
+            $(asm::instr("ret", Vec::<&str>::new()))
+    };
+
+    assert_eq!(
+        vec!["main:", "\tmov     eax, 1", "\tret"],
+        tokens.to_file_vec()?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_nested_label() -> genco::fmt::Result {
+    let body: asm::Tokens = quote! {
+        $(asm::instr("dec", ["ecx"]))
+        $(asm::label(".inner"))
+        $(asm::instr("jnz", [".inner"]))
+    };
+
+    let tokens: asm::Tokens = quote! {
+        $(asm::label("outer"))
+            $(asm::instr("mov", ["ecx", "10"]))
+            $(asm::instr("cmpxchg16b", ["[rdi]"]))
+            ; nested block
+            $body
+    };
+
+    assert_eq!(
+        vec![
+            "outer:",
+            "\tmov     ecx, 10",
+            "\tcmpxchg16b [rdi]",
+            "\t; nested block",
+            "\tdec     ecx",
+            ".inner:",
+            "\tjnz     .inner",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use genco::assert_tokens;
+use genco::prelude::*;
+
+#[test]
+fn test_assert_tokens() {
+    let tokens: js::Tokens = quote! {
+        function foo() {
+            return 42;
+        }
+    };
+
+    assert_tokens!(tokens, "function foo() {\n    return 42;\n}");
+
+    assert_tokens!(
+        tokens,
+        "
+function foo() {
+    return 42;
+}
+"
+    );
+}
+
+#[test]
+fn test_assert_tokens_mismatch() {
+    let tokens: js::Tokens = quote! {
+        function foo() {
+            return 42;
+        }
+        foo();
+    };
+
+    let error = std::panic::catch_unwind(|| {
+        assert_tokens!(tokens, "function foo() {\n    return 43;\n}");
+    })
+    .unwrap_err();
+
+    let message = error.downcast_ref::<String>().unwrap();
+
+    assert_eq!(
+        message,
+        concat!(
+            "tokens differ from expected output at line 2\n",
+            " expected: \"    return 43;\"\n",
+            "   actual: \"    return 42;\"\n",
+            "\n",
+            "    1 | function foo() {\n",
+            "-   2 |     return 43;\n",
+            "+   2 |     return 42;\n",
+            "    3 | }\n",
+            "+   4 | foo();\n",
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = "at line 2\n expected: \"<end of output>\"\n   actual: \"b\"")]
+fn test_assert_tokens_extra_line() {
+    let tokens: Tokens = quote! {
+        a
+        b
+    };
+
+    assert_tokens!(tokens, "a");
+}
@@ -0,0 +1,26 @@
+use genco::prelude::*;
+
+#[test]
+fn test_quoted_paths() -> genco::fmt::Result {
+    let src = "/mnt/My Files/report 'final'.pdf";
+    let dest = "$HOME/backup \"2024\"";
+
+    let tokens: bash::Tokens = quote! {
+        #!/bin/sh
+        # copy the report
+        cp -- $(bash::single_quoted(src)) $(bash::double_quoted(dest))
+        ls -l $(bash::var("HOME")) "`date`"
+    };
+
+    assert_eq!(
+        vec![
+            "#!/bin/sh",
+            "# copy the report",
+            "cp -- '/mnt/My Files/report '\\''final'\\''.pdf' \"\\$HOME/backup \\\"2024\\\"\"",
+            "ls -l \"$HOME\" \"\\`date\\`\"",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
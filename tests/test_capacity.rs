@@ -0,0 +1,119 @@
+//! Test that capacity hints avoid growing the buffer of a token stream.
+//!
+//! NB: this is kept in its own test binary, since it counts reallocations
+//! through a global allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use genco::prelude::*;
+use genco::tokens::{Item, ItemStr};
+
+struct Counting;
+
+static REALLOCS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        REALLOCS.fetch_add(1, Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const ITEMS: usize = 100_000;
+
+fn build(mut tokens: Tokens<()>) -> (Tokens<()>, usize) {
+    let before = REALLOCS.load(Ordering::SeqCst);
+
+    for n in 0..ITEMS / 2 {
+        tokens.append(ItemStr::Static("item"));
+
+        if n % 8 == 0 {
+            tokens.push();
+        } else {
+            tokens.space();
+        }
+    }
+
+    (tokens, REALLOCS.load(Ordering::SeqCst) - before)
+}
+
+#[test]
+fn test_capacity_hint() {
+    let (without, grown) = build(Tokens::new());
+    let (with, hinted) = build(Tokens::with_capacity(ITEMS));
+
+    assert_eq!(ITEMS, without.iter().count());
+    assert_eq!(without, with);
+    assert!(grown > 0);
+    assert_eq!(0, hinted);
+
+    let mut tokens = Tokens::<()>::new();
+    tokens.reserve(ITEMS);
+    let (_, reserved) = build(tokens);
+    assert_eq!(0, reserved);
+
+    let before = REALLOCS.load(Ordering::SeqCst);
+    let mut extended = Tokens::<()>::new();
+    Extend::extend(&mut extended, &without);
+    assert_eq!(0, REALLOCS.load(Ordering::SeqCst) - before);
+    assert_eq!(without, extended);
+
+    let items: Vec<Item<()>> = with.into_iter().collect();
+    assert_eq!(ITEMS, items.len());
+
+    // NB: called from here, since tests running in parallel would count each
+    // others reallocations.
+    loop_reserve();
+}
+
+fn loop_reserve() {
+    let names = (0..ITEMS / 4).map(|n| n.to_string()).collect::<Vec<_>>();
+
+    let before = REALLOCS.load(Ordering::SeqCst);
+    let tokens: Tokens<()> = quote!($(for n in &names join (, ) => item $n));
+    let grown = REALLOCS.load(Ordering::SeqCst) - before;
+
+    // NB: the buffer is only grown while appending the first iteration, and
+    // once more to fit the remaining ones.
+    assert!(grown <= 2, "grown {} times", grown);
+
+    let mut expected = Tokens::<()>::new();
+
+    for (i, n) in names.iter().enumerate() {
+        if i > 0 {
+            expected.append(ItemStr::Static(","));
+            expected.space();
+        }
+
+        expected.append(ItemStr::Static("item"));
+        expected.space();
+        expected.append(n);
+    }
+
+    assert_eq!(expected, tokens);
+
+    // NB: a filtered iterator has no lower bound, so nothing is reserved up
+    // front but the output is the same.
+    let before = REALLOCS.load(Ordering::SeqCst);
+    let tokens: Tokens<()> = quote!($(for n in names.iter().filter(|n| n.len() > 1) => $n));
+    assert_eq!(
+        names.len() - 10,
+        tokens
+            .iter()
+            .filter(|item| matches!(item, Item::Literal(..)))
+            .count()
+    );
+    assert!(REALLOCS.load(Ordering::SeqCst) - before > 1);
+}
@@ -0,0 +1,62 @@
+use genco::prelude::*;
+use genco::tokens::concat;
+
+#[test]
+fn test_concat() -> genco::fmt::Result {
+    let js: js::Tokens = quote!(const s = $(concat([quote!("a"), quote!(b), quote!("c")])););
+    assert_eq!("const s = \"a\" + b + \"c\";", js.to_string()?);
+
+    let nim: nim::Tokens = quote!(let s = $(concat([quote!("a"), quote!(b)])));
+    assert_eq!("let s = \"a\" & b", nim.to_string()?);
+
+    let zig: zig::Tokens = quote!(const s = $(concat([quote!("a"), quote!("b")])););
+    assert_eq!("const s = \"a\" ++ \"b\";", zig.to_string()?);
+
+    let c: c::Tokens = quote!(const char *s = $(concat([quote!("a"), quote!("b")])););
+    assert_eq!("const char *s = \"a\" \"b\";", c.to_string()?);
+
+    let r: r::Tokens = quote!(s <- $(concat([quote!("a"), quote!(b)])));
+    assert_eq!("s <- paste0(\"a\", b)", r.to_string()?);
+
+    let solidity: solidity::Tokens = quote!(string s = $(concat([quote!("a"), quote!(b)])););
+    assert_eq!("string s = string.concat(\"a\", b);", solidity.to_string()?);
+
+    let bash: bash::Tokens = quote!(s=$(concat([quote!("a"), quote!($$b)])));
+    assert_eq!("s=\"a\"$b", bash.to_string()?);
+
+    let verilog: verilog::Tokens = quote!(assign s = $(concat([quote!(a), quote!(b)])););
+    assert_eq!("assign s = {a, b};", verilog.to_string()?);
+
+    let hcl: hcl::Tokens = quote!(s = $(concat([quote!("a"), quote!(var.b)])));
+    assert_eq!("s = \"${\"a\"}${var.b}\"", hcl.to_string()?);
+
+    let rust: rust::Tokens = quote!(let s = $(concat([quote!("a"), quote!(b)])););
+    assert_eq!("let s = [\"a\", b].concat();", rust.to_string()?);
+
+    let single: js::Tokens = quote!($(concat([quote!(a)])));
+    assert_eq!("a", single.to_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_concat_block() -> genco::fmt::Result {
+    let field = "Name";
+    let suffix = quote!(Ref);
+
+    let tokens: js::Tokens = quote!(this.$(concat get $field $suffix)());
+    assert_eq!("this.getNameRef()", tokens.to_string()?);
+
+    let tokens: rust::Tokens = quote! {
+        fn $(concat set _ $(field.to_lowercase()))(value: u32) {}
+        let $(concat a b c) = $(concat 1 . 5);
+    };
+    assert_eq!(
+        vec!["fn set_name(value: u32) {}", "let abc = 1.5;"],
+        tokens.to_file_vec()?
+    );
+
+    // NB: anything which is an expression is still evaluated as one.
+    let tokens: js::Tokens = quote!($(concat([quote!(a), quote!(b)])));
+    assert_eq!("a + b", tokens.to_string()?);
+    Ok(())
+}
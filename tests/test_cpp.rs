@@ -0,0 +1,52 @@
+use genco::fmt;
+use genco::prelude::*;
+
+#[test]
+fn test_nested_templates() -> fmt::Result {
+    let vector = cpp::imported("std", "vector");
+    let string = cpp::imported("std", "string");
+    let config = cpp::include("config.h", "Config");
+
+    let rows = vector
+        .clone()
+        .with_arguments([vector.with_arguments([&string])]);
+
+    let tokens: cpp::Tokens = quote! {
+        $rows parse(const $string& input, const $config& config);
+    };
+
+    assert_eq!(
+        vec![
+            "#include \"config.h\"",
+            "#include <string>",
+            "#include <vector>",
+            "",
+            "std::vector<std::vector<std::string>> parse(const std::string& input, const Config& config);",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_with_header_keeps_kind() -> fmt::Result {
+    let widget = cpp::include("ui.h", "Widget").with_header("ui/widget.h");
+    let map = cpp::imported("std", "unordered_map").with_header("map");
+    let button = cpp::imported("ui", "Button").with_local_header("ui/button.h");
+
+    let tokens: cpp::Tokens = quote!($widget $map $button);
+
+    assert_eq!(
+        vec![
+            "#include <map>",
+            "#include \"ui/button.h\"",
+            "#include \"ui/widget.h\"",
+            "",
+            "Widget std::unordered_map ui::Button",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
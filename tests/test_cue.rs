@@ -0,0 +1,46 @@
+use genco::prelude::*;
+
+#[test]
+fn test_nested_struct() -> genco::fmt::Result {
+    let greeting = "Hello \\(name), costs $5 \"today\"";
+
+    let tokens: cue::Tokens = quote! {
+        package config
+
+        service: web: $(cue::struct_(quote! {
+            image: "nginx:1.25"
+            env: $(cue::struct_(quote! {
+                GREETING: $(quoted(greeting))
+                ADDR: $[str]($host:$port)
+            }))
+            ports: $(cue::list([80, 443].iter().map(|p| quote!($(*p)))))
+            script: $(cue::multiline("echo \\(date)\n\nprint \"\"\"done\"\"\""))
+        }))
+    };
+
+    assert_eq!(
+        vec![
+            "package config",
+            "",
+            "service: web: {",
+            "\timage: \"nginx:1.25\"",
+            "\tenv: {",
+            "\t\tGREETING: \"Hello \\\\(name), costs $5 \\\"today\\\"\"",
+            "\t\tADDR: \"\\(host):\\(port)\"",
+            "\t}",
+            "\tports: [",
+            "\t\t80,",
+            "\t\t443,",
+            "\t]",
+            "\tscript: \"\"\"",
+            "\t\techo \\\\(date)",
+            "",
+            "\t\tprint \\\"\"\"done\\\"\"\"",
+            "\t\t\"\"\"",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
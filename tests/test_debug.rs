@@ -0,0 +1,76 @@
+use std::process::Command;
+
+use genco::prelude::*;
+
+/// Build the tokens which are logged when the `GENCO_DEBUG` environment
+/// variable is set.
+fn build() -> rust::Tokens {
+    let body: rust::Tokens = quote!(return 42;);
+    let name = "answer";
+
+    quote! {
+        fn $(debug name)() -> u32 {
+            $(debug body)
+        }
+    }
+}
+
+#[test]
+#[ignore = "run by test_debug_logging in a child process"]
+fn debug_child() {
+    assert_eq!(
+        vec!["fn answer() -> u32 {", "    return 42;", "}"],
+        build().to_file_vec().unwrap()
+    );
+}
+
+#[test]
+fn test_debug_logging() {
+    let run = |enabled: bool| {
+        let mut command = Command::new(std::env::current_exe().unwrap());
+        command.args(["debug_child", "--exact", "--ignored", "--nocapture"]);
+
+        if enabled {
+            command.env("GENCO_DEBUG", "1");
+        } else {
+            command.env_remove("GENCO_DEBUG");
+        }
+
+        let output = command.output().unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stderr).unwrap()
+    };
+
+    let stderr = run(true);
+
+    if cfg!(debug_assertions) {
+        let lines = stderr
+            .lines()
+            .filter(|line| line.starts_with('['))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            lines,
+            vec![
+                "[tests/test_debug.rs:12] name = \"answer\"",
+                "[tests/test_debug.rs:13] body = \"return 42;\"",
+            ]
+        );
+    } else {
+        assert!(!stderr.contains("test_debug.rs"));
+    }
+
+    assert!(!run(false).contains("test_debug.rs"));
+}
+
+/// A function named `debug` can still be called.
+#[test]
+fn test_debug_as_function() -> genco::fmt::Result {
+    fn debug(n: u32) -> String {
+        format!("n{}", n)
+    }
+
+    let tokens: rust::Tokens = quote!($(debug(1)) $(debug(2).len()) $(debug "a"));
+    assert_eq!("n1 2 a", tokens.to_string()?);
+    Ok(())
+}
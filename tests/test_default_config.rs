@@ -0,0 +1,63 @@
+use genco::fmt;
+use genco::prelude::*;
+use std::fmt::Write as _;
+
+genco::impl_lang! {
+    Banner {
+        type Config = Config;
+        type Item = Import;
+        type Format = ();
+
+        fn default_config() -> Config {
+            Config {
+                banner: Some("generated"),
+            }
+        }
+
+        fn format_file(
+            tokens: &Tokens<Banner>,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut header: Tokens<Banner> = Tokens::new();
+
+            if let Some(banner) = config.banner {
+                quote_in!(header => # $banner);
+                header.line();
+            }
+
+            header.format(out, config, &())?;
+            tokens.format(out, config, &())?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &()) -> fmt::Result {
+            out.write_str(self.0)?;
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Config {
+    banner: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Import(&'static str);
+
+#[test]
+fn test_default_config() -> fmt::Result {
+    let tokens: Tokens<Banner> = quote!(run $(Import("main")));
+
+    assert_eq!(vec!["# generated", "", "run main"], tokens.to_file_vec()?);
+    assert_eq!("# generated\n\nrun main\n", tokens.to_file_string()?);
+
+    let fmt = fmt::Config::from_lang::<Banner>();
+    let mut w = fmt::VecWriter::new();
+    tokens.format_file_with(&mut w.as_formatter(&fmt), Some(&Config { banner: None }))?;
+    assert_eq!(vec!["run main"], w.into_vec());
+    Ok(())
+}
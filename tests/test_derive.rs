@@ -0,0 +1,60 @@
+use genco::prelude::*;
+
+#[derive(FormatInto)]
+struct Assign {
+    name: &'static str,
+    value: u32,
+}
+
+#[test]
+fn test_two_fields() -> genco::fmt::Result {
+    let assign = Assign {
+        name: "answer",
+        value: 42,
+    };
+
+    let tokens: rust::Tokens = quote!(let $assign;);
+    assert_eq!("let answer 42;", tokens.to_string()?);
+    Ok(())
+}
+
+#[derive(FormatInto)]
+#[genco(sep = ", ")]
+struct Args<T>(T, T, #[genco(skip)] ());
+
+#[test]
+fn test_tuple_struct() -> genco::fmt::Result {
+    let args = Args("a", "b", ());
+
+    let tokens: js::Tokens = quote!(f($args));
+    assert_eq!("f(a, b)", tokens.to_string()?);
+    Ok(())
+}
+
+#[derive(FormatInto)]
+#[genco(sep = line)]
+struct Items {
+    first: rust::Tokens,
+    second: rust::Tokens,
+}
+
+#[test]
+fn test_line_separator() -> genco::fmt::Result {
+    let items = Items {
+        first: quote!(
+            fn first() {}
+        ),
+        second: quote!(
+            fn second() {}
+        ),
+    };
+
+    let tokens: rust::Tokens = quote!($items);
+
+    assert_eq!(
+        vec!["fn first() {}", "", "fn second() {}"],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use genco::prelude::*;
+
+#[test]
+fn test_multi_stage() -> genco::fmt::Result {
+    let tokens: dockerfile::Tokens = quote! {
+        $(dockerfile::from("rust:1").with_name("build"))
+        # build the release binary
+        $(dockerfile::run([
+            "apt-get update",
+            "apt-get install -y --no-install-recommends libssl-dev",
+            "rm -rf /var/lib/apt/lists/*",
+        ]))
+        $(dockerfile::run(["cargo build --release"]))
+
+        $(dockerfile::from("debian:bookworm-slim"))
+        $(dockerfile::copy("/src/target/release/app", "/app").with_stage("build"))
+        $(dockerfile::entrypoint(["/app"]))
+        $(dockerfile::cmd(["--port", "8080"]))
+    };
+
+    assert_eq!(
+        vec![
+            "FROM rust:1 AS build",
+            "# build the release binary",
+            "RUN apt-get update \\",
+            "    && apt-get install -y --no-install-recommends libssl-dev \\",
+            "    && rm -rf /var/lib/apt/lists/*",
+            "RUN cargo build --release",
+            "",
+            "FROM debian:bookworm-slim",
+            "COPY --from=build /src/target/release/app /app",
+            "ENTRYPOINT [\"/app\"]",
+            "CMD [\"--port\", \"8080\"]",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_shell_form() -> genco::fmt::Result {
+    let tokens: dockerfile::Tokens = quote! {
+        $(dockerfile::entrypoint_shell("exec app"))
+        $(dockerfile::cmd_shell("echo \"$HOME\""))
+        $(dockerfile::cmd(["sh", "-c", "echo \"$HOME\"\n"]))
+    };
+
+    assert_eq!(
+        vec![
+            "ENTRYPOINT exec app",
+            "CMD echo \"$HOME\"",
+            "CMD [\"sh\", \"-c\", \"echo \\\"$HOME\\\"\\n\"]",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
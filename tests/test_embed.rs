@@ -0,0 +1,45 @@
+use genco::fmt;
+use genco::prelude::*;
+
+#[test]
+fn test_embed_js_in_html() -> fmt::Result {
+    let render = js::import(js::Module::Path("app/render".into()), "render");
+
+    let script: js::Tokens = quote! {
+        if (items.length < 10) {
+            $render(items);
+        }
+    };
+
+    let mut body = html::Tokens::new();
+    body.embed(&script, &js::Config::default().with_module_path("index"))?;
+
+    let page: html::Tokens = quote! {
+        <html>
+            <body>
+                <script type="module">
+                    $body
+                </script>
+            </body>
+        </html>
+    };
+
+    assert_eq!(
+        vec![
+            "<html>",
+            "    <body>",
+            "        <script type=\"module\">",
+            "            import {render} from \"./app/render.js\";",
+            "",
+            "            if (items.length < 10) {",
+            "                render(items);",
+            "            }",
+            "        </script>",
+            "    </body>",
+            "</html>",
+        ],
+        page.to_file_vec()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use genco::prelude::*;
+
+/// Locals colliding with keywords are rendered as raw identifiers.
+#[test]
+fn test_rust_local() -> genco::fmt::Result {
+    let tokens: rust::Tokens = quote!($(rust::local("match")));
+    assert_eq!("r#match", tokens.to_string()?);
+
+    let tokens: rust::Tokens = quote!($(rust::local("self")) $(rust::local("value")));
+    assert_eq!("self value", tokens.to_string()?);
+    Ok(())
+}
+
+/// Imported names colliding with keywords are escaped both in the import and
+/// where they are used.
+#[test]
+fn test_rust_import() -> genco::fmt::Result {
+    let a = rust::import("foo::bar", "type");
+    let b = rust::import("foo::bar", "Name").with_alias("async");
+    let c = rust::import("foo::baz", "loop").qualified();
+
+    let tokens: rust::Tokens = quote! {
+        $a
+        $b
+        $c
+    };
+
+    assert_eq!(
+        vec![
+            "use foo::bar::{Name as r#async, r#type};",
+            "use foo::baz;",
+            "",
+            "r#type",
+            "r#async",
+            "baz::r#loop",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use std::fs;
+use std::path::Path;
+
+use genco::prelude::*;
+use genco::FileSet;
+
+#[test]
+fn test_independent_imports() -> anyhow::Result<()> {
+    let map = rust::import("std::collections", "HashMap");
+    let set = rust::import("std::collections", "HashSet");
+    let display = rust::import("std::fmt", "Display");
+
+    let mut files = FileSet::<Rust>::new();
+
+    files.add(
+        "maps.rs",
+        quote! {
+            pub type Map = $map<u32, u32>;
+        },
+    );
+
+    files.add(
+        "nested/sets.rs",
+        quote! {
+            pub type Set = $set<u32>;
+            pub fn show(value: &dyn $display) {}
+        },
+    );
+
+    assert_eq!(2, files.len());
+
+    let paths = files.iter().map(|(path, _)| path).collect::<Vec<_>>();
+    assert_eq!(
+        vec![Path::new("maps.rs"), Path::new("nested/sets.rs")],
+        paths
+    );
+
+    let dir = std::env::temp_dir().join(format!("genco-test-file-set-{}", std::process::id()));
+    files.write_to_dir(&dir, &rust::Config::default())?;
+
+    assert_eq!(
+        "use std::collections::HashMap;\n\npub type Map = HashMap<u32, u32>;\n",
+        fs::read_to_string(dir.join("maps.rs"))?
+    );
+
+    assert_eq!(
+        "use std::collections::HashSet;\nuse std::fmt::Display;\n\npub type Set = HashSet<u32>;\npub fn show(value: &dyn Display) {}\n",
+        fs::read_to_string(dir.join("nested").join("sets.rs"))?
+    );
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_reject_escaping_paths() -> anyhow::Result<()> {
+    let dir =
+        std::env::temp_dir().join(format!("genco-test-file-set-escape-{}", std::process::id()));
+
+    for path in ["../escape.rs", "nested/../../escape.rs", "/tmp/escape.rs"] {
+        let mut files = FileSet::<Rust>::new();
+        files.add(
+            "ok.rs",
+            quote!(
+                pub struct Ok;
+            ),
+        );
+        files.add(
+            path,
+            quote!(
+                pub struct Escape;
+            ),
+        );
+
+        let error = files
+            .write_to_dir(&dir, &rust::Config::default())
+            .unwrap_err();
+
+        assert_eq!(std::io::ErrorKind::InvalidInput, error.kind());
+        assert!(!dir.exists());
+    }
+
+    Ok(())
+}
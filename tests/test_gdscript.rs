@@ -0,0 +1,72 @@
+use genco::fmt;
+use genco::prelude::*;
+
+#[test]
+fn test_class_with_preload() -> fmt::Result {
+    let enemy = gdscript::preload("Enemy", "res://enemies/enemy.gd");
+    let explosion = gdscript::preload("Explosion", "res://fx/explosion.tscn");
+
+    let toks: gdscript::Tokens = quote! {
+        @export var count := 3
+
+        func spawn() -> void:
+            for i in range(count):
+                var e = $(&enemy).new()
+                e.name = $(quoted("enemy_")) + str(i)
+                add_child(e)
+
+        func _on_enemy_died(e: $enemy) -> void:
+            add_child($explosion.instantiate())
+    };
+
+    let mut w = fmt::VecWriter::new();
+    let fmt = fmt::Config::from_lang::<GDScript>();
+    let config = gdscript::Config::default()
+        .with_class_name("Spawner")
+        .with_extends("Node2D");
+    toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+
+    assert_eq!(
+        vec![
+            "class_name Spawner",
+            "extends Node2D",
+            "",
+            "const Enemy = preload(\"res://enemies/enemy.gd\")",
+            "const Explosion = preload(\"res://fx/explosion.tscn\")",
+            "",
+            "@export var count := 3",
+            "",
+            "func spawn() -> void:",
+            "\tfor i in range(count):",
+            "\t\tvar e = Enemy.new()",
+            "\t\te.name = \"enemy_\" + str(i)",
+            "\t\tadd_child(e)",
+            "",
+            "func _on_enemy_died(e: Enemy) -> void:",
+            "\tadd_child(Explosion.instantiate())",
+        ],
+        w.into_vec()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_dedup_by_name() -> fmt::Result {
+    let toks: gdscript::Tokens = quote! {
+        $(gdscript::load("Level", "res://level1.tscn"))
+        $(gdscript::preload("Level", "res://level2.tscn"))
+    };
+
+    assert_eq!(
+        vec![
+            "var Level = load(\"res://level1.tscn\")",
+            "",
+            "Level",
+            "Level",
+        ],
+        toks.to_file_vec()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use genco::fmt;
+use genco::prelude::*;
+
+fn format(tokens: &go::Tokens, config: &go::Config) -> fmt::Result<Vec<String>> {
+    let mut w = fmt::VecWriter::new();
+    let fmt = fmt::Config::from_lang::<Go>();
+    tokens.format_file(&mut w.as_formatter(&fmt), config)?;
+    Ok(w.into_vec())
+}
+
+#[test]
+fn test_align_struct_fields() -> fmt::Result {
+    let tokens: go::Tokens = quote! {
+        type User struct {
+            ID int64
+            Name string
+            EmailAddress string
+            Settings struct {
+                Theme string
+                FontSize int
+            }
+        }
+
+        func main() {
+            x := 1
+            longer := 2
+        }
+    };
+
+    let config = go::Config::default().with_align_struct_fields(true);
+
+    assert_eq!(
+        vec![
+            "type User struct {",
+            "\tID           int64",
+            "\tName         string",
+            "\tEmailAddress string",
+            "\tSettings     struct {",
+            "\t\tTheme    string",
+            "\t\tFontSize int",
+            "\t}",
+            "}",
+            "",
+            "func main() {",
+            "\tx := 1",
+            "\tlonger := 2",
+            "}",
+        ],
+        format(&tokens, &config)?
+    );
+
+    assert_eq!(
+        vec![
+            "type User struct {",
+            "\tID int64",
+            "\tName string",
+            "\tEmailAddress string",
+            "\tSettings struct {",
+            "\t\tTheme string",
+            "\t\tFontSize int",
+            "\t}",
+            "}",
+            "",
+            "func main() {",
+            "\tx := 1",
+            "\tlonger := 2",
+            "}",
+        ],
+        format(&tokens, &go::Config::default())?
+    );
+
+    Ok(())
+}
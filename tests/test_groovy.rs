@@ -0,0 +1,38 @@
+use genco::fmt;
+use genco::prelude::*;
+
+#[test]
+fn test_import_and_task() -> fmt::Result {
+    let list = groovy::imported("java.util", "List");
+    let file = groovy::imported("java.io", "File");
+
+    let body: groovy::Tokens = quote! {
+        group = "build"
+        doLast {
+            $list<$(&file)> outputs = [new $file($[str]($buildDir/$(project.name).jar))]
+            println "Built $outputs"
+        }
+    };
+
+    let tokens: groovy::Tokens = quote! {
+        $(groovy::task("assembleJar", body))
+    };
+
+    assert_eq!(
+        vec![
+            "import java.io.File",
+            "import java.util.List",
+            "",
+            "task assembleJar {",
+            "    group = \"build\"",
+            "    doLast {",
+            "        List<File> outputs = [new File(\"$buildDir/${project.name}.jar\")]",
+            "        println \"Built \\$outputs\"",
+            "    }",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,227 @@
+use genco::fmt;
+use genco::lang::{ImportOrder, Lang};
+use genco::prelude::*;
+
+fn format<L>(tokens: &Tokens<L>, config: &L::Config) -> Result<Vec<String>, fmt::Error>
+where
+    L: Lang,
+{
+    let fmt = fmt::Config::from_lang::<L>();
+    let mut w = fmt::VecWriter::new();
+    tokens.format_file(&mut w.as_formatter(&fmt), config)?;
+    Ok(w.into_vec())
+}
+
+#[test]
+fn test_rust() -> fmt::Result {
+    let tokens: rust::Tokens = quote! {
+        $(rust::import("std::fmt", "Display"))
+        $(rust::import("std::collections", "HashMap"))
+        $(rust::import("std::fmt", "Debug"))
+        $(rust::import("anyhow", "Result"))
+    };
+
+    let sorted = rust::Config::default();
+    let encountered = rust::Config::default().with_import_order(ImportOrder::AsEncountered);
+
+    assert_eq!(
+        vec![
+            "use anyhow::Result;",
+            "use std::collections::HashMap;",
+            "use std::fmt::{Debug, Display};",
+            "",
+            "Display",
+            "HashMap",
+            "Debug",
+            "Result",
+        ],
+        format(&tokens, &sorted)?
+    );
+
+    assert_eq!(
+        vec![
+            "use std::fmt::{Display, Debug};",
+            "use std::collections::HashMap;",
+            "use anyhow::Result;",
+            "",
+            "Display",
+            "HashMap",
+            "Debug",
+            "Result",
+        ],
+        format(&tokens, &encountered)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_js() -> fmt::Result {
+    let tokens: js::Tokens = quote! {
+        $(js::import("react", "useState"))
+        $(js::import("axios", "get"))
+        $(js::import("react", "useEffect"))
+        $(js::import("./util.js", "helper"))
+    };
+
+    let sorted = js::Config::default();
+    let encountered = js::Config::default().with_import_order(ImportOrder::AsEncountered);
+
+    assert_eq!(
+        vec![
+            "import {helper} from \"./util.js\";",
+            "import {get} from \"axios\";",
+            "import {useEffect, useState} from \"react\";",
+            "",
+            "useState",
+            "get",
+            "useEffect",
+            "helper",
+        ],
+        format(&tokens, &sorted)?
+    );
+
+    assert_eq!(
+        vec![
+            "import {useState, useEffect} from \"react\";",
+            "import {get} from \"axios\";",
+            "import {helper} from \"./util.js\";",
+            "",
+            "useState",
+            "get",
+            "useEffect",
+            "helper",
+        ],
+        format(&tokens, &encountered)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_python() -> fmt::Result {
+    let tokens: python::Tokens = quote! {
+        $(python::import_module("sys"))
+        $(python::import("collections", "namedtuple"))
+        $(python::import_module("os"))
+    };
+
+    let sorted = python::Config::default();
+    let encountered = python::Config::default().with_import_order(ImportOrder::AsEncountered);
+
+    assert_eq!(
+        vec![
+            "from collections import namedtuple",
+            "import os",
+            "import sys",
+            "",
+            "sys",
+            "namedtuple",
+            "os",
+        ],
+        format(&tokens, &sorted)?
+    );
+
+    assert_eq!(
+        vec![
+            "from collections import namedtuple",
+            "import sys",
+            "import os",
+            "",
+            "sys",
+            "namedtuple",
+            "os",
+        ],
+        format(&tokens, &encountered)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_go() -> fmt::Result {
+    let tokens: go::Tokens = quote! {
+        $(go::import("strings", "Join"))
+        $(go::import("fmt", "Println"))
+        $(go::import("os", "Exit"))
+    };
+
+    let sorted = go::Config::default();
+    let encountered = go::Config::default().with_import_order(ImportOrder::AsEncountered);
+
+    assert_eq!(
+        vec![
+            "import \"fmt\"",
+            "import \"os\"",
+            "import \"strings\"",
+            "",
+            "strings.Join",
+            "fmt.Println",
+            "os.Exit",
+        ],
+        format(&tokens, &sorted)?
+    );
+
+    assert_eq!(
+        vec![
+            "import \"strings\"",
+            "import \"fmt\"",
+            "import \"os\"",
+            "",
+            "strings.Join",
+            "fmt.Println",
+            "os.Exit",
+        ],
+        format(&tokens, &encountered)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_comments() -> fmt::Result {
+    let tokens: rust::Tokens = quote! {
+        $(rust::import("tokio", "spawn").with_comment("runtime"))
+        $(rust::import("std::fmt", "Display"))
+        $(rust::import("anyhow", "Result").with_comment("error handling"))
+        $(rust::import("anyhow", "Context").with_comment("error handling"))
+    };
+
+    assert_eq!(
+        vec![
+            "// error handling",
+            "use anyhow::{Context, Result};",
+            "use std::fmt::Display;",
+            "// runtime",
+            "use tokio::spawn;",
+            "",
+            "spawn",
+            "Display",
+            "Result",
+            "Context",
+        ],
+        format(&tokens, &rust::Config::default())?
+    );
+
+    let tokens: python::Tokens = quote! {
+        $(python::import("requests", "get").with_comment("third party"))
+        $(python::import_module("os"))
+        $(python::import("dataclasses", "dataclass"))
+    };
+
+    assert_eq!(
+        vec![
+            "from dataclasses import dataclass",
+            "# third party",
+            "from requests import get",
+            "import os",
+            "",
+            "get",
+            "os",
+            "dataclass",
+        ],
+        format(&tokens, &python::Config::default())?
+    );
+
+    Ok(())
+}
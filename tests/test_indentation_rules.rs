@@ -34,3 +34,218 @@ fn test_indentation_rules() -> genco::fmt::Result {
 
     Ok(())
 }
+
+#[test]
+fn test_softpush() -> genco::fmt::Result {
+    let tokens: Tokens<Rust> = quote!(a$[softpush]$[softpush]b);
+    assert_eq!("a\nb", tokens.to_string()?);
+
+    let constants = [("A", 1), ("B", 2), ("C", 3)];
+
+    let mut tokens = Tokens::<Rust>::new();
+
+    for (name, value) in constants {
+        let constant: Tokens<Rust> = quote!(const $name: u32 = $value;$['\n']);
+        quote_in!(tokens => $[softpush]$constant$[softpush]);
+    }
+
+    assert_eq!(
+        vec![
+            "const A: u32 = 1;",
+            "const B: u32 = 2;",
+            "const C: u32 = 3;",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_flush() -> genco::fmt::Result {
+    let attributes: Vec<&str> = Vec::new();
+
+    let mut tokens = Tokens::<Rust>::new();
+
+    quote_in! { tokens =>
+        struct Foo $(ref t => for a in attributes.iter().copied() {
+            quote_in!(*t => $a);
+        })$[flush]
+    };
+
+    tokens.append(";");
+    assert_eq!("struct Foo;", tokens.to_string()?);
+
+    let mut tokens = Tokens::<Rust>::new();
+    quote_in!(tokens => a$['\n']$[flush]);
+    tokens.flush();
+    tokens.append("b");
+    assert_eq!("ab", tokens.to_string()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_indent_dedent() -> genco::fmt::Result {
+    let expected: Tokens<Rust> = quote! {
+        fn test() {
+            if true {
+                println!("Hello");
+            }
+
+            println!("World");
+        }
+    };
+
+    let mut tokens = Tokens::<Rust>::new();
+    tokens.append("fn test() {");
+    tokens.indent();
+    tokens.append("if true {");
+    tokens.indent();
+    tokens.append("println!(\"Hello\");");
+    tokens.dedent();
+    tokens.append("}");
+    tokens.line();
+    tokens.append("println!(\"World\");");
+    tokens.dedent();
+    tokens.append("}");
+
+    assert_eq!(expected.to_file_vec()?, tokens.to_file_vec()?);
+    Ok(())
+}
+
+#[test]
+#[should_panic = "dedent without a matching indent in the token stream"]
+fn test_dedent_underflow() {
+    let mut tokens = Tokens::<Rust>::new();
+    tokens.indent();
+    tokens.append("foo");
+    tokens.dedent();
+    tokens.dedent();
+}
+
+#[test]
+fn test_surround() -> genco::fmt::Result {
+    let mut tokens: rust::Tokens = quote!(fn main());
+    tokens.block(quote! {
+        let v = vec![1, 2];
+        if v.is_empty() {
+            return;
+        }
+    });
+
+    assert_eq!(
+        vec![
+            "fn main() {",
+            "    let v = vec![1, 2];",
+            "    if v.is_empty() {",
+            "        return;",
+            "    }",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    let mut tokens: rust::Tokens = Tokens::new();
+    tokens.surround("[", quote!(1, 2), "]");
+    tokens.block(Tokens::new());
+    assert_eq!("[1, 2] {}", tokens.to_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_empty_block() -> genco::fmt::Result {
+    let empty: rust::Tokens = quote!($(register(rust::import("std::fmt", "Debug"))));
+    let body: rust::Tokens = quote!(todo!());
+
+    let tokens: rust::Tokens = quote! {
+        fn empty() {
+            $(&empty)
+        }
+
+        fn body() {
+            $(&body)
+        }
+    };
+
+    assert_eq!(
+        vec![
+            "use std::fmt::Debug;",
+            "",
+            "fn empty() {}",
+            "",
+            "fn body() {",
+            "    todo!()",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    let mut tokens: rust::Tokens = quote!(struct Empty);
+    tokens.block(empty);
+    tokens.push();
+    tokens.append(quote!(fn body()));
+    tokens.block(body);
+
+    assert_eq!(
+        vec![
+            "use std::fmt::Debug;",
+            "",
+            "struct Empty {}",
+            "fn body() {",
+            "    todo!()",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_empty_block_without_delimiter() -> genco::fmt::Result {
+    let empty: python::Tokens = quote!($(register(python::import_module("os"))));
+
+    let tokens: python::Tokens = quote! {
+        def foo():
+            $(&empty)
+        def bar():
+            pass
+    };
+
+    assert_eq!(
+        vec!["import os", "", "def foo():", "def bar():", "    pass",],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_default_indentation() -> genco::fmt::Result {
+    let tokens: go::Tokens = quote! {
+        func main() {
+            if true {
+                run()
+            }
+        }
+    };
+
+    assert_eq!(
+        vec!["func main() {", "\tif true {", "\t\trun()", "\t}", "}"],
+        tokens.to_file_vec()?
+    );
+
+    let tokens: python::Tokens = quote! {
+        def main():
+            if True:
+                run()
+    };
+
+    assert_eq!(
+        vec!["def main():", "    if True:", "        run()"],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use genco::prelude::*;
+
+#[test]
+fn test_builtin_imports() -> genco::fmt::Result {
+    let string = java::import("java.lang", "String");
+    let list = java::import("java.util", "List");
+
+    let tokens: java::Tokens = quote! {
+        $(&list)<$string> names = $list.of();
+    };
+
+    assert_eq!(
+        vec![
+            "import java.util.List;",
+            "",
+            "List<String> names = List.of();",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
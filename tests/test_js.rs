@@ -0,0 +1,181 @@
+use genco::fmt;
+use genco::prelude::*;
+
+fn render(tokens: &js::Tokens, quote: js::QuoteChar) -> fmt::Result<Vec<String>> {
+    let mut w = fmt::VecWriter::new();
+    let fmt = fmt::Config::from_lang::<JavaScript>();
+    let config = js::Config::default().with_string_quote(quote);
+    tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+    Ok(w.into_vec())
+}
+
+#[test]
+fn test_string_quote() -> fmt::Result {
+    let tokens: js::Tokens = quote! {
+        const a = $(quoted("it's a \"${test}\" `quote`"));
+        const b = $[str](Hello $(user));
+    };
+
+    assert_eq!(
+        vec![
+            "const a = \"it's a \\\"${test}\\\" `quote`\";",
+            "const b = `Hello ${user}`;",
+        ],
+        render(&tokens, js::QuoteChar::Double)?
+    );
+
+    assert_eq!(
+        vec![
+            "const a = 'it\\'s a \"${test}\" `quote`';",
+            "const b = `Hello ${user}`;",
+        ],
+        render(&tokens, js::QuoteChar::Single)?
+    );
+
+    assert_eq!(
+        vec![
+            "const a = `it's a \"\\${test}\" \\`quote\\``;",
+            "const b = `Hello ${user}`;",
+        ],
+        render(&tokens, js::QuoteChar::Backtick)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_template() -> fmt::Result {
+    let name = js::template(["Hello ", "!"], [quote!(name)]);
+    let tokens: js::Tokens = quote!(const greeting = $name;);
+    assert_eq!("const greeting = `Hello ${name}!`;", tokens.to_string()?);
+
+    let escaped = js::template(
+        ["`${raw}` and ", " then `$`"],
+        [quote!(lookup($(quoted("key`s"))))],
+    );
+
+    let tokens: js::Tokens = quote!(const text = $escaped;);
+
+    assert_eq!(
+        "const text = `\\`\\${raw}\\` and ${lookup(\"key`s\")} then \\`$\\``;",
+        tokens.to_string()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_relative_imports() -> fmt::Result {
+    let sibling = js::import(js::Module::Path("a/b".into()), "b");
+    let parent = js::import(js::Module::Path("lib.mjs".into()), "lib");
+
+    let tokens: js::Tokens = quote! {
+        $sibling($parent);
+    };
+
+    let mut w = fmt::VecWriter::new();
+    let fmt = fmt::Config::from_lang::<JavaScript>();
+    let config = js::Config::default().with_module_path("a/c");
+    tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+
+    assert_eq!(
+        vec![
+            "import {b} from \"./b.js\";",
+            "import {lib} from \"../lib.mjs\";",
+            "",
+            "b(lib);",
+        ],
+        w.into_vec()
+    );
+
+    let tokens: js::Tokens = quote! {
+        $(js::import(js::Module::Path("./foo".into()), "foo"))();
+    };
+
+    assert_eq!(
+        vec!["import {foo} from \"./foo\";", "", "foo();"],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_bin_op() -> fmt::Result {
+    use genco::tokens::bin_op;
+
+    let power = bin_op(quote!(a), "**", bin_op(quote!(b), "**", quote!(c)));
+    let grouped = bin_op(bin_op(quote!(a), "**", quote!(b)), "**", quote!(c));
+
+    let tokens: js::Tokens = quote!($power + $grouped);
+    assert_eq!("a ** b ** c + (a ** b) ** c", tokens.to_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_exports() -> fmt::Result {
+    let tokens: js::Tokens = quote! {
+        $(js::export("add", quote! {
+            function add(a, b) {
+                return a + b;
+            }
+        }))
+
+        $(js::export("zero", quote!(const zero = 0;)))
+
+        $(js::export("main", quote!(function main() {})).into_default())
+    };
+
+    let fmt = fmt::Config::from_lang::<JavaScript>();
+
+    let mut w = fmt::VecWriter::new();
+    tokens.format_file(&mut w.as_formatter(&fmt), &js::Config::default())?;
+
+    assert_eq!(
+        vec![
+            "function add(a, b) {",
+            "    return a + b;",
+            "}",
+            "",
+            "const zero = 0;",
+            "",
+            "function main() {}",
+            "",
+            "export {add, zero};",
+            "export default main;",
+        ],
+        w.into_vec()
+    );
+
+    let mut w = fmt::VecWriter::new();
+    let config = js::Config::default().with_export_style(js::ExportStyle::Inline);
+    tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+
+    assert_eq!(
+        vec![
+            "export function add(a, b) {",
+            "    return a + b;",
+            "}",
+            "",
+            "export const zero = 0;",
+            "",
+            "export default function main() {}",
+        ],
+        w.into_vec()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_async_await() -> fmt::Result {
+    let js: js::Tokens = quote!($(js::async_(quote!(function f() { return $(js::await_("x")); }))));
+    assert_eq!("async function f() { return await x; }", js.to_string()?);
+
+    let rust: rust::Tokens = quote!($(rust::async_fn("f()", quote!($(rust::await_("x"))))));
+    assert_eq!(
+        vec!["async fn f() {", "    x.await", "}"],
+        rust.to_file_vec()?
+    );
+    Ok(())
+}
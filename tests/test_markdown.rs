@@ -0,0 +1,63 @@
+use genco::prelude::*;
+
+#[test]
+fn test_heading_and_code_block() -> genco::fmt::Result {
+    let code: js::Tokens = quote! {
+        function hello() {
+            const fence = "```";
+            return $[str](Hello $(name) *world*);
+        }
+    };
+
+    let toks: markdown::Tokens = quote! {
+        $(markdown::heading(2, markdown::text("hello_world()")))
+
+        $(markdown::code_block("js", code.to_file_string()?))
+
+        $(markdown::ordered_list([markdown::link("docs", "docs/README.md")]))
+    };
+
+    assert_eq!(
+        vec![
+            "## hello\\_world\\(\\)",
+            "",
+            "````js",
+            "function hello() {",
+            "    const fence = \"```\";",
+            "    return `Hello ${name} *world*`;",
+            "}",
+            "````",
+            "",
+            "1. [docs](docs/README.md)",
+        ],
+        toks.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_text_block_markers() -> genco::fmt::Result {
+    let toks: markdown::Tokens = quote! {
+        $(markdown::text("- not a list"))
+        $(markdown::text("+ nor this"))
+        $(markdown::text("12. nor this"))
+        $(markdown::text("=="))
+        a $(markdown::text("- b + c = d. 1. e"))
+        $(markdown::text("![img](x) & co\n  - next"))
+    };
+
+    assert_eq!(
+        vec![
+            "\\- not a list",
+            "\\+ nor this",
+            "12\\. nor this",
+            "\\==",
+            "a - b + c = d. 1. e",
+            "\\!\\[img\\]\\(x\\) \\& co\n  \\- next",
+        ],
+        toks.to_file_vec()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use genco::fmt;
+use genco::prelude::*;
+
+#[test]
+fn test_open_and_qualified() -> fmt::Result {
+    let map = ocaml::imported("List", "map");
+    let iter = ocaml::imported("List", "iter");
+    let length = ocaml::qualified("Array", "length");
+    let printf = ocaml::qualified("Printf", "printf");
+
+    let tokens: ocaml::Tokens = quote! {
+        let print_all xs =
+          $iter ($printf "%s\n") ($map String.uppercase_ascii xs)
+
+        let size a = $length a
+    };
+
+    assert_eq!(
+        vec![
+            "open List",
+            "",
+            "let print_all xs =",
+            "  iter (Printf.printf \"%s\\n\") (map String.uppercase_ascii xs)",
+            "",
+            "let size a = Array.length a",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    let tokens: ocaml::Tokens = quote!($(ocaml::qualified("List", "map")));
+    assert_eq!(vec!["List.map"], tokens.to_file_vec()?);
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use genco::prelude::*;
+
+fn helper() -> rust::Tokens {
+    quote! {
+        $(once "clamp" =>
+            fn clamp(v: i32) -> i32 {
+                v.max(0).min(10)
+            }
+        )
+    }
+}
+
+#[test]
+fn test_once() -> genco::fmt::Result {
+    let tokens: rust::Tokens = quote! {
+        $(helper())
+
+        fn a() -> i32 {
+            clamp(1)
+        }
+
+        $(helper())
+
+        fn b() -> i32 {
+            clamp(2)
+        }
+    };
+
+    assert_eq!(
+        vec![
+            "fn clamp(v: i32) -> i32 {",
+            "    v.max(0).min(10)",
+            "}",
+            "",
+            "fn a() -> i32 {",
+            "    clamp(1)",
+            "}",
+            "",
+            "fn b() -> i32 {",
+            "    clamp(2)",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_once_keys() -> genco::fmt::Result {
+    let once = "not a keyword";
+    let key = String::from("b");
+
+    let tokens: Tokens = quote! {
+        $(once "a" { a1 })
+        $(once &key => b1 $(once "a" => nested))
+        $(once "a" => a2)
+        $(once &key => b2)
+        $(once "c" => c1 $(once "c" => c2))
+        $once
+    };
+
+    assert_eq!(
+        "<open-once \"a\"> \"a1\" <close-once> <push> <open-once \"b\"> \"b1\" <space> <open-once \"a\"> \"nested\" <close-once> <close-once> <push> <open-once \"a\"> \"a2\" <close-once> <push> <open-once \"b\"> \"b2\" <close-once> <push> <open-once \"c\"> \"c1\" <space> <open-once \"c\"> \"c2\" <close-once> <close-once> <push> \"not a keyword\"",
+        format!("{:?}", tokens.debug_items())
+    );
+
+    let expected = vec!["a1", "b1", "c1", "not a keyword"];
+
+    // NB: every formatting session keeps track of its own keys.
+    assert_eq!(expected, tokens.to_file_vec()?);
+    assert_eq!(expected, tokens.to_file_vec()?);
+    Ok(())
+}
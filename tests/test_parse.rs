@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use genco::prelude::*;
+use genco::tokens::ParseError;
+
+#[test]
+fn test_parse() -> Result<(), Box<dyn std::error::Error>> {
+    let mut bindings = HashMap::new();
+    bindings.insert(String::from("name"), quote!(Config));
+
+    let template = r#"
+        struct $name {
+            value: u32,
+
+            other:   u32,
+        }
+
+        impl $name {
+        }
+    "#;
+
+    let tokens = rust::Tokens::parse(template, &bindings)?;
+
+    assert_eq!(
+        vec![
+            "struct Config {",
+            "    value: u32,",
+            "",
+            "    other: u32,",
+            "}",
+            "",
+            "impl Config {",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_errors() {
+    let bindings = HashMap::<String, rust::Tokens>::new();
+
+    assert_eq!(
+        Err(ParseError::MissingBinding {
+            name: String::from("missing"),
+            line: 2,
+        }),
+        rust::Tokens::parse("a\nb $missing", &bindings)
+    );
+
+    assert_eq!(
+        Err(ParseError::ExpectedName { line: 1, column: 5 }),
+        rust::Tokens::parse("  a $ b", &bindings)
+    );
+
+    assert_eq!(
+        Err(ParseError::Indentation { line: 3 }),
+        rust::Tokens::parse("a\n    b\n  c", &bindings)
+    );
+
+    assert_eq!(
+        Err(ParseError::Indentation { line: 2 }),
+        rust::Tokens::parse("  a\nb", &bindings)
+    );
+}
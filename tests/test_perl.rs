@@ -0,0 +1,47 @@
+use genco::fmt;
+use genco::prelude::*;
+
+#[test]
+fn test_qw_collation() -> fmt::Result {
+    let sum = perl::imported("List::Util", "sum");
+    let max = perl::imported("List::Util", "max");
+    let min = perl::imported("List::Util", "min");
+    let basename = perl::imported("File::Basename", "basename");
+
+    let tokens: perl::Tokens = quote! {
+        my $$total = $(&sum)(@ARGV);
+        my $$range = $max(@ARGV) - $min(@ARGV);
+        print $basename($$0), " ", $sum(@ARGV);
+    };
+
+    assert_eq!(
+        vec![
+            "use File::Basename qw(basename);",
+            "use List::Util qw(max min sum);",
+            "",
+            "my $total = sum(@ARGV);",
+            "my $range = max(@ARGV) - min(@ARGV);",
+            "print basename($0), \" \", sum(@ARGV);",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    let mut w = fmt::VecWriter::new();
+    let fmt = fmt::Config::from_lang::<Perl>();
+    let config = perl::Config::default().with_import_order(genco::lang::ImportOrder::AsEncountered);
+    tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+
+    assert_eq!(
+        vec![
+            "use List::Util qw(sum max min);",
+            "use File::Basename qw(basename);",
+            "",
+            "my $total = sum(@ARGV);",
+            "my $range = max(@ARGV) - min(@ARGV);",
+            "print basename($0), \" \", sum(@ARGV);",
+        ],
+        w.into_vec()
+    );
+
+    Ok(())
+}
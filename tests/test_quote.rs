@@ -77,3 +77,178 @@ fn test_scope() -> genco::fmt::Result {
 
     Ok(())
 }
+
+#[test]
+fn test_nested_scope() -> genco::fmt::Result {
+    let names = ["a", "b"];
+    let mut seen = Vec::new();
+
+    let mut out = rust::Tokens::new();
+
+    quote_in! { out =>
+        fn test() {
+            $(ref toks => for name in names {
+                quote_in! { *toks =>
+                    $(ref toks {
+                        seen.push(name);
+                        quote_in!(*toks => let $name = $(seen.len()););
+                    })
+                }
+
+                toks.push();
+            })
+        }
+    };
+
+    let out_ref = &mut out;
+    out_ref.push();
+
+    quote_in! { *out_ref =>
+        $(ref toks => quote_in!(toks => $(ref toks => quote_in!(toks => done();))))
+    };
+
+    assert_eq!(
+        vec![
+            "fn test() {",
+            "    let a = 1;",
+            "    let b = 2;",
+            "}",
+            "done();",
+        ],
+        out.to_file_vec()?
+    );
+
+    assert_eq!(seen, ["a", "b"]);
+    Ok(())
+}
+
+#[test]
+fn test_repeat() -> genco::fmt::Result {
+    let tokens: Tokens = quote!($(repeat 3 join (, ) => ?));
+    assert_eq!("?, ?, ?", tokens.to_string()?);
+
+    let count = 2;
+    let tokens: Tokens = quote!(let $(repeat count => _) = ($(repeat count join (, ) => v$index)););
+    assert_eq!("let __ = (v0, v1);", tokens.to_string()?);
+
+    let tokens: Tokens = quote!(f($(repeat 0 join (, ) => ?)));
+    assert_eq!("f()", tokens.to_string()?);
+
+    // Not a repetition, but a variable named `repeat`.
+    let repeat = "r";
+    let tokens: Tokens = quote!($(repeat) $repeat);
+    assert_eq!("r r", tokens.to_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_sorted_loop() -> genco::fmt::Result {
+    use std::collections::{HashMap, HashSet};
+
+    let render = || -> rust::Tokens {
+        let map = (0..32u32).map(|n| (n, n * 2)).collect::<HashMap<_, _>>();
+        quote!($(for (k, v) in map sorted join (, ) => $k: $v))
+    };
+
+    let expected = (0..32u32)
+        .map(|n| format!("{}: {}", n, n * 2))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    for _ in 0..8 {
+        assert_eq!(expected, render().to_string()?);
+    }
+
+    let set = ["c", "a", "b"].iter().copied().collect::<HashSet<_>>();
+    let tokens: Tokens = quote!($(for s in set sorted => $s));
+    assert_eq!("abc", tokens.to_string()?);
+
+    // Not a modifier, but a variable named `sorted`.
+    let sorted = "s";
+    let tokens: Tokens = quote!($(for n in 0..2 => $n $sorted));
+    assert_eq!("0 s1 s", tokens.to_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_borrowed_interpolation() -> genco::fmt::Result {
+    fn render(body: &rust::Tokens, name: &&str, count: &u32) -> rust::Tokens {
+        quote! {
+            fn $name() -> u32 {
+                $body
+                $(&body)
+                $count
+            }
+        }
+    }
+
+    let body: rust::Tokens = quote!(tick(););
+    let tokens = render(&body, &"run", &3);
+
+    assert_eq!(
+        vec![
+            "fn run() -> u32 {",
+            "    tick();",
+            "    tick();",
+            "    3",
+            "}"
+        ],
+        tokens.to_file_vec()?
+    );
+
+    let bodies = vec![&body, &body];
+    let tokens: rust::Tokens = quote!($(for b in &bodies join ( ) => $b));
+    assert_eq!("tick(); tick();", tokens.to_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_loop_binding_modes() -> genco::fmt::Result {
+    let items = vec![String::from("a"), String::from("b")];
+
+    let tokens: Tokens = quote!($(for s in &items join (, ) => $s));
+    assert_eq!("a, b", tokens.to_string()?);
+
+    let tokens: Tokens = quote!($(for ref s in items.clone() join (, ) => $s));
+    assert_eq!("a, b", tokens.to_string()?);
+
+    let tokens: Tokens = quote!($(for mut s in items.clone() join (, ) => $({ s.push('!'); s })));
+    assert_eq!("a!, b!", tokens.to_string()?);
+
+    let mut pairs = [(1, String::from("x")), (2, String::from("y"))];
+    let tokens: Tokens = quote! {
+        $(for (n, ref mut s) in pairs.iter_mut() join (, ) => $({ s.push('?'); *n }) $(&**s))
+    };
+    assert_eq!("1 x?, 2 y?", tokens.to_string()?);
+    assert_eq!("x?", pairs[0].1);
+
+    // NB: the items are still available since they were only borrowed.
+    assert_eq!(2, items.len());
+    Ok(())
+}
+
+#[test]
+fn test_spread() -> genco::fmt::Result {
+    let args: Vec<Tokens> = vec![quote!(a), quote!(b), quote!(c)];
+
+    let tokens: Tokens = quote!(call($(spread &args with ", ")));
+    assert_eq!("call(a, b, c)", tokens.to_string()?);
+
+    let sep = String::from(" | ");
+    let tokens: Tokens = quote!($(spread args.iter().rev() with &sep));
+    assert_eq!("c | b | a", tokens.to_string()?);
+
+    // NB: the separator is only evaluated once, so it can be moved.
+    let sep = String::from(", ");
+    let tokens: Tokens = quote!(call($(spread &args with sep)));
+    assert_eq!("call(a, b, c)", tokens.to_string()?);
+
+    let tokens: Tokens = quote!(call($(spread Vec::<Tokens>::new() with ", ")));
+    assert_eq!("call()", tokens.to_string()?);
+
+    // NB: a variable named `spread` is still evaluated as one.
+    let spread = "x";
+    let tokens: Tokens = quote!($(spread) $(spread.len()));
+    assert_eq!("x 1", tokens.to_string()?);
+    Ok(())
+}
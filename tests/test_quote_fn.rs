@@ -0,0 +1,33 @@
+use genco::prelude::*;
+
+/// quote_fn! captures bindings by move and can be applied to a fresh token
+/// stream.
+#[test]
+fn test_quote_fn_captures() -> genco::fmt::Result {
+    let name = String::from("hello");
+
+    let f = quote_fn! {
+        fn $name() {}
+    };
+
+    let mut tokens = rust::Tokens::new();
+    tokens.append(f);
+
+    assert_eq!("fn hello() {}", tokens.to_string()?);
+    Ok(())
+}
+
+/// Copyable captures produce a reusable quote_fn!.
+#[test]
+fn test_quote_fn_reusable() -> genco::fmt::Result {
+    let name = "hello";
+
+    let f = quote_fn!(fn $name() {});
+
+    let a: rust::Tokens = quote!($f);
+    let b: rust::Tokens = quote!($f);
+
+    assert_eq!("fn hello() {}", a.to_string()?);
+    assert_eq!(a, b);
+    Ok(())
+}
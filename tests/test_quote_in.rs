@@ -17,3 +17,32 @@ fn test_quote_into_unit() -> genco::fmt::Result {
     assert_eq!("uint32", tokens.to_string()?);
     Ok(())
 }
+
+struct Generator {
+    buf: rust::Tokens,
+}
+
+impl Generator {
+    fn buf(&mut self) -> &mut rust::Tokens {
+        &mut self.buf
+    }
+}
+
+/// quote_in! accepts struct fields, mutable references and method calls.
+#[test]
+fn test_quote_in_place_expressions() -> genco::fmt::Result {
+    fn into_ref(tokens: &mut rust::Tokens) {
+        quote_in!(tokens => c);
+    }
+
+    let mut generator = Generator {
+        buf: rust::Tokens::new(),
+    };
+
+    quote_in!(generator.buf => a);
+    quote_in!(generator.buf() => $[' ']b);
+    into_ref(&mut generator.buf);
+
+    assert_eq!("a bc", generator.buf.to_string()?);
+    Ok(())
+}
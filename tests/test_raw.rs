@@ -0,0 +1,160 @@
+use genco::prelude::*;
+
+/// A pre-formatted multi-line SQL snippet is embedded unchanged.
+#[test]
+fn test_raw_sql() -> genco::fmt::Result {
+    let sql = String::from(
+        "SELECT u.id,\n       u.name\n  FROM users u\n WHERE u.id  =  ?\n   AND u.active",
+    );
+
+    let tokens: rust::Tokens = quote! {
+        fn query() {
+            let sql = $(raw &sql);
+            run(sql);
+        }
+    };
+
+    assert_eq!(
+        vec![
+            "fn query() {",
+            "    let sql = SELECT u.id,",
+            "       u.name",
+            "  FROM users u",
+            " WHERE u.id  =  ?",
+            "   AND u.active;",
+            "    run(sql);",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    assert_eq!(
+        "fn query() {\n    let sql = SELECT u.id,\n       u.name\n  FROM users u\n WHERE u.id  =  ?\n   AND u.active;\n    run(sql);\n}\n",
+        tokens.to_file_string()?
+    );
+
+    Ok(())
+}
+
+/// A variable named `raw` can still be evaluated.
+#[test]
+fn test_raw_as_variable() -> genco::fmt::Result {
+    let raw = "a";
+
+    let tokens: Tokens = quote!($(raw) $(raw.len()));
+    assert_eq!("a 1", tokens.to_string()?);
+    Ok(())
+}
+
+/// A function named `raw` can still be called.
+#[test]
+fn test_raw_as_function() -> genco::fmt::Result {
+    fn raw(n: u32) -> String {
+        format!("n{}", n)
+    }
+
+    let tokens: Tokens = quote!($(raw(1)) $(raw(2).len()) $(raw "a\nb"));
+    assert_eq!("n1 2 a\nb", tokens.to_string()?);
+    Ok(())
+}
+
+/// A function named `verbatim` can still be called.
+#[test]
+fn test_verbatim_as_function() -> genco::fmt::Result {
+    fn verbatim(n: u32) -> String {
+        format!("n{}", n)
+    }
+
+    let tokens: Tokens = quote!($(verbatim(1)) $(verbatim(2).len()) $(verbatim "a"));
+    assert_eq!("n1 2 a", tokens.to_string()?);
+    Ok(())
+}
+
+/// A verbatim block keeps its relative indentation at the current level.
+#[test]
+fn test_verbatim_block() -> genco::fmt::Result {
+    let banner = "\n+-------+\n  | genco |\n+-------+\n\n\n  done\n";
+
+    let tokens: python::Tokens = quote! {
+        class Banner:
+            def show(self):
+                $(verbatim banner)
+            $(verbatim "x = 1")
+    };
+
+    assert_eq!(
+        vec![
+            "class Banner:",
+            "    def show(self):",
+            "        +-------+",
+            "          | genco |",
+            "        +-------+",
+            "",
+            "          done",
+            "    x = 1",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+/// Embedded code indented with two spaces is re-indented with four.
+#[test]
+fn test_normalize_indentation() -> genco::fmt::Result {
+    let block = "class Foo:\n  def bar(self):\n    if self.x:\n      return 1\n\n    return 2";
+
+    let mut tokens: python::Tokens = quote! {
+        import os
+
+        $(raw block)
+    };
+
+    let before = tokens.clone();
+    tokens.normalize_indentation(2);
+    assert_eq!(before, tokens);
+
+    tokens.normalize_indentation(4);
+
+    assert_eq!(
+        vec![
+            "import os",
+            "",
+            "class Foo:",
+            "    def bar(self):",
+            "        if self.x:",
+            "            return 1",
+            "",
+            "        return 2",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+/// Odd continuation lines, like those of a doc comment, don't determine the
+/// indentation unit.
+#[test]
+fn test_normalize_indentation_doc_comment() -> genco::fmt::Result {
+    let block = "class Foo {\n  /**\n   * Run it.\n   */\n  run() {\n    go();\n  }\n}";
+
+    let mut tokens: js::Tokens = quote!($(raw block));
+    tokens.normalize_indentation(4);
+
+    assert_eq!(
+        vec![
+            "class Foo {",
+            "    /**",
+            "     * Run it.",
+            "     */",
+            "    run() {",
+            "        go();",
+            "    }",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use genco::fmt;
+use genco::lang::ImportOrder;
+use genco::prelude::*;
+
+fn rust_file(order: ImportOrder) -> fmt::Result<String> {
+    let names = ["HashMap", "BTreeMap", "HashSet", "BTreeSet", "VecDeque"];
+
+    let tokens: rust::Tokens = quote! {
+        $(for name in names {
+            let _ = $(rust::import("std::collections", name))::new();$['\r']
+        })
+        let _ = $(rust::import("std::rc", "Rc"))::new(());
+        let _ = $(rust::import("std::cell", "Cell"))::new(());
+        let _ = $(rust::import("alloc::sync", "Arc"))::new(());
+    };
+
+    let mut out = String::new();
+    let fmt = fmt::Config::from_lang::<Rust>();
+    let config = rust::Config::default().with_import_order(order);
+    tokens.format_file(
+        &mut fmt::FmtWriter::new(&mut out).as_formatter(&fmt),
+        &config,
+    )?;
+    Ok(out)
+}
+
+fn python_file() -> fmt::Result<String> {
+    let tokens: python::Tokens = quote! {
+        $(python::import("os.path", "join"))
+        $(python::import("collections", "deque"))
+        $(python::import("os.path", "basename"))
+        $(python::import_module("sys"))
+        $(python::import_module("json"))
+    };
+
+    tokens.to_file_string()
+}
+
+#[test]
+fn test_reproducible_output() -> fmt::Result {
+    for order in [ImportOrder::Sorted, ImportOrder::AsEncountered] {
+        let first = rust_file(order)?;
+
+        for _ in 0..8 {
+            assert_eq!(first.as_bytes(), rust_file(order)?.as_bytes());
+        }
+    }
+
+    let first = python_file()?;
+
+    for _ in 0..8 {
+        assert_eq!(first.as_bytes(), python_file()?.as_bytes());
+    }
+
+    assert!(rust_file(ImportOrder::Sorted)?.starts_with("use alloc::sync::Arc;\n"));
+    Ok(())
+}
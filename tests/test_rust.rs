@@ -0,0 +1,263 @@
+use genco::fmt;
+use genco::prelude::*;
+
+#[test]
+fn test_relative_imports() -> fmt::Result {
+    let bar = rust::import("crate::foo", "Bar");
+    let error = rust::import("self::error", "Error");
+    let aliased = rust::import("self::error", "Error").with_alias("E");
+    let module = rust::import("self::error", "Kind").with_module_alias("errors");
+    let local = rust::import("self", "Config").qualified();
+
+    let tokens: rust::Tokens = quote! {
+        fn run(bar: $bar, config: $local) -> Result<(), $error> {
+            let _: $aliased = $module::Other.into();
+        }
+    };
+
+    assert_eq!(
+        vec![
+            "use crate::foo::Bar;",
+            "use self::error::{self as errors, Error as E};",
+            "",
+            "fn run(bar: Bar, config: Config) -> Result<(), error::Error> {",
+            "    let _: E = errors::Kind::Other.into();",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_generics() -> fmt::Result {
+    let display = rust::import("std::fmt", "Display");
+
+    let generics = rust::Generics::new()
+        .with_lifetime("'a")
+        .with("T", [quote!($(&display)), quote!($(rust::lifetime("a")))]);
+
+    let tokens: rust::Tokens = quote! {
+        struct Wrapper$(&generics) {
+            value: &$(rust::lifetime("a")) T,
+        }
+
+        impl$(&generics) $display for Wrapper$(generics.arguments()) {
+        }
+    };
+
+    assert_eq!(
+        vec![
+            "use std::fmt::Display;",
+            "",
+            "struct Wrapper<'a, T: Display + 'a> {",
+            "    value: &'a T,",
+            "}",
+            "",
+            "impl<'a, T: Display + 'a> Display for Wrapper<'a, T> {",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    let only_types = rust::Generics::new().with("T", [] as [&str; 0]);
+    let tokens: rust::Tokens = quote!(fn id$(&only_types)(value: T) -> T);
+    assert_eq!("fn id<T>(value: T) -> T", tokens.to_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_bin_op() -> fmt::Result {
+    use genco::tokens::bin_op;
+
+    let product = bin_op(quote!(a), "*", bin_op(quote!(b), "+", quote!(c)));
+    let sum = bin_op(bin_op(quote!(a), "+", quote!(b)), "+", quote!(c));
+    let compare = bin_op(bin_op(quote!(a), "==", quote!(b)), "==", quote!(c));
+    let assign = bin_op(quote!(a), "=", bin_op(quote!(b), "=", quote!(c)));
+    let unknown = bin_op(quote!(a), "<>", bin_op(quote!(b), "*", quote!(c)));
+
+    let tokens: rust::Tokens = quote! {
+        $product;
+        $sum;
+        $compare;
+        $assign;
+        $unknown;
+    };
+
+    assert_eq!(
+        vec![
+            "a * (b + c);",
+            "a + b + c;",
+            "(a == b) == c;",
+            "a = b = c;",
+            "a <> (b * c);",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_match_expr() -> fmt::Result {
+    let arms = vec![
+        (quote!(Some(0)), quote!(None)),
+        (
+            quote!(Some(n) if n > 10),
+            quote! {
+                let half = n / 2;
+                Some(half)
+            },
+        ),
+        (quote!(other), quote!(other)),
+    ];
+
+    let tokens: rust::Tokens = quote! {
+        fn halve(value: Option<u32>) -> Option<u32> {
+            $(rust::match_expr("value", arms))
+        }
+    };
+
+    assert_eq!(
+        vec![
+            "fn halve(value: Option<u32>) -> Option<u32> {",
+            "    match value {",
+            "        Some(0) => {",
+            "            None",
+            "        },",
+            "        Some(n) if n > 10 => {",
+            "            let half = n / 2;",
+            "            Some(half)",
+            "        },",
+            "        other => {",
+            "            other",
+            "        },",
+            "    }",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    #[cfg(feature = "pretty")]
+    assert!(rust::format_pretty(&tokens).is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_conditional_imports() -> fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+    let set = rust::import("std::collections", "HashSet");
+    let rc = rust::import("std::rc", "Rc");
+    let cond = false;
+
+    let tokens: rust::Tokens = quote! {
+        let a: $(if cond { $(&map) } else { $(&set) });
+        let b = $(if cond => $(&rc)::new)(1);
+        let c = $(match cond { true => $(&map)::new(), false => None });
+    };
+
+    assert_eq!(
+        vec![
+            "use std::collections::HashSet;",
+            "",
+            "let a: HashSet;",
+            "let b = (1);",
+            "let c = None;",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_impl_block() -> fmt::Result {
+    let default = rust::import("std::default", "Default");
+    let generics = rust::Generics::new().with("T", [quote!($(&default))]);
+
+    let inherent = rust::impl_block(
+        None,
+        "Foo",
+        quote! {
+            fn new() -> Self {
+                Foo
+            }
+        },
+    );
+
+    let trait_impl = rust::impl_block(
+        Some(quote!($default)),
+        quote!(Bar$(generics.arguments())),
+        quote! {
+            fn default() -> Self {
+                Bar(T::default())
+            }
+        },
+    )
+    .with_generics(generics);
+
+    let empty = rust::impl_block(Some(quote!(Send)), "Foo", rust::Tokens::new());
+
+    let tokens: rust::Tokens = quote! {
+        $inherent
+
+        $trait_impl
+
+        $empty
+    };
+
+    assert_eq!(
+        vec![
+            "use std::default::Default;",
+            "",
+            "impl Foo {",
+            "    fn new() -> Self {",
+            "        Foo",
+            "    }",
+            "}",
+            "",
+            "impl<T: Default> Default for Bar<T> {",
+            "    fn default() -> Self {",
+            "        Bar(T::default())",
+            "    }",
+            "}",
+            "",
+            "impl Send for Foo {}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_conflicting_imports() -> fmt::Result {
+    let a = rust::import("a", "Foo");
+    let b = rust::import("b", "Foo");
+    let c = rust::import("c::d", "Foo");
+    let taken = rust::import("e", "Bar").with_alias("Foo2");
+
+    let tokens: rust::Tokens = quote! {
+        let x: $(&a) = $(&b)::new();
+        let y: $(&b) = $c::from($a);
+        let z: $taken = $b::default();
+    };
+
+    assert_eq!(
+        vec![
+            "use a::Foo;",
+            "use b::Foo as Foo3;",
+            "use c::d::Foo as Foo4;",
+            "use e::Bar as Foo2;",
+            "",
+            "let x: Foo = Foo3::new();",
+            "let y: Foo3 = Foo4::from(Foo);",
+            "let z: Foo2 = Foo3::default();",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+#![cfg(feature = "serde")]
+
+use genco::fmt;
+use genco::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+
+genco::impl_lang! {
+    MyLang {
+        type Config = ();
+        type Item = Import;
+        type Format = ();
+
+        fn default_config() -> Self::Config {}
+
+        fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            genco::lang::c_family_write_quoted(out, input)
+        }
+
+        fn format_file(
+            tokens: &Tokens<MyLang>,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut header: Tokens<MyLang> = Tokens::new();
+
+            for import in tokens.walk_imports() {
+                header.push();
+                quote_in!(header => import $(&import.0));
+            }
+
+            header.line();
+            header.format(out, config, &())?;
+            tokens.format(out, config, &())?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &(), _: &()) -> fmt::Result {
+            out.write_str(&self.0)?;
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+struct Import(String);
+
+#[test]
+fn test_round_trip() -> fmt::Result {
+    let map = Import(String::from("Map"));
+    let set = Import(String::from("Set"));
+
+    let tokens: Tokens<MyLang> = quote! {
+        $(register(set))
+
+        fn build() {
+            let m = new $map();
+            m.insert($(quoted("key")), "value");
+        }
+    };
+
+    let json = serde_json::to_string(&tokens).unwrap();
+    let back: Tokens<MyLang> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(json, serde_json::to_string(&back).unwrap());
+    assert_eq!(tokens.walk_imports().count(), back.walk_imports().count());
+
+    let expected = vec![
+        "import Map",
+        "import Set",
+        "",
+        "fn build() {",
+        "    let m = new Map();",
+        "    m.insert(\"key\", \"value\");",
+        "}",
+    ];
+
+    assert_eq!(expected, tokens.to_file_vec()?);
+    assert_eq!(expected, back.to_file_vec()?);
+    Ok(())
+}
+
+#[test]
+fn test_item_form() {
+    let tokens: Tokens<MyLang> = quote!(foo $(Import(String::from("Bar"))));
+
+    assert_eq!(
+        r#"[{"Literal":"foo"},"Space",{"Lang":"Bar"}]"#,
+        serde_json::to_string(&tokens).unwrap()
+    );
+
+    let item: genco::tokens::Item<()> = serde_json::from_str(r#"{"Indentation":-1}"#).unwrap();
+    assert_eq!(genco::tokens::Item::Indentation(-1), item);
+}
+
+#[test]
+fn test_round_trip_rust() -> fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+    let debug = rust::import("std::fmt", "Debug").with_alias("Dbg");
+
+    let tokens: rust::Tokens = quote! {
+        fn build<T: $debug>() -> $(&map)<u32, T> {
+            $map::new()
+        }
+    };
+
+    let json = serde_json::to_string(&tokens).unwrap();
+    let back: rust::Tokens = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(json, serde_json::to_string(&back).unwrap());
+    assert_eq!(tokens.to_file_vec()?, back.to_file_vec()?);
+    Ok(())
+}
+
+#[test]
+fn test_round_trip_js() -> fmt::Result {
+    let react = js::import("react", "React").into_default();
+    let helper = js::import(js::Module::Path("lib/helper".into()), "helper");
+
+    let tokens: js::Tokens = quote!($react.render($helper()););
+
+    let json = serde_json::to_string(&tokens).unwrap();
+    let back: js::Tokens = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(json, serde_json::to_string(&back).unwrap());
+    assert_eq!(tokens.to_file_vec()?, back.to_file_vec()?);
+    Ok(())
+}
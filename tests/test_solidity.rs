@@ -0,0 +1,96 @@
+use genco::fmt;
+use genco::prelude::*;
+
+#[test]
+fn test_contract_with_import_and_function() -> fmt::Result {
+    let ierc20 = solidity::import("./IERC20.sol", "IERC20");
+    let ownable = solidity::import("@openzeppelin/contracts/access/Ownable.sol", "Ownable");
+
+    let withdraw = solidity::function(
+        "withdraw",
+        ["uint256 amount"],
+        quote! {
+            require(amount <= balance, "insufficient balance");
+            balance -= amount;
+            return $(&ierc20)(token).transfer(owner(), amount);
+        },
+    )
+    .with_modifier("external")
+    .with_modifier("onlyOwner")
+    .with_returns("bool");
+
+    let noop = solidity::function("noop", Vec::<&str>::new(), solidity::Tokens::new())
+        .with_modifier("public")
+        .with_modifier("pure");
+
+    let toks: solidity::Tokens = quote! {
+        $(solidity::contract("Treasury", quote! {
+            address public token;
+            uint256 private balance;
+
+            $withdraw
+
+            $noop
+        }).with_base(ownable))
+    };
+
+    let mut w = fmt::VecWriter::new();
+    let fmt = fmt::Config::from_lang::<Solidity>();
+    let config = solidity::Config::default()
+        .with_license("MIT")
+        .with_pragma("^0.8.0");
+    toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+
+    assert_eq!(
+        vec![
+            "// SPDX-License-Identifier: MIT",
+            "pragma solidity ^0.8.0;",
+            "",
+            "import \"./IERC20.sol\";",
+            "import \"@openzeppelin/contracts/access/Ownable.sol\";",
+            "",
+            "contract Treasury is Ownable {",
+            "    address public token;",
+            "    uint256 private balance;",
+            "",
+            "    function withdraw(uint256 amount) external onlyOwner returns (bool) {",
+            "        require(amount <= balance, \"insufficient balance\");",
+            "        balance -= amount;",
+            "        return IERC20(token).transfer(owner(), amount);",
+            "    }",
+            "",
+            "    function noop() public pure {}",
+            "}",
+        ],
+        w.into_vec()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_function_with_imported_parameter() -> fmt::Result {
+    let ierc20 = solidity::import("./IERC20.sol", "IERC20");
+
+    let function = solidity::function(
+        "sweep",
+        [quote!($ierc20 token), quote!(address to)],
+        quote!(token.transfer(to, token.balanceOf(address(this)));),
+    )
+    .with_modifier("external");
+
+    let toks: solidity::Tokens = quote!($function);
+
+    assert_eq!(
+        vec![
+            "import \"./IERC20.sol\";",
+            "",
+            "function sweep(IERC20 token, address to) external {",
+            "    token.transfer(to, token.balanceOf(address(this)));",
+            "}",
+        ],
+        toks.to_file_vec()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "sourcemap")]
+
+use genco::prelude::*;
+use genco::tokens::record_sources;
+
+fn body(name: &str) -> (rust::Tokens, u32) {
+    let line = line!() + 2;
+    let tokens = quote! {
+        println!("Hello {}", $(quoted(name)));
+    };
+    (tokens, line)
+}
+
+#[test]
+fn test_sourcemap() -> genco::fmt::Result {
+    let ((tokens, body_line), start) = record_sources(|| {
+        let (body, body_line) = body("World");
+        let start = line!() + 2;
+        let tokens: rust::Tokens = quote! {
+            fn main() {
+                $body
+            }
+
+            fn other() {}
+        };
+        ((tokens, body_line), start)
+    });
+
+    let (code, map) = tokens.to_file_with_sourcemap()?;
+
+    assert_eq!(
+        "fn main() {\n    println!(\"Hello {}\", \"World\");\n}\n\nfn other() {}\n",
+        code
+    );
+
+    let file = file!();
+
+    let expected = format!(
+        "{{\"1\":\"{file}:{}\",\"2\":\"{file}:{}\",\"3\":\"{file}:{}\",\"5\":\"{file}:{}\"}}",
+        start,
+        body_line,
+        start + 2,
+        start + 4,
+        file = file,
+    );
+
+    assert_eq!(expected, map);
+    Ok(())
+}
+
+#[test]
+fn test_sourcemap_with_imports() -> genco::fmt::Result {
+    let (tokens, start) = record_sources(|| {
+        let map = rust::import("std::collections", "HashMap");
+        let start = line!() + 2;
+        let tokens: rust::Tokens = quote! {
+            let m = $map::new();
+        };
+        (tokens, start)
+    });
+
+    let (code, map) = tokens.to_file_with_sourcemap()?;
+
+    assert_eq!(
+        "use std::collections::HashMap;\n\nlet m = HashMap::new();\n",
+        code
+    );
+    assert_eq!(format!("{{\"3\":\"{}:{}\"}}", file!(), start), map);
+    Ok(())
+}
+
+#[test]
+fn test_sourcemap_not_recording() -> genco::fmt::Result {
+    let tokens: rust::Tokens = quote! {
+        fn main() {}
+    };
+
+    assert_eq!(
+        tokens,
+        quote!(
+            fn main() {}
+        )
+    );
+
+    let (code, map) = tokens.to_file_with_sourcemap()?;
+    assert_eq!("fn main() {}\n", code);
+    assert_eq!("{}", map);
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use genco::fmt;
+use genco::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Spaced;
+
+impl genco::lang::Lang for Spaced {
+    type Config = ();
+    type Format = ();
+    type Item = ();
+
+    fn default_config() -> Self::Config {}
+
+    fn write_literal(out: &mut fmt::Formatter<'_>, literal: &str) -> fmt::Result {
+        fmt::space_operators(out, literal)
+    }
+}
+
+#[test]
+fn test_space_operators() -> fmt::Result {
+    let tokens: Tokens<Spaced> = quote! { a+b };
+    assert_eq!("a + b", tokens.to_string()?);
+
+    let tokens: Tokens<Spaced> = quote! {
+        x=f(-a)*b[1];
+        y=1e-5+x.len()-1;
+        ok=!done&&x>=0 "a+b"
+    };
+
+    assert_eq!(
+        vec![
+            "x = f(-a) * b[1];",
+            "y = 1e-5 + x.len() - 1;",
+            "ok = !done && x >= 0 \"a+b\"",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    let tokens: rust::Tokens = quote! { a+b };
+    assert_eq!("a+b", tokens.to_string()?);
+    Ok(())
+}
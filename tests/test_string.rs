@@ -25,3 +25,125 @@ fn test_string_in_string_in() -> genco::fmt::Result {
     assert_eq!("`Hello ${`${\"World\"}`}`", t.to_string()?);
     Ok(())
 }
+
+#[test]
+fn test_rust_byte_string() -> genco::fmt::Result {
+    let t: rust::Tokens = quote!($(rust::byte_string(b"\0abc")));
+    assert_eq!(r#"b"\x00abc""#, t.to_string()?);
+
+    let t: rust::Tokens = quote!($(rust::byte_string(b"\"quoted\\\"\r\n\t\x7f\x80")));
+    assert_eq!(r#"b"\"quoted\\\"\r\n\t\x7f\x80""#, t.to_string()?);
+
+    let t: rust::Tokens = quote!($(rust::byte_string(&[])));
+    assert_eq!(r#"b"""#, t.to_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_quoted_char() -> genco::fmt::Result {
+    let t: rust::Tokens = quote!($(quoted_char('\n')) $(quoted_char('\\')) $(quoted_char('\'')) $(quoted_char('"')) $(quoted_char('é')));
+    assert_eq!(r#"'\n' '\\' '\'' '\"' 'é'"#, t.to_string()?);
+
+    let t: js::Tokens = quote!($(quoted_char('\'')) $(quoted_char('😊')));
+    assert_eq!(r#"'\'' '😊'"#, t.to_string()?);
+
+    let t: go::Tokens = quote!($(quoted_char('"')) $(quoted_char('\'')) $(quoted_char('\t')));
+    assert_eq!(r#"'"' '\'' '\t'"#, t.to_string()?);
+
+    let t: swift::Tokens = quote!($(quoted_char('\'')));
+    assert_eq!(r#""\'""#, t.to_string()?);
+
+    let t: elixir::Tokens = quote!($(quoted_char(' ')) $(quoted_char('\n')));
+    assert_eq!(r#"?\s ?\n"#, t.to_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_char() -> genco::fmt::Result {
+    let chars = ['a', 'é', '\''];
+
+    let t: rust::Tokens =
+        quote!($('x') $(quoted_char('x')) $(&chars[..]) $(for c in &chars join (, ) => $c));
+    assert_eq!("x 'x' aé' a, é, '", t.to_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_ascii_only() -> genco::fmt::Result {
+    fn ascii<L>(tokens: &Tokens<L>) -> genco::fmt::Result<String>
+    where
+        L: genco::lang::Lang,
+        L::Config: Default,
+        L::Format: Default,
+    {
+        let mut out = String::new();
+        let fmt = genco::fmt::Config::from_lang::<L>().with_ascii_only(true);
+        tokens.fmt_to(&mut out, &fmt)?;
+        Ok(out)
+    }
+
+    let t: rust::Tokens = quote!("é 😊");
+    assert_eq!("\"é 😊\"", t.to_string()?);
+    assert_eq!("\"\\u{00e9} \\u{1f60a}\"", ascii(&t)?);
+
+    let t: js::Tokens = quote!("é 😊");
+    assert_eq!("\"é 😊\"", t.to_string()?);
+    assert_eq!("\"\\u00e9 \\ud83d\\ude0a\"", ascii(&t)?);
+
+    let t: dart::Tokens = quote!("😊");
+    assert_eq!("\"\\ud83d\\ude0a\"", ascii(&t)?);
+
+    let t: swift::Tokens = quote!("😊");
+    assert_eq!("\"\\u{1f60a}\"", ascii(&t)?);
+
+    let t: nim::Tokens = quote!("😊");
+    assert_eq!("\"\\u{1f60a}\"", ascii(&t)?);
+
+    let t: zig::Tokens = quote!("😊");
+    assert_eq!("\"\\u{1f60a}\"", ascii(&t)?);
+
+    let t: elixir::Tokens = quote!("😊");
+    assert_eq!("\"\\u{1F60A}\"", ascii(&t)?);
+
+    let t: python::Tokens = quote!("😊");
+    assert_eq!("\"\\U0001f60a\"", ascii(&t)?);
+
+    let t: html::Tokens = quote!(<p title="é">$(html::text("é & 😊"))</p>);
+    assert_eq!("<p title=\"é\">é &amp; 😊</p>", t.to_string()?);
+    assert_eq!("<p title=\"&#xe9;\">&#xe9; &amp; &#x1f60a;</p>", ascii(&t)?);
+
+    let t: rust::Tokens = quote!($(quoted_char('é')) $(quoted(quoted_char('é'))));
+    assert_eq!("'é' \"'é'\"", t.to_string()?);
+    assert_eq!("'\\u{00e9}' \"'\\\\u{00e9}'\"", ascii(&t)?);
+    Ok(())
+}
+
+#[test]
+fn test_path() -> genco::fmt::Result {
+    use std::path::PathBuf;
+
+    let mut nested = PathBuf::from("out");
+    nested.push("gen");
+    nested.push("mod.rs");
+
+    let t: rust::Tokens = quote!($(quoted(&nested)) $(PathBuf::from("/usr/lib")));
+    assert_eq!("\"out/gen/mod.rs\" /usr/lib", t.to_string()?);
+
+    #[cfg(windows)]
+    {
+        let windows = PathBuf::from("C:\\Users\\john\\file.txt");
+        let t: rust::Tokens = quote!($(&windows));
+        assert_eq!("C:/Users/john/file.txt", t.to_string()?);
+    }
+
+    // NB: a backslash is a regular character in file names outside of
+    // Windows.
+    #[cfg(not(windows))]
+    {
+        let escaped = PathBuf::from("a\\b");
+        let t: rust::Tokens = quote!($(&escaped));
+        assert_eq!("a\\b", t.to_string()?);
+    }
+
+    Ok(())
+}
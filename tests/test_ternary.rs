@@ -0,0 +1,42 @@
+use genco::prelude::*;
+use genco::tokens::ternary;
+
+#[test]
+fn test_ternary() -> genco::fmt::Result {
+    let py: python::Tokens = quote!(x = $(ternary(quote!(a > b), quote!(a), quote!(b))));
+    assert_eq!("x = a if a > b else b", py.to_string()?);
+
+    let js: js::Tokens = quote!(const x = $(ternary(quote!(a > b), quote!(a), quote!(b))););
+    assert_eq!("const x = a > b ? a : b;", js.to_string()?);
+
+    let java: java::Tokens = quote!(int x = $(ternary(quote!(a > b), quote!(a), quote!(b))););
+    assert_eq!("int x = a > b ? a : b;", java.to_string()?);
+
+    let rust: rust::Tokens = quote!(let x = $(ternary(quote!(a > b), quote!(a), quote!(b))););
+    assert_eq!("let x = if a > b { a } else { b };", rust.to_string()?);
+
+    let elixir: elixir::Tokens = quote!(x = $(ternary(quote!(a > b), quote!(a), quote!(b))));
+    assert_eq!("x = if a > b, do: a, else: b", elixir.to_string()?);
+
+    let nim: nim::Tokens = quote!(let x = $(ternary(quote!(a > b), quote!(a), quote!(b))));
+    assert_eq!("let x = if a > b: a else: b", nim.to_string()?);
+
+    let zig: zig::Tokens = quote!(const x = $(ternary(quote!(a > b), quote!(a), quote!(b))););
+    assert_eq!("const x = if (a > b) a else b;", zig.to_string()?);
+
+    let r: r::Tokens = quote!(x <- $(ternary(quote!(a > b), quote!(a), quote!(b))));
+    assert_eq!("x <- if (a > b) a else b", r.to_string()?);
+
+    let go: go::Tokens = quote!(x := $(ternary(quote!(a > b), quote!(a), quote!(b))));
+    assert_eq!(
+        "x := func() any { if a > b { return a }; return b }()",
+        go.to_string()?
+    );
+
+    let cue: cue::Tokens = quote!(x: $(ternary(quote!(a > b), quote!(a), quote!(b))));
+    assert_eq!(
+        "x: [if a > b { a }, if !(a > b) { b }][0]",
+        cue.to_string()?
+    );
+    Ok(())
+}
@@ -266,6 +266,25 @@ fn test_if() {
     };
 }
 
+#[test]
+fn test_if_else_arrow() -> fmt::Result {
+    fn test(cond: bool) -> rust::Tokens {
+        quote! {
+            $(if cond => let a = f(x, y); else => let b = [0];)
+            $(if cond { yes } else => no)
+        }
+    }
+
+    assert_eq!(vec!["let a = f(x, y);", "yes"], test(true).to_file_vec()?);
+    assert_eq!(vec!["let b = [0];", "no"], test(false).to_file_vec()?);
+
+    // An `else` which isn't followed by an arrow is part of the branch.
+    let a = false;
+    let output: rust::Tokens = quote!($(if !a => foo else bar));
+    assert_eq!("foo else bar", output.to_string()?);
+    Ok(())
+}
+
 #[test]
 fn test_match() {
     enum Alt {
@@ -547,3 +566,508 @@ fn test_lines() -> fmt::Result {
 
     Ok(())
 }
+
+#[test]
+fn test_append_str() {
+    static HELLO: &str = "hello";
+
+    let world = String::from("world");
+
+    let mut tokens = Tokens::<()>::new();
+    tokens.append_str(Static(HELLO));
+    tokens.space();
+    tokens.append_str(&world[..]);
+    tokens.space();
+    tokens.append_str(world);
+
+    assert_eq!("hello world world", tokens.to_string().unwrap());
+
+    // Static strings are stored as-is without being copied.
+    match tokens.iter().next() {
+        Some(Literal(Static(s))) => assert!(std::ptr::eq(*s, HELLO)),
+        other => panic!("expected static literal, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_replace_literal() {
+    let map = rust::import("std::collections", "HashMap");
+
+    let mut tokens: rust::Tokens = quote! {
+        struct __NAME__ {
+            __NAME___map: $map<u32, String>,
+        }
+
+        impl __NAME__ {
+            const NAME: &str = "__NAME__";
+        }
+    };
+
+    tokens.replace_literal("__NAME__", "Config");
+    tokens.replace_literal("HashMap", "BTreeMap");
+
+    assert_eq!(
+        vec![
+            "use std::collections::HashMap;",
+            "",
+            "struct Config {",
+            "    Config_map: HashMap<u32, String>,",
+            "}",
+            "",
+            "impl Config {",
+            "    const NAME: &str = \"Config\";",
+            "}",
+        ],
+        tokens.to_file_vec().unwrap()
+    );
+}
+
+#[test]
+fn test_fmt_to() -> fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+
+    let tokens: rust::Tokens = quote! {
+        fn new() -> $(&map)<u32, u32> {
+            $map::new()
+        }
+    };
+
+    let fmt = fmt::Config::from_lang::<Rust>();
+
+    let mut out = String::new();
+    tokens.fmt_to(&mut out, &fmt)?;
+    assert_eq!(tokens.to_string()?, out);
+
+    let mut out = String::new();
+    tokens.fmt_file_to(&mut out, &fmt)?;
+    assert_eq!(tokens.to_file_string()?, out);
+    Ok(())
+}
+
+#[test]
+fn test_debug_items() {
+    let tokens: Tokens = quote!(a$['\r']b);
+    assert_eq!("\"a\" <push> \"b\"", format!("{:?}", tokens.debug_items()));
+
+    let tokens: js::Tokens = quote! {
+        function greet() {
+            return $[str](hello $name);
+        }$['\n']
+        greet();
+    };
+
+    assert_eq!(
+        concat!(
+            "\"function\" <space> \"greet()\" <space> \"{\" <indent 1> ",
+            "\"return\" <space> <open-quote eval> \"hello \" <open-eval> \"name\" <close-eval> <close-quote> \";\" ",
+            "<unindent 1> \"}\" <line> \"greet();\""
+        ),
+        format!("{:?}", tokens.debug_items())
+    );
+}
+
+#[test]
+fn test_append_tokens_moves() -> fmt::Result {
+    fn literals(tokens: &Tokens) -> Vec<*const u8> {
+        tokens
+            .iter()
+            .filter_map(|item| match item {
+                Literal(s) => Some(s.as_ptr()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    let mut part = Tokens::new();
+    part.append(String::from("first"));
+    part.space();
+    part.append(String::from("second"));
+    let expected = literals(&part);
+
+    // Appending into an empty stream reuses the whole stream.
+    let mut a = Tokens::new();
+    a.append_tokens(part);
+    assert_eq!(expected, literals(&a));
+
+    // Appending into a non-empty stream moves each item.
+    let mut b: Tokens = quote!(head$[' ']);
+    b.append_tokens(a);
+    assert_eq!(expected, literals(&b)[1..]);
+    assert_eq!("head first second", b.to_string()?);
+
+    // Appending by reference copies each item.
+    let mut c = Tokens::new();
+    c.append_ref(&b);
+    assert_ne!(literals(&b)[1..], literals(&c)[1..]);
+    assert_eq!(b, c);
+    Ok(())
+}
+
+#[test]
+fn test_count_lines() -> fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+    let debug = rust::import("std::fmt", "Debug");
+    let raw_sql = "SELECT *\n  FROM users";
+
+    let tokens: rust::Tokens = quote! {
+        pub fn first() -> $(&map)<u32, u32> {
+            $(&map)::new()
+        }
+
+        pub fn second(value: &dyn $debug) {
+            println!("{:?}", value);
+
+
+            let sql = $(raw raw_sql);
+        }
+
+        pub fn third() {}
+    };
+
+    let config = rust::Config::default();
+    assert_eq!(tokens.to_file_vec()?.len(), tokens.count_lines(&config)?);
+    assert_eq!(15, tokens.count_lines(&config)?);
+
+    let empty = rust::Tokens::new();
+    assert_eq!(empty.to_file_vec()?.len(), empty.count_lines(&config)?);
+
+    let literal: rust::Tokens = quote!(let x = $("a\nb"););
+    assert_eq!(literal.to_file_vec()?.len(), literal.count_lines(&config)?);
+    Ok(())
+}
+
+#[test]
+fn test_final_newline() -> fmt::Result {
+    let tokens: js::Tokens = quote! {
+        function foo() {
+            return 1;
+        }
+    };
+
+    let mut out = String::new();
+    tokens.fmt_file_to(&mut out, &fmt::Config::from_lang::<JavaScript>())?;
+    assert!(out.ends_with("}\n"));
+
+    let config = fmt::Config::from_lang::<JavaScript>().with_final_newline(false);
+
+    let mut out = String::new();
+    tokens.fmt_file_to(&mut out, &config)?;
+    assert_eq!("function foo() {\n    return 1;\n}", out);
+    assert_ne!(Some('\n'), out.chars().last());
+
+    let mut out = Vec::new();
+    let mut w = fmt::IoWriter::new(&mut out);
+    tokens.format_file(&mut w.as_formatter(&config), &js::Config::default())?;
+    assert_eq!(b"function foo() {\n    return 1;\n}", &out[..]);
+    Ok(())
+}
+
+#[test]
+fn test_lines_iter() -> fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+    let raw_sql = "SELECT *\n  FROM users";
+
+    let tokens: rust::Tokens = quote! {
+        pub fn first() -> $(&map)<u32, u32> {
+            let sql = $(raw raw_sql);
+            $(&map)::new()
+        }
+
+        pub fn second() {}
+    };
+
+    let config = rust::Config::default();
+    let lines = tokens.lines(&config)?.collect::<Vec<_>>();
+    assert_eq!(tokens.to_file_vec()?, lines);
+    assert_eq!(9, lines.len());
+
+    let mut empty = rust::Tokens::new().lines(&config)?;
+    assert_eq!(Some(String::new()), empty.next());
+    assert_eq!(None, empty.next());
+
+    // Line breaks in literals don't split lines, like in `to_file_vec`.
+    let tokens: rust::Tokens = quote!(let x = $("a\nb"););
+    assert_eq!(vec!["let x = a\nb;"], tokens.to_file_vec()?);
+    assert_eq!(
+        tokens.to_file_vec()?,
+        tokens.lines(&config)?.collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_max_blank_lines() -> fmt::Result {
+    let tokens: js::Tokens = quote! {
+        function foo() {
+            $(raw "a();\n\n\n\n\n\nb();")
+        }
+    };
+
+    let config = fmt::Config::from_lang::<JavaScript>().with_max_blank_lines(1);
+
+    let mut w = fmt::VecWriter::new();
+    tokens.format_file(&mut w.as_formatter(&config), &js::Config::default())?;
+
+    assert_eq!(
+        vec!["function foo() {", "    a();", "", "b();", "}"],
+        w.into_vec()
+    );
+
+    let config = fmt::Config::from_lang::<JavaScript>().with_max_blank_lines(0);
+
+    let mut out = String::new();
+    tokens.fmt_file_to(&mut out, &config)?;
+    assert_eq!("function foo() {\n    a();\nb();\n}\n", out);
+
+    let mut out = String::new();
+    tokens.fmt_file_to(&mut out, &fmt::Config::from_lang::<JavaScript>())?;
+    assert_eq!("function foo() {\n    a();\n\n\n\n\n\nb();\n}\n", out);
+    Ok(())
+}
+
+#[test]
+fn test_base_indent() -> fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+
+    let tokens: rust::Tokens = quote! {
+        fn foo() -> $(&map)<u32, u32> {
+            let m = $map::new();
+
+            m
+        }
+    };
+
+    let config = fmt::Config::from_lang::<Rust>().with_base_indent(2);
+
+    let mut w = fmt::VecWriter::new();
+    tokens.format_file(&mut w.as_formatter(&config), &rust::Config::default())?;
+
+    assert_eq!(
+        vec![
+            "        use std::collections::HashMap;",
+            "",
+            "        fn foo() -> HashMap<u32, u32> {",
+            "            let m = HashMap::new();",
+            "",
+            "            m",
+            "        }",
+        ],
+        w.into_vec()
+    );
+
+    let config = fmt::Config::from_lang::<Rust>()
+        .with_indentation(fmt::Indentation::Tab)
+        .with_base_indent(1);
+
+    let mut out = String::new();
+    tokens.fmt_to(&mut out, &config)?;
+    assert_eq!(
+        "\tfn foo() -> HashMap<u32, u32> {\n\t\tlet m = HashMap::new();\n\n\t\tm\n\t}",
+        out
+    );
+    Ok(())
+}
+
+#[test]
+fn test_comment_out() -> fmt::Result {
+    let mut tokens: rust::Tokens = quote! {
+        first();
+        second();
+    };
+
+    tokens.comment_out()?;
+    assert_eq!(vec!["// first();", "// second();"], tokens.to_file_vec()?);
+
+    let mut tokens: python::Tokens = quote! {
+        if x:
+            pass
+    };
+
+    tokens.comment_out()?;
+    assert_eq!(vec!["# if x:", "#     pass"], tokens.to_file_vec()?);
+
+    let mut tokens: rust::Tokens = quote! {
+        let s = $("r\"a\nb\"");
+    };
+
+    tokens.comment_out()?;
+    assert_eq!(vec!["// let s = r\"a", "// b\";"], tokens.to_file_vec()?);
+
+    let mut empty = rust::Tokens::new();
+    empty.comment_out()?;
+    assert!(empty.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_append_spaced() -> fmt::Result {
+    let mut tokens = rust::Tokens::new();
+    tokens.append_spaced("a").append_spaced("b");
+    assert_eq!("a b", tokens.to_string()?);
+
+    tokens.space();
+    tokens.append_spaced("c");
+    tokens.indent();
+    tokens.append_spaced("d");
+    assert_eq!(vec!["a b c", "    d"], tokens.to_file_vec()?);
+    Ok(())
+}
+
+#[test]
+fn test_post_format() -> fmt::Result {
+    use genco::lang::Lang;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Trimmed;
+
+    impl Lang for Trimmed {
+        type Config = ();
+        type Format = ();
+        type Item = ();
+
+        fn default_config() -> Self::Config {}
+
+        fn post_format(output: String, _: &()) -> String {
+            let mut trimmed = String::new();
+
+            for line in output.split_inclusive('\n') {
+                trimmed.push_str(line.trim_end());
+
+                if line.ends_with('\n') {
+                    trimmed.push('\n');
+                }
+            }
+
+            trimmed
+        }
+    }
+
+    let tokens: Tokens<Trimmed> = quote! {
+        $("foo  ")
+        $(raw "bar\t\nbaz ")
+
+        qux
+        $("a \nb ")
+    };
+
+    assert_eq!("foo\nbar\nbaz\n\nqux\na\nb\n", tokens.to_file_string()?);
+
+    // Line-based output is not post-processed.
+    let expected = vec!["foo  ", "bar\t", "baz ", "", "qux", "a \nb "];
+    assert_eq!(expected, tokens.to_file_vec()?);
+    assert_eq!(expected, tokens.lines(&())?.collect::<Vec<_>>());
+    assert_eq!(expected.len(), tokens.count_lines(&())?);
+
+    let dir = std::env::temp_dir().join(format!("genco-post-format-{}", std::process::id()));
+    let mut files = genco::FileSet::<Trimmed>::new();
+    files.add("out.txt", tokens.clone());
+    files.write_to_dir(&dir, &()).unwrap();
+    let written = std::fs::read_to_string(dir.join("out.txt")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(tokens.to_file_string()?, written);
+
+    let mut out = String::new();
+    tokens.fmt_file_to(&mut out, &fmt::Config::from_lang::<Trimmed>())?;
+    assert_eq!(tokens.to_file_string()?, out);
+
+    let tokens: Tokens = quote!($("foo  "));
+    assert_eq!("foo  \n", tokens.to_file_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_trim() -> fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+    let set = rust::import("std::collections", "HashSet");
+
+    let mut fragment: rust::Tokens = quote!($[' ']$map::new()$[' ']$set::new());
+    fragment.line();
+    fragment.trim_start();
+    fragment.trim_end();
+
+    let tokens: rust::Tokens = quote!(let a = ($(&fragment)););
+
+    assert_eq!(
+        vec![
+            "use std::collections::{HashMap, HashSet};",
+            "",
+            "let a = (HashMap::new() HashSet::new());",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    assert_eq!(2, fragment.walk_imports().count());
+
+    let mut tokens: rust::Tokens = quote! {
+        fn foo() {
+            bar();
+        }
+    };
+
+    tokens.trim_start();
+    tokens.trim_end();
+    assert_eq!(vec!["fn foo() {", "    bar();", "}"], tokens.to_file_vec()?);
+
+    let mut tokens = rust::Tokens::new();
+    tokens.trim_start();
+    tokens.trim_end();
+    assert!(tokens.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_append_separated() -> fmt::Result {
+    let mut first: rust::Tokens = quote!(let a = 1;);
+    first.push();
+    let second: rust::Tokens = quote!(let b = 2;);
+    let third: rust::Tokens = quote!(let c = 3;);
+
+    let mut tokens = rust::Tokens::new();
+    tokens.append_separated(first);
+    tokens.append_separated(second.clone());
+    tokens.append_separated(rust::Tokens::new());
+    tokens.append_separated(third);
+
+    assert_eq!(
+        vec!["let a = 1;", "let b = 2;", "let c = 3;"],
+        tokens.to_file_vec()?
+    );
+
+    let mut joined = rust::Tokens::new();
+    joined.append_tokens(second.clone());
+    joined.append_tokens(second);
+    assert_eq!("let b = 2;let b = 2;", joined.to_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_eq_ignoring_whitespace() {
+    let list = java::import("java.util", "List");
+
+    let compact: java::Tokens = quote! {
+        class A { $(&list)<String> names() { return $(quoted("a b")); } }
+    };
+
+    let spaced: java::Tokens = quote! {
+        class A {
+            $(&list)<String> names() {
+                return $(quoted("a b"));
+            }
+        }
+    };
+
+    let other_string: java::Tokens = quote! {
+        class A { $(&list)<String> names() { return $(quoted("ab")); } }
+    };
+
+    let other_import: java::Tokens = quote! {
+        class A { $(java::import("java.awt", "List"))<String> names() { return $(quoted("a b")); } }
+    };
+
+    assert_ne!(compact, spaced);
+    assert!(compact.eq_ignoring_whitespace(&spaced));
+    assert!(spaced.eq_ignoring_whitespace(&compact));
+    assert!(!compact.eq_ignoring_whitespace(&other_string));
+    assert!(!compact.eq_ignoring_whitespace(&other_import));
+    assert!(java::Tokens::new().eq_ignoring_whitespace(&quote!($['\n'])));
+}
@@ -0,0 +1,38 @@
+use genco::prelude::*;
+
+#[derive(Debug, PartialEq, Eq)]
+struct Error(&'static str);
+
+fn fragment(ok: bool) -> Result<rust::Tokens, Error> {
+    if !ok {
+        return Err(Error("bad fragment"));
+    }
+
+    Ok(quote!(fragment()))
+}
+
+fn build(ok: bool) -> Result<rust::Tokens, Error> {
+    Ok(quote! {
+        fn test() {
+            $(try fragment(ok));
+        }
+    })
+}
+
+#[test]
+fn test_try_ok() -> genco::fmt::Result {
+    let tokens = build(true).unwrap();
+
+    assert_eq!(
+        vec!["fn test() {", "    fragment();", "}"],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+/// An `Err` fragment propagates out of the enclosing function.
+#[test]
+fn test_try_err() {
+    assert_eq!(Error("bad fragment"), build(false).unwrap_err());
+}
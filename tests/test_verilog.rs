@@ -0,0 +1,61 @@
+use genco::prelude::*;
+
+#[test]
+fn test_module_with_sized_literal() -> genco::fmt::Result {
+    let ports = [
+        verilog::input("clk"),
+        verilog::input("rst_n").with_type("wire"),
+        verilog::output("mask").with_type("reg").with_width(8),
+    ];
+
+    let tokens: verilog::Tokens = quote! {
+        // reset to all ones
+        $(verilog::module("mask_reg", ports, quote! {
+            always @(posedge clk or negedge rst_n) begin
+                if (!rst_n)
+                    mask <= $(verilog::literal(8, 0xff));
+                else
+                    mask <= $(verilog::literal(8, 0x100));
+            end
+        }))
+    };
+
+    assert_eq!(
+        vec![
+            "module mask_reg (",
+            "    input clk,",
+            "    input wire rst_n,",
+            "    output reg [7:0] mask",
+            ");",
+            "    always @(posedge clk or negedge rst_n) begin",
+            "        if (!rst_n)",
+            "            mask <= 8'hFF;",
+            "        else",
+            "            mask <= 8'h00;",
+            "    end",
+            "endmodule",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_literal_bases() -> genco::fmt::Result {
+    use verilog::Base;
+
+    let tokens: verilog::Tokens = quote! {
+        $(verilog::literal(1, 1).with_base(Base::Binary))
+        $(verilog::literal(7, 5).with_base(Base::Octal))
+        $(verilog::literal(64, u64::MAX))
+        $(verilog::literal(70, 1).with_base(Base::Decimal))
+    };
+
+    assert_eq!(
+        vec!["1'b1", "7'o005", "64'hFFFFFFFFFFFFFFFF", "70'd1"],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use genco::prelude::*;
+
+#[test]
+fn test_write_into_file() -> std::io::Result<()> {
+    let dir = std::env::temp_dir().join(format!("genco-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("generated.rs");
+
+    let map = rust::import("std::collections", "HashMap");
+
+    let tokens: rust::Tokens = quote! {
+        fn main() {
+            let mut m = $map::new();
+            m.insert(1u32, 2u32);
+        }
+    };
+
+    std::fs::write(
+        &path,
+        "stale contents that are longer than the output\n".repeat(10),
+    )?;
+    tokens.write_into_file(&path, &rust::Config::default())?;
+
+    let expected = tokens.to_file_string().expect("formatting failed");
+    assert_eq!(expected, std::fs::read_to_string(&path)?);
+
+    let entries = std::fs::read_dir(&dir)?.count();
+    std::fs::remove_dir_all(&dir)?;
+    assert_eq!(1, entries, "temporary file was left behind");
+    Ok(())
+}
+
+#[test]
+fn test_write_if_changed() -> std::io::Result<()> {
+    let dir = std::env::temp_dir().join(format!("genco-test-if-changed-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("version.rs");
+    let config = rust::Config::default();
+
+    let tokens: rust::Tokens = quote!(
+        pub const VERSION: u32 = 1;
+    );
+    assert!(tokens.write_if_changed(&path, &config)?);
+
+    // Backdate the file so that any rewrite would be observable.
+    let old = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+    std::fs::File::options()
+        .write(true)
+        .open(&path)?
+        .set_modified(old)?;
+
+    assert!(!tokens.write_if_changed(&path, &config)?);
+    assert_eq!(old, std::fs::metadata(&path)?.modified()?);
+
+    let tokens: rust::Tokens = quote!(
+        pub const VERSION: u32 = 2;
+    );
+    assert!(tokens.write_if_changed(&path, &config)?);
+    assert_ne!(old, std::fs::metadata(&path)?.modified()?);
+    assert_eq!(
+        tokens.to_file_string().expect("formatting failed"),
+        std::fs::read_to_string(&path)?
+    );
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_write_into_file_concurrently() -> std::io::Result<()> {
+    let dir = std::env::temp_dir().join(format!("genco-test-concurrent-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("shared.rs");
+
+    let threads = (0..8u32)
+        .map(|n| {
+            let path = path.clone();
+
+            std::thread::spawn(move || -> std::io::Result<()> {
+                let tokens: rust::Tokens = quote!(pub const N: u32 = $n;);
+
+                for _ in 0..20 {
+                    tokens.write_into_file(&path, &rust::Config::default())?;
+                }
+
+                Ok(())
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for thread in threads {
+        thread.join().expect("thread panicked")?;
+    }
+
+    let contents = std::fs::read_to_string(&path)?;
+    let entries = std::fs::read_dir(&dir)?.count();
+    std::fs::remove_dir_all(&dir)?;
+
+    assert!(contents.starts_with("pub const N: u32 = "));
+    assert_eq!(1, entries, "temporary file was left behind");
+    Ok(())
+}